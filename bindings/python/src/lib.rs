@@ -72,7 +72,7 @@ fn raise_on_error(compiled: &jsonschema::JSONSchema, instance: &PyAny) -> PyResu
     let error = if let Some(mut errors) = result.err() {
         // If we have `Err` case, then the iterator is not empty
         let error = errors.next().expect("Iterator should not be empty");
-        Some(error.to_string())
+        Some(error.message().to_string())
     } else {
         None
     };
@@ -297,6 +297,12 @@ bench!(
   valid = 4;
   invalid = 1;
 );
+bench!(
+  name = "all_of_many";
+  schema = {"allOf": [{"type": "integer"}, {"minimum": 2}, {"maximum": 10}, {"multipleOf": 2}, {"not": {"const": 6}}, {"enum": [2, 4, 8, 10]}]};
+  valid = 4;
+  invalid = "a", 3;
+);
 bench!(
   name = "any_of";
   schema = {"anyOf": [{"type": "integer"}, {"minimum": 2}]};
@@ -554,6 +560,18 @@ bench!(
   valid = "foo";
   invalid = 1;
 );
+bench!(
+  name = "type_single_item_array";
+  schema = {"type": ["string"]};
+  valid = "foo";
+  invalid = 1;
+);
+bench!(
+  name = "type_items";
+  schema = {"items": {"type": "integer"}};
+  valid = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20];
+  invalid = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, "foo"];
+);
 bench!(
   name = "type_multiple";
   schema = {"type": ["integer", "string"]};
@@ -580,6 +598,7 @@ criterion_group!(
     bench_additional_properties_false_and_pattern_properties,
     bench_additional_properties_false_and_properties_and_pattern_properties,
     bench_all_of,
+    bench_all_of_many,
     bench_any_of,
     bench_any_of_multiple_types,
     bench_boolean_false,
@@ -623,6 +642,8 @@ criterion_group!(
     bench_required,
     bench_type_integer,
     bench_type_string,
+    bench_type_single_item_array,
+    bench_type_items,
     bench_type_multiple,
     bench_unique_items,
 );
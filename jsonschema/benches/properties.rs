@@ -0,0 +1,85 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use jsonschema::JSONSchema;
+use serde_json::{json, Map, Value};
+
+const SIZES: &[usize] = &[10, 100, 1000];
+
+/// A schema with `size` integer properties, all of them required.
+fn schema(size: usize) -> Value {
+    let properties: Map<String, Value> = (0..size)
+        .map(|idx| {
+            (
+                format!("p{}", idx),
+                json!({"type": "integer", "minimum": 0}),
+            )
+        })
+        .collect();
+    let required: Vec<_> = properties.keys().cloned().collect();
+    json!({"type": "object", "properties": properties, "required": required})
+}
+
+/// An instance for a schema of `size` properties, where the first `invalid` properties fail.
+fn instance(size: usize, invalid: usize) -> Value {
+    let object: Map<String, Value> = (0..size)
+        .map(|idx| {
+            let value = if idx < invalid { json!(-1) } else { json!(idx) };
+            (format!("p{}", idx), value)
+        })
+        .collect();
+    Value::Object(object)
+}
+
+fn compile(c: &mut Criterion) {
+    let mut group = c.benchmark_group("properties compile");
+    for &size in SIZES {
+        let schema = schema(size);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &schema, |b, schema| {
+            b.iter(|| JSONSchema::compile(schema).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn is_valid(c: &mut Criterion) {
+    let mut group = c.benchmark_group("properties is_valid valid");
+    for &size in SIZES {
+        let schema = schema(size);
+        let validator = JSONSchema::compile(&schema).unwrap();
+        let instance = black_box(instance(size, 0));
+        assert!(validator.is_valid(&instance));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(size),
+            &instance,
+            |b, instance| b.iter(|| validator.is_valid(instance)),
+        );
+    }
+    group.finish();
+}
+
+fn validate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("properties validate invalid");
+    for &size in SIZES {
+        let schema = schema(size);
+        let validator = JSONSchema::compile(&schema).unwrap();
+        for &invalid in &[1, 10] {
+            let instance = black_box(instance(size, invalid));
+            assert!(!validator.is_valid(&instance));
+            group.bench_with_input(
+                BenchmarkId::new(
+                    format!("{} properties", size),
+                    format!("{} failing", invalid),
+                ),
+                &instance,
+                |b, instance| {
+                    b.iter(|| {
+                        let _: Vec<_> = validator.validate(instance).unwrap_err().collect();
+                    })
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(properties, compile, is_valid, validate);
+criterion_main!(properties);
@@ -0,0 +1,21 @@
+#![no_main]
+use jsonschema::JSONSchema;
+use libfuzzer_sys::{arbitrary::Arbitrary, fuzz_target};
+
+#[derive(Arbitrary, Debug)]
+struct Input<'a> {
+    schema: &'a [u8],
+    instance: &'a [u8],
+}
+
+fuzz_target!(|input: Input| {
+    if let Ok(schema) = serde_json::from_slice(input.schema) {
+        // Compilation should either succeed or return an error, but never panic
+        if let Ok(compiled) = JSONSchema::compile(&schema) {
+            if let Ok(instance) = serde_json::from_slice(input.instance) {
+                let is_valid = compiled.is_valid(&instance);
+                assert_eq!(compiled.validate(&instance).is_ok(), is_valid);
+            }
+        }
+    }
+});
@@ -0,0 +1,54 @@
+#![no_main]
+use jsonschema::JSONSchema;
+use lazy_static::lazy_static;
+use libfuzzer_sys::fuzz_target;
+use serde_json::{json, Value};
+
+lazy_static! {
+    static ref SCHEMAS: Vec<JSONSchema<'static>> = vec![
+        json!({"type": "integer", "minimum": 0, "multipleOf": 3}),
+        json!({"type": "string", "minLength": 2, "maxLength": 10, "pattern": "^[a-z]+$"}),
+        json!({"type": "array", "items": {"type": "number"}, "uniqueItems": true, "contains": {"const": 1}}),
+        json!({
+            "type": "object",
+            "properties": {"a": {"type": "string"}},
+            "patternProperties": {"^b": {"type": "boolean"}},
+            "additionalProperties": {"type": "null"},
+            "required": ["a"],
+            "propertyNames": {"maxLength": 3},
+            "dependencies": {"a": ["c"]}
+        }),
+        json!({
+            "anyOf": [{"type": "string"}, {"type": "number"}],
+            "oneOf": [{"minimum": 5}, {"maxLength": 3}],
+            "not": {"enum": [7, "foo"]}
+        }),
+        json!({
+            "definitions": {"node": {"type": "array", "items": {"$ref": "#/definitions/node"}}},
+            "if": {"type": "array"},
+            "then": {"$ref": "#/definitions/node"},
+            "else": {"format": "date-time"}
+        }),
+    ]
+    .into_iter()
+    .map(|schema| JSONSchema::compile_owned(schema).expect("Invalid schema"))
+    .collect();
+}
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(instance) = serde_json::from_slice::<Value>(data) {
+        for schema in SCHEMAS.iter() {
+            let is_valid = schema.is_valid(&instance);
+            let errors: Vec<_> = match schema.validate(&instance) {
+                Ok(_) => Vec::new(),
+                Err(errors) => errors.collect(),
+            };
+            assert_eq!(
+                is_valid,
+                errors.is_empty(),
+                "`is_valid` and `validate` disagree for {}",
+                instance
+            );
+        }
+    }
+});
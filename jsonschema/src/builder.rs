@@ -0,0 +1,133 @@
+//! Programmatic construction of JSON schemas.
+use serde_json::{Map, Value};
+
+/// A builder for JSON schemas. The resulting `Value` can be compiled with `JSONSchema::compile`.
+///
+/// ```rust
+/// # use jsonschema::{JSONSchema, SchemaBuilder};
+/// # use serde_json::json;
+/// let schema = SchemaBuilder::default()
+///     .type_("object")
+///     .property("name", SchemaBuilder::default().type_("string").min_length(3))
+///     .required(&["name"])
+///     .additional_properties(false)
+///     .build();
+/// let compiled = JSONSchema::compile(&schema).expect("A valid schema");
+/// assert!(compiled.is_valid(&json!({"name": "foo"})));
+/// assert!(!compiled.is_valid(&json!({"name": "fo"})));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SchemaBuilder {
+    schema: Map<String, Value>,
+}
+
+impl SchemaBuilder {
+    /// Set the `type` keyword, e.g. `"string"`.
+    #[inline]
+    pub fn type_(&mut self, type_: &str) -> &mut Self {
+        self.set("type", type_.into())
+    }
+
+    /// Set the `minLength` keyword.
+    #[inline]
+    pub fn min_length(&mut self, limit: u64) -> &mut Self {
+        self.set("minLength", limit.into())
+    }
+
+    /// Set the `maxLength` keyword.
+    #[inline]
+    pub fn max_length(&mut self, limit: u64) -> &mut Self {
+        self.set("maxLength", limit.into())
+    }
+
+    /// Set the `pattern` keyword.
+    #[inline]
+    pub fn pattern(&mut self, pattern: &str) -> &mut Self {
+        self.set("pattern", pattern.into())
+    }
+
+    /// Add property names to the `required` keyword.
+    pub fn required(&mut self, properties: &[&str]) -> &mut Self {
+        let required = self
+            .schema
+            .entry("required")
+            .or_insert_with(|| Value::Array(Vec::with_capacity(properties.len())));
+        if let Value::Array(items) = required {
+            items.extend(properties.iter().map(|&property| property.into()));
+        }
+        self
+    }
+
+    /// Add a sub-schema for `name` to the `properties` keyword.
+    pub fn property(&mut self, name: &str, schema: &SchemaBuilder) -> &mut Self {
+        let properties = self
+            .schema
+            .entry("properties")
+            .or_insert_with(|| Value::Object(Map::new()));
+        if let Value::Object(properties) = properties {
+            properties.insert(name.to_string(), schema.build());
+        }
+        self
+    }
+
+    /// Set the `additionalProperties` keyword to a boolean schema.
+    #[inline]
+    pub fn additional_properties(&mut self, allowed: bool) -> &mut Self {
+        self.set("additionalProperties", allowed.into())
+    }
+
+    /// Build the schema.
+    #[must_use]
+    #[inline]
+    pub fn build(&self) -> Value {
+        Value::Object(self.schema.clone())
+    }
+
+    #[inline]
+    fn set(&mut self, keyword: &str, value: Value) -> &mut Self {
+        self.schema.insert(keyword.to_string(), value);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SchemaBuilder;
+    use serde_json::json;
+
+    #[test]
+    fn build() {
+        let schema = SchemaBuilder::default()
+            .type_("object")
+            .property(
+                "name",
+                SchemaBuilder::default()
+                    .type_("string")
+                    .min_length(1)
+                    .max_length(100)
+                    .pattern("^[a-z]+$"),
+            )
+            .property("email", SchemaBuilder::default().type_("string"))
+            .required(&["name"])
+            .required(&["email"])
+            .additional_properties(false)
+            .build();
+        assert_eq!(
+            schema,
+            json!({
+                "type": "object",
+                "properties": {
+                    "name": {"type": "string", "minLength": 1, "maxLength": 100, "pattern": "^[a-z]+$"},
+                    "email": {"type": "string"}
+                },
+                "required": ["name", "email"],
+                "additionalProperties": false
+            })
+        );
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(SchemaBuilder::default().build(), json!({}));
+    }
+}
@@ -0,0 +1,652 @@
+//! Schema evolution checks and diffs.
+//!
+//! Whether every instance valid under one schema is valid under another is undecidable in general.
+//! The checks here are conservative - they recognize common changes like adding optional
+//! properties, widening types or relaxing limits, and report anything else as incompatible.
+//! Local references are compared by their targets.
+use crate::{compilation::JSONSchema, schemas::ANNOTATION_KEYWORDS};
+use serde_json::{Map, Value};
+
+/// Compatibility checks between versions of the same schema.
+#[derive(Debug, Clone, Copy)]
+pub struct SchemaCompatibility;
+
+impl SchemaCompatibility {
+    /// Whether every instance valid under `old` is also valid under `new`.
+    ///
+    /// The check is conservative - `false` means that compatibility could not be proven, not
+    /// that an incompatible instance exists.
+    ///
+    /// ```rust
+    /// # use jsonschema::{JSONSchema, SchemaCompatibility};
+    /// # use serde_json::json;
+    /// let old = json!({"properties": {"name": {"type": "string"}}, "required": ["name"]});
+    /// let new = json!({"properties": {"name": {"type": ["string", "null"]}}});
+    /// let old = JSONSchema::compile(&old).expect("A valid schema");
+    /// let new = JSONSchema::compile(&new).expect("A valid schema");
+    /// assert!(SchemaCompatibility::is_backward_compatible(&old, &new));
+    /// // Some instances valid under `new` are not valid under `old`
+    /// assert!(!SchemaCompatibility::is_backward_compatible(&new, &old));
+    /// ```
+    #[must_use]
+    pub fn is_backward_compatible(old: &JSONSchema, new: &JSONSchema) -> bool {
+        Documents::new(&old.schema, &new.schema).is_subschema(&old.schema, &new.schema)
+    }
+}
+
+/// Whether `keyword` does not affect validation.
+/// Definitions are used only through references, which are compared by their targets. Identifiers
+/// change how references are resolved, therefore they are compared as any other keyword.
+fn is_ignored(keyword: &str) -> bool {
+    !matches!(keyword, "$id" | "id") && ANNOTATION_KEYWORDS.contains(&keyword)
+}
+
+/// The old and the new root schemas, where local references are resolved.
+struct Documents<'s> {
+    old: &'s Value,
+    new: &'s Value,
+    /// Whether references can be resolved as JSON pointers from the roots, i.e. there are no
+    /// sub-schemas with their own identifiers that change the base of references within them.
+    resolvable: bool,
+    /// Pairs of references whose targets are being compared. They are assumed to be compatible
+    /// while the comparison is in progress, which stops the recursion in recursive schemas.
+    assumed: Vec<(&'s str, &'s str)>,
+}
+
+impl<'s> Documents<'s> {
+    fn new(old: &'s Value, new: &'s Value) -> Documents<'s> {
+        Documents {
+            old,
+            new,
+            resolvable: !has_nested_id(old) && !has_nested_id(new),
+            assumed: Vec::new(),
+        }
+    }
+
+    /// Whether `old` accepts a subset of instances accepted by `new`.
+    fn is_subschema(&mut self, old: &'s Value, new: &'s Value) -> bool {
+        if old == new && self.are_references_unchanged(old) {
+            return true;
+        }
+        match (old, new) {
+            (_, Value::Bool(true)) | (Value::Bool(false), _) => true,
+            (Value::Object(old), Value::Object(new)) => new.iter().all(|(keyword, value)| {
+                is_ignored(keyword) || self.is_implied(keyword, value, old, new)
+            }),
+            (Value::Bool(true), Value::Object(new)) => {
+                new.keys().all(|keyword| is_ignored(keyword))
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether all references reachable from `schema` have equal targets in both documents.
+    fn are_references_unchanged(&self, schema: &'s Value) -> bool {
+        if self.old == self.new {
+            return true;
+        }
+        let mut stack = vec![schema];
+        let mut visited = Vec::new();
+        while let Some(value) = stack.pop() {
+            match value {
+                Value::Object(object) => {
+                    if let Some(Value::String(reference)) = object.get("$ref") {
+                        if !visited.contains(&reference) {
+                            visited.push(reference);
+                            match (
+                                self.resolve(self.old, reference),
+                                self.resolve(self.new, reference),
+                            ) {
+                                (Some(old), Some(new)) if old == new => stack.push(old),
+                                // Remote documents are the same for both schemas
+                                _ if !reference.starts_with('#') => {}
+                                _ => return false,
+                            }
+                        }
+                    }
+                    stack.extend(object.values());
+                }
+                Value::Array(items) => stack.extend(items),
+                _ => {}
+            }
+        }
+        true
+    }
+
+    /// The target of a local reference within `root`, e.g. `#/definitions/foo`.
+    fn resolve(&self, root: &'s Value, reference: &str) -> Option<&'s Value> {
+        if !self.resolvable {
+            return None;
+        }
+        let fragment = reference.strip_prefix('#')?;
+        let pointer = percent_encoding::percent_decode_str(fragment)
+            .decode_utf8()
+            .ok()?;
+        root.pointer(&pointer)
+    }
+
+    /// Whether the target of `new` accepts all instances accepted by the target of `old`.
+    fn is_reference_implied(&mut self, old: &'s str, new: &'s str) -> bool {
+        if self.assumed.contains(&(old, new)) {
+            return true;
+        }
+        match (self.resolve(self.old, old), self.resolve(self.new, new)) {
+            (Some(old_target), Some(new_target)) => {
+                self.assumed.push((old, new));
+                let result = self.is_subschema(old_target, new_target);
+                self.assumed.pop();
+                result
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether the constraint from `new` is implied by constraints of `old`.
+    fn is_implied(
+        &mut self,
+        keyword: &str,
+        value: &'s Value,
+        old: &'s Map<String, Value>,
+        new: &'s Map<String, Value>,
+    ) -> bool {
+        // Unchanged references are still compared by their targets below
+        if keyword != "$ref"
+            && old.get(keyword) == Some(value)
+            && self.are_references_unchanged(value)
+        {
+            return true;
+        }
+        match keyword {
+            "$ref" => match (old.get("$ref"), value) {
+                (Some(Value::String(old_reference)), Value::String(new_reference)) => {
+                    self.is_reference_implied(old_reference, new_reference)
+                }
+                _ => false,
+            },
+            "type" => match old.get("type") {
+                Some(old_types) => types(old_types).iter().all(|old_type| {
+                    types(value).iter().any(|new_type| {
+                        new_type == old_type || (*new_type == "number" && *old_type == "integer")
+                    })
+                }),
+                None => false,
+            },
+            "required" => match (value, old.get("required")) {
+                (Value::Array(new_required), Some(Value::Array(old_required))) => new_required
+                    .iter()
+                    .all(|property| old_required.contains(property)),
+                (Value::Array(new_required), None) => new_required.is_empty(),
+                _ => false,
+            },
+            "properties" => match value {
+                Value::Object(new_properties) => {
+                    new_properties.iter().all(|(name, new_subschema)| {
+                        match old
+                            .get("properties")
+                            .and_then(|properties| properties.get(name))
+                        {
+                            Some(old_subschema) => self.is_subschema(old_subschema, new_subschema),
+                            // Not defined in the old schema - possible only if it is disallowed there
+                            None => {
+                                matches!(old.get("additionalProperties"), Some(Value::Bool(false)))
+                            }
+                        }
+                    })
+                }
+                _ => false,
+            },
+            "additionalProperties" => {
+                let new_names = property_names(new);
+                match old.get("additionalProperties") {
+                    // Properties that are not in `new` could appear in old instances only if they
+                    // are defined in `old`
+                    Some(Value::Bool(false)) => {
+                        !old.contains_key("patternProperties")
+                            && !new.contains_key("patternProperties")
+                            && property_names(old)
+                                .iter()
+                                .all(|name| new_names.contains(name))
+                    }
+                    Some(old_additional) => {
+                        !old.contains_key("patternProperties")
+                            && !new.contains_key("patternProperties")
+                            && property_names(old) == new_names
+                            && self.is_subschema(old_additional, value)
+                    }
+                    None => self.is_subschema(&Value::Bool(true), value),
+                }
+            }
+            "items" => match old.get("items") {
+                // The array form validates items by position and it is not compared
+                Some(old_items) if !old_items.is_array() && !value.is_array() => {
+                    self.is_subschema(old_items, value)
+                }
+                None => self.is_subschema(&Value::Bool(true), value),
+                _ => false,
+            },
+            "minimum" | "minLength" | "minItems" | "minProperties" | "exclusiveMinimum" => {
+                is_lower_bound_relaxed(old.get(keyword), value)
+            }
+            "maximum" | "maxLength" | "maxItems" | "maxProperties" | "exclusiveMaximum" => {
+                is_upper_bound_relaxed(old.get(keyword), value)
+            }
+            "enum" => match value {
+                Value::Array(new_options) => match (old.get("enum"), old.get("const")) {
+                    (Some(Value::Array(old_options)), _) => old_options
+                        .iter()
+                        .all(|option| new_options.contains(option)),
+                    (_, Some(old_const)) => new_options.contains(old_const),
+                    _ => false,
+                },
+                _ => false,
+            },
+            "const" => match (old.get("const"), old.get("enum")) {
+                (Some(old_const), _) => old_const == value,
+                (_, Some(Value::Array(old_options))) => {
+                    !old_options.is_empty() && old_options.iter().all(|option| option == value)
+                }
+                _ => false,
+            },
+            // Anything else is compatible only if it is unchanged
+            _ => false,
+        }
+    }
+}
+
+/// Whether some sub-schema of `schema` has its own identifier.
+fn has_nested_id(schema: &Value) -> bool {
+    let mut stack: Vec<_> = match schema {
+        Value::Object(object) => object.values().collect(),
+        Value::Array(items) => items.iter().collect(),
+        _ => return false,
+    };
+    while let Some(value) = stack.pop() {
+        match value {
+            Value::Object(object) => {
+                if ["$id", "id"]
+                    .iter()
+                    .any(|keyword| matches!(object.get(*keyword), Some(Value::String(_))))
+                {
+                    return true;
+                }
+                stack.extend(object.values());
+            }
+            Value::Array(items) => stack.extend(items),
+            _ => {}
+        }
+    }
+    false
+}
+
+fn is_lower_bound_relaxed(old: Option<&Value>, new: &Value) -> bool {
+    match (old.and_then(Value::as_f64), new.as_f64()) {
+        (Some(old), Some(new)) => new <= old,
+        _ => false,
+    }
+}
+
+fn is_upper_bound_relaxed(old: Option<&Value>, new: &Value) -> bool {
+    match (old.and_then(Value::as_f64), new.as_f64()) {
+        (Some(old), Some(new)) => new >= old,
+        _ => false,
+    }
+}
+
+fn types(value: &Value) -> Vec<&str> {
+    match value {
+        Value::String(type_) => vec![type_.as_str()],
+        Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+        _ => vec![],
+    }
+}
+
+fn property_names(schema: &Map<String, Value>) -> Vec<&str> {
+    let mut names: Vec<_> = match schema.get("properties") {
+        Some(Value::Object(properties)) => properties.keys().map(String::as_str).collect(),
+        _ => vec![],
+    };
+    names.sort_unstable();
+    names
+}
+
+/// How a schema change affects the set of valid instances.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// The same instances are valid, e.g. only annotations like `title` were changed.
+    Equivalent,
+    /// Some previously valid instances are no longer valid, e.g. `maxLength` was decreased.
+    Narrowing,
+    /// All previously valid instances are still valid, e.g. `type` was extended with `null`.
+    Widening,
+    /// Neither of the above could be proven, e.g. `type` was replaced with another one.
+    Incompatible,
+}
+
+/// A change of a single top-level keyword.
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeywordChange {
+    /// The keyword is present only in the new schema.
+    Added {
+        /// Keyword name.
+        keyword: String,
+        /// Its value in the new schema.
+        value: Value,
+    },
+    /// The keyword is present only in the old schema.
+    Removed {
+        /// Keyword name.
+        keyword: String,
+        /// Its value in the old schema.
+        value: Value,
+    },
+    /// The keyword has different values in the old and the new schemas.
+    Changed {
+        /// Keyword name.
+        keyword: String,
+        /// Its value in the old schema.
+        old: Value,
+        /// Its value in the new schema.
+        new: Value,
+    },
+}
+
+impl KeywordChange {
+    /// The name of the changed keyword.
+    #[must_use]
+    pub fn keyword(&self) -> &str {
+        match self {
+            KeywordChange::Added { keyword, .. }
+            | KeywordChange::Removed { keyword, .. }
+            | KeywordChange::Changed { keyword, .. } => keyword,
+        }
+    }
+}
+
+/// Differences between two versions of a schema. See `JSONSchema::diff`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaDiff {
+    changes: Vec<KeywordChange>,
+    kind: ChangeKind,
+}
+
+impl SchemaDiff {
+    /// Changed top-level keywords, ordered by their names.
+    /// Changes within sub-schemas are reported as a change of the containing keyword.
+    #[must_use]
+    pub fn changes(&self) -> &[KeywordChange] {
+        &self.changes
+    }
+
+    /// How the change affects the set of valid instances.
+    /// The same conservative checks as in `SchemaCompatibility` are used.
+    #[must_use]
+    pub fn kind(&self) -> ChangeKind {
+        self.kind
+    }
+
+    /// Whether the schemas are structurally equal.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl<'a> JSONSchema<'a> {
+    /// Differences between this schema and `other`, which is considered the newer version.
+    ///
+    /// ```rust
+    /// # use jsonschema::{ChangeKind, JSONSchema, KeywordChange};
+    /// # use serde_json::json;
+    /// let old = json!({"type": "string", "maxLength": 10});
+    /// let new = json!({"type": "string", "maxLength": 5});
+    /// let old = JSONSchema::compile(&old).expect("A valid schema");
+    /// let new = JSONSchema::compile(&new).expect("A valid schema");
+    /// let diff = old.diff(&new);
+    /// assert_eq!(diff.kind(), ChangeKind::Narrowing);
+    /// assert_eq!(
+    ///     diff.changes(),
+    ///     &[KeywordChange::Changed {
+    ///         keyword: "maxLength".to_string(),
+    ///         old: json!(10),
+    ///         new: json!(5)
+    ///     }]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn diff(&self, other: &JSONSchema) -> SchemaDiff {
+        let (old, new) = (&*self.schema, &*other.schema);
+        let is_widening = Documents::new(old, new).is_subschema(old, new);
+        let is_narrowing = Documents::new(new, old).is_subschema(new, old);
+        let kind = match (is_widening, is_narrowing) {
+            (true, true) => ChangeKind::Equivalent,
+            (true, false) => ChangeKind::Widening,
+            (false, true) => ChangeKind::Narrowing,
+            (false, false) => ChangeKind::Incompatible,
+        };
+        SchemaDiff {
+            changes: keyword_changes(&keywords(old), &keywords(new)),
+            kind,
+        }
+    }
+}
+
+/// Keywords of a schema, boolean schemas have none.
+fn keywords(schema: &Value) -> Map<String, Value> {
+    match schema {
+        Value::Object(object) => object.clone(),
+        _ => Map::new(),
+    }
+}
+
+fn keyword_changes(old: &Map<String, Value>, new: &Map<String, Value>) -> Vec<KeywordChange> {
+    let mut changes: Vec<_> = old
+        .iter()
+        .filter_map(|(keyword, old_value)| match new.get(keyword) {
+            Some(new_value) if new_value == old_value => None,
+            Some(new_value) => Some(KeywordChange::Changed {
+                keyword: keyword.clone(),
+                old: old_value.clone(),
+                new: new_value.clone(),
+            }),
+            None => Some(KeywordChange::Removed {
+                keyword: keyword.clone(),
+                value: old_value.clone(),
+            }),
+        })
+        .chain(
+            new.iter()
+                .filter(|(keyword, _)| !old.contains_key(*keyword))
+                .map(|(keyword, value)| KeywordChange::Added {
+                    keyword: keyword.clone(),
+                    value: value.clone(),
+                }),
+        )
+        .collect();
+    changes.sort_by(|left, right| left.keyword().cmp(right.keyword()));
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChangeKind, KeywordChange, SchemaCompatibility};
+    use crate::compilation::JSONSchema;
+    use serde_json::{json, Value};
+    use test_case::test_case;
+
+    #[test_case(&json!({"type": "string"}), &json!({"type": "string"}); "unchanged")]
+    #[test_case(&json!({"type": "string"}), &json!({}); "empty")]
+    #[test_case(&json!({"type": "string"}), &json!(true); "true schema")]
+    #[test_case(&json!(false), &json!({"type": "string"}); "false schema")]
+    #[test_case(&json!({"type": "string"}), &json!({"type": ["string", "null"]}); "widened type")]
+    #[test_case(&json!({"type": "integer"}), &json!({"type": "number"}); "integer to number")]
+    #[test_case(&json!({"type": "string", "title": "Old"}), &json!({"type": "string", "title": "New"}); "annotation")]
+    #[test_case(&json!({"required": ["a", "b"]}), &json!({"required": ["a"]}); "fewer required")]
+    #[test_case(
+        &json!({"properties": {"a": {"type": "string"}}}),
+        &json!({"properties": {"a": {"type": ["string", "null"]}}});
+        "widened property"
+    )]
+    #[test_case(
+        &json!({"properties": {"a": {"type": "string"}}, "additionalProperties": false}),
+        &json!({"properties": {"a": {"type": "string"}, "b": {"type": "integer"}}, "additionalProperties": false});
+        "new optional property"
+    )]
+    #[test_case(&json!({"items": {"maxLength": 3}}), &json!({"items": {"maxLength": 5}}); "relaxed items")]
+    #[test_case(&json!({"minLength": 3}), &json!({"minLength": 1}); "relaxed minimum")]
+    #[test_case(&json!({"maximum": 3}), &json!({"maximum": 5}); "relaxed maximum")]
+    #[test_case(&json!({"enum": [1, 2]}), &json!({"enum": [1, 2, 3]}); "more options")]
+    #[test_case(&json!({"const": 1}), &json!({"enum": [1, 2]}); "const to enum")]
+    #[test_case(
+        &json!({"$ref": "#/definitions/x", "definitions": {"x": {"type": "string"}}}),
+        &json!({"$ref": "#/definitions/x", "definitions": {"x": {"type": ["string", "null"]}}});
+        "widened definition"
+    )]
+    #[test_case(
+        &json!({"$ref": "#/definitions/x", "definitions": {"x": {"type": "string"}}}),
+        &json!({"$ref": "#/$defs/y", "$defs": {"y": {"type": "string"}}});
+        "moved definition"
+    )]
+    #[test_case(
+        &json!({"allOf": [{"$ref": "#/definitions/x"}], "definitions": {"x": {"type": "string"}}, "title": "Old"}),
+        &json!({"allOf": [{"$ref": "#/definitions/x"}], "definitions": {"x": {"type": "string"}}, "title": "New"});
+        "unchanged definition"
+    )]
+    #[test_case(
+        &json!({"$ref": "#/definitions/node", "definitions": {"node": {"properties": {"next": {"$ref": "#/definitions/node"}}, "maxProperties": 1}}}),
+        &json!({"$ref": "#/definitions/node", "definitions": {"node": {"properties": {"next": {"$ref": "#/definitions/node"}}, "maxProperties": 2}}});
+        "recursive definition"
+    )]
+    fn compatible(old: &Value, new: &Value) {
+        let old = JSONSchema::compile(old).unwrap();
+        let new = JSONSchema::compile(new).unwrap();
+        assert!(SchemaCompatibility::is_backward_compatible(&old, &new))
+    }
+
+    #[test_case(&json!({}), &json!({"type": "string"}); "added type")]
+    #[test_case(&json!({"type": ["string", "null"]}), &json!({"type": "string"}); "narrowed type")]
+    #[test_case(&json!({"type": "number"}), &json!({"type": "integer"}); "number to integer")]
+    #[test_case(&json!({"required": ["a"]}), &json!({"required": ["a", "b"]}); "more required")]
+    #[test_case(
+        &json!({"properties": {"a": {"type": "string"}}}),
+        &json!({"properties": {"a": {"type": "string"}, "b": {"type": "integer"}}});
+        "new property without additionalProperties"
+    )]
+    #[test_case(
+        &json!({"properties": {"a": {"type": "string"}}}),
+        &json!({"properties": {"a": {"type": "string"}}, "additionalProperties": false});
+        "disallowed additional properties"
+    )]
+    #[test_case(&json!({"minLength": 1}), &json!({"minLength": 3}); "stricter minimum")]
+    #[test_case(&json!({"maximum": 5}), &json!({"maximum": 3}); "stricter maximum")]
+    #[test_case(&json!({}), &json!({"maximum": 3}); "added maximum")]
+    #[test_case(&json!({"enum": [1, 2]}), &json!({"enum": [1]}); "fewer options")]
+    #[test_case(&json!({"pattern": "^a"}), &json!({"pattern": "^b"}); "changed pattern")]
+    #[test_case(&json!(true), &json!(false); "true to false")]
+    #[test_case(
+        &json!({"$ref": "#/definitions/x", "definitions": {"x": {"type": "string"}}}),
+        &json!({"$ref": "#/definitions/x", "definitions": {"x": {"type": "integer"}}});
+        "changed definition"
+    )]
+    #[test_case(
+        &json!({"properties": {"a": {"$ref": "#/definitions/x"}}, "definitions": {"x": {"type": "string"}}}),
+        &json!({"properties": {"a": {"$ref": "#/definitions/x"}}, "definitions": {"x": {"type": "string", "maxLength": 1}}});
+        "changed nested definition"
+    )]
+    #[test_case(
+        &json!({"$ref": "#/definitions/node", "definitions": {"node": {"properties": {"next": {"$ref": "#/definitions/node"}}, "maxProperties": 2}}}),
+        &json!({"$ref": "#/definitions/node", "definitions": {"node": {"properties": {"next": {"$ref": "#/definitions/node"}}, "maxProperties": 1}}});
+        "narrowed recursive definition"
+    )]
+    #[test_case(
+        &json!({"$id": "http://example.com/a.json", "$ref": "item.json"}),
+        &json!({"$id": "http://example.com/b.json", "$ref": "item.json"});
+        "changed identifier"
+    )]
+    #[test_case(
+        &json!({"items": {"$id": "http://example.com/item.json", "$ref": "#/definitions/x", "definitions": {"x": {"type": "string"}}}}),
+        &json!({"items": {"$id": "http://example.com/item.json", "$ref": "#/definitions/x", "definitions": {"x": {"type": "integer"}}}});
+        "changed definition within a nested resource"
+    )]
+    fn incompatible(old: &Value, new: &Value) {
+        let old = JSONSchema::compile(old).unwrap();
+        let new = JSONSchema::compile(new).unwrap();
+        assert!(!SchemaCompatibility::is_backward_compatible(&old, &new))
+    }
+
+    #[test_case(&json!({"type": "string"}), &json!({"type": "string"}), ChangeKind::Equivalent; "unchanged")]
+    #[test_case(&json!({"title": "Old"}), &json!({"title": "New"}), ChangeKind::Equivalent; "annotation")]
+    #[test_case(&json!({"type": "string"}), &json!({"type": ["string", "null"]}), ChangeKind::Widening; "widened type")]
+    #[test_case(&json!({"maxLength": 10}), &json!({"maxLength": 5}), ChangeKind::Narrowing; "tightened maximum")]
+    #[test_case(&json!({"required": ["a"]}), &json!({"required": ["a", "b"]}), ChangeKind::Narrowing; "added required")]
+    #[test_case(&json!({"type": "string"}), &json!({"type": "integer"}), ChangeKind::Incompatible; "replaced type")]
+    #[test_case(
+        &json!({"$ref": "#/definitions/x", "definitions": {"x": {"type": "string"}}}),
+        &json!({"$ref": "#/definitions/x", "definitions": {"x": {"type": "integer"}}}),
+        ChangeKind::Incompatible;
+        "replaced type in definition"
+    )]
+    #[test_case(
+        &json!({"$ref": "#/definitions/x", "definitions": {"x": {"type": "string"}}}),
+        &json!({"$ref": "#/definitions/x", "definitions": {"x": {"type": "string", "title": "X"}}}),
+        ChangeKind::Equivalent;
+        "annotation in definition"
+    )]
+    #[test_case(
+        &json!({"$ref": "#/definitions/x", "definitions": {"x": {"maxLength": 10}}}),
+        &json!({"$ref": "#/definitions/x", "definitions": {"x": {"maxLength": 5}}}),
+        ChangeKind::Narrowing;
+        "tightened definition"
+    )]
+    fn diff_kind(old: &Value, new: &Value, expected: ChangeKind) {
+        let old = JSONSchema::compile(old).unwrap();
+        let new = JSONSchema::compile(new).unwrap();
+        assert_eq!(old.diff(&new).kind(), expected)
+    }
+
+    #[test]
+    fn changed_definition_is_not_backward_compatible() {
+        let old = json!({
+            "properties": {"a": {"$ref": "#/definitions/x"}},
+            "definitions": {"x": {"type": "string"}}
+        });
+        let new = json!({
+            "properties": {"a": {"$ref": "#/definitions/x"}},
+            "definitions": {"x": {"type": "integer"}}
+        });
+        let old = JSONSchema::compile(&old).unwrap();
+        let new = JSONSchema::compile(&new).unwrap();
+        let instance = json!({"a": "s"});
+        assert!(old.is_valid(&instance));
+        assert!(!new.is_valid(&instance));
+        assert!(!SchemaCompatibility::is_backward_compatible(&old, &new));
+    }
+
+    #[test]
+    fn diff_changes() {
+        let old = json!({"type": "string", "minLength": 1, "title": "Name"});
+        let new = json!({"type": "string", "minLength": 2, "maxLength": 10});
+        let old = JSONSchema::compile(&old).unwrap();
+        let new = JSONSchema::compile(&new).unwrap();
+        let diff = old.diff(&new);
+        assert_eq!(
+            diff.changes(),
+            &[
+                KeywordChange::Added {
+                    keyword: "maxLength".to_string(),
+                    value: json!(10)
+                },
+                KeywordChange::Changed {
+                    keyword: "minLength".to_string(),
+                    old: json!(1),
+                    new: json!(2)
+                },
+                KeywordChange::Removed {
+                    keyword: "title".to_string(),
+                    value: json!("Name")
+                },
+            ]
+        );
+        assert_eq!(diff.kind(), ChangeKind::Narrowing);
+        assert!(old.diff(&old).is_empty());
+    }
+}
@@ -1,14 +1,18 @@
 use super::options::CompilationOptions;
-use crate::schemas;
+use crate::{
+    paths::{JSONPointer, PathChunk},
+    schemas,
+};
 use serde_json::Value;
 use std::borrow::Cow;
 use url::{ParseError, Url};
 
-/// Context holds information about used draft and current scope.
+/// Context holds information about used draft, current scope and location within the schema.
 #[derive(Debug)]
 pub(crate) struct CompilationContext<'a> {
     pub(crate) scope: Cow<'a, Url>,
     pub(crate) config: Cow<'a, CompilationOptions>,
+    pub(crate) schema_path: JSONPointer,
 }
 
 impl<'a> CompilationContext<'a> {
@@ -16,6 +20,7 @@ impl<'a> CompilationContext<'a> {
         CompilationContext {
             scope: Cow::Owned(scope),
             config,
+            schema_path: JSONPointer::default(),
         }
     }
 
@@ -36,15 +41,39 @@ impl<'a> CompilationContext<'a> {
             Ok(CompilationContext {
                 scope: Cow::Owned(scope),
                 config: Cow::Borrowed(&self.config),
+                schema_path: self.schema_path.clone(),
             })
         } else {
             Ok(CompilationContext {
                 scope: Cow::Borrowed(self.scope.as_ref()),
                 config: Cow::Borrowed(&self.config),
+                schema_path: self.schema_path.clone(),
             })
         }
     }
 
+    /// A new context for a sub-location of the current one, e.g. a keyword or an item of `allOf`.
+    /// Validators store the resulting path to report where in the schema they are defined.
+    #[inline]
+    pub(crate) fn with_path(&'a self, chunk: impl Into<PathChunk>) -> Self {
+        CompilationContext {
+            scope: Cow::Borrowed(self.scope.as_ref()),
+            config: Cow::Borrowed(&self.config),
+            schema_path: self.schema_path.extend_with(chunk),
+        }
+    }
+
+    /// A new context for a sibling keyword of the current one.
+    /// E.g. `additionalProperties` compiles the sub-schemas of `properties` it depends on.
+    #[inline]
+    pub(crate) fn with_sibling(&'a self, keyword: &str) -> Self {
+        CompilationContext {
+            scope: Cow::Borrowed(self.scope.as_ref()),
+            config: Cow::Borrowed(&self.config),
+            schema_path: self.schema_path.sibling(keyword),
+        }
+    }
+
     /// Build a new URL. Used for `ref` compilation to keep their full paths.
     pub(crate) fn build_url(&self, reference: &str) -> Result<Url, ParseError> {
         Url::options().base_url(Some(&self.scope)).parse(reference)
@@ -3,27 +3,63 @@
 //! everything needed to perform such validation in runtime.
 pub(crate) mod context;
 pub(crate) mod options;
+mod sub_schemas;
 
 use crate::{
-    error::{CompilationError, ErrorIterator},
+    error::{CompilationError, ErrorIterator, OwnedErrorIterator, ValidationError},
     keywords,
     keywords::Validators,
+    meta_schemas,
+    output::{PartialApplication, ValidationOutput},
+    paths::JSONPointer,
     resolver::Resolver,
+    schemas::{self, Draft},
+    validation_context::{self, ValidationContext},
 };
 use context::CompilationContext;
-use options::CompilationOptions;
-use serde_json::Value;
+use options::{CompilationOptions, UnknownKeywordBehavior};
+use serde_json::{Map, Value};
+use std::{borrow::Cow, fmt, sync::Arc};
+use sub_schemas::SubSchemas;
 
 use url::Url;
 
 pub(crate) const DEFAULT_ROOT_URL: &str = "json-schema:///";
+/// Keywords that are not a part of Open API 3.0 Schema Object.
+const OPENAPI_30_UNSUPPORTED_KEYWORDS: &[&str] =
+    &["additionalItems", "dependencies", "patternProperties"];
+/// Keywords of Open API 3.0 Schema Object that are not a part of JSON Schema.
+const OPENAPI_30_KEYWORDS: &[&str] = &[
+    "discriminator",
+    "example",
+    "externalDocs",
+    "nullable",
+    "xml",
+];
 
-/// The structure that holds a JSON Schema compiled into a validation tree
+/// The structure that holds a JSON Schema compiled into a validation tree.
+///
+/// It is `Send + Sync`, therefore a compiled schema can be shared between threads, e.g. via `Arc`:
+///
+/// ```rust
+/// # use jsonschema::JSONSchema;
+/// # use serde_json::json;
+/// # use std::{sync::Arc, thread};
+/// let schema = json!({"maxLength": 5});
+/// let compiled = Arc::new(JSONSchema::compile_owned(schema).expect("A valid schema"));
+/// let handle = {
+///     let compiled = Arc::clone(&compiled);
+///     thread::spawn(move || compiled.is_valid(&json!("foo")))
+/// };
+/// assert!(handle.join().expect("Thread panicked"));
+/// ```
 #[derive(Debug)]
 pub struct JSONSchema<'a> {
-    pub(crate) schema: &'a Value,
+    pub(crate) schema: Cow<'a, Value>,
+    /// The document `schema` belongs to, if it is a sub-schema. References are resolved against it.
+    pub(crate) document: Option<&'a Value>,
     pub(crate) validators: Validators,
-    pub(crate) resolver: Resolver<'a>,
+    pub(crate) resolver: Arc<Resolver>,
     pub(crate) context: CompilationContext<'a>,
 }
 
@@ -52,18 +88,209 @@ impl<'a> JSONSchema<'a> {
 
     /// Compile the input schema into a validation tree.
     ///
-    /// The method is equivalent to `JSONSchema::options().compile(schema)`
+    /// The method is equivalent to `JSONSchema::compile_with_options(schema, &Default::default())`
     pub fn compile(schema: &'a Value) -> Result<JSONSchema<'a>, CompilationError> {
-        Self::options().compile(schema)
+        Self::compile_with_options(schema, &Self::options())
+    }
+
+    /// Compile the input schema into a validation tree using `options`.
+    ///
+    /// The same options could be reused for multiple schemas.
+    ///
+    /// ```rust
+    /// # use jsonschema::{CompilationOptions, Draft, JSONSchema};
+    /// # use serde_json::json;
+    /// let mut options = CompilationOptions::default();
+    /// options.with_draft(Draft::Draft4);
+    /// let schema = json!({"maximum": 5, "exclusiveMaximum": true});
+    /// let compiled = JSONSchema::compile_with_options(&schema, &options).expect("A valid schema");
+    /// assert!(!compiled.is_valid(&json!(5)));
+    /// ```
+    pub fn compile_with_options(
+        schema: &'a Value,
+        options: &CompilationOptions,
+    ) -> Result<JSONSchema<'a>, CompilationError> {
+        options.compile(schema)
+    }
+
+    /// Compile the input schema into a validation tree, taking ownership of it.
+    ///
+    /// Unlike `JSONSchema::compile`, the result does not borrow the schema, which is useful when the
+    /// schema is loaded at runtime and the compiled one should outlive it.
+    /// The method is equivalent to `JSONSchema::options().compile_owned(schema)`
+    ///
+    /// ```rust
+    /// # use jsonschema::JSONSchema;
+    /// # use serde_json::json;
+    /// fn load() -> JSONSchema<'static> {
+    ///     let schema = json!({"maxLength": 5});
+    ///     JSONSchema::compile_owned(schema).expect("A valid schema")
+    /// }
+    /// assert!(load().is_valid(&json!("foo")));
+    /// ```
+    pub fn compile_owned(schema: Value) -> Result<JSONSchema<'static>, CompilationError> {
+        Self::options().compile_owned(schema)
+    }
+
+    /// The source schema this validation tree was compiled from.
+    ///
+    /// ```rust
+    /// # use jsonschema::JSONSchema;
+    /// # use serde_json::json;
+    /// let schema = json!({"maxLength": 5});
+    /// let compiled = JSONSchema::compile(&schema).expect("A valid schema");
+    /// assert_eq!(compiled.to_value(), &schema);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn to_value(&self) -> &Value {
+        &self.schema
+    }
+
+    /// The document references are resolved against.
+    #[inline]
+    pub(crate) fn document(&self) -> &Value {
+        self.document.unwrap_or(&self.schema)
+    }
+
+    /// Iterate over this schema and all its sub-schemas, e.g. in `properties` or `allOf`, as pairs
+    /// of JSON pointers to them and their compiled forms. Sub-schemas are compiled lazily in the
+    /// depth-first order. Local references are followed, and each location is yielded once, even
+    /// if it is referenced recursively. Sub-schemas that fail to compile are skipped.
+    ///
+    /// ```rust
+    /// # use jsonschema::JSONSchema;
+    /// # use serde_json::json;
+    /// let schema = json!({
+    ///     "properties": {
+    ///         "color": {"enum": ["red", "green"]},
+    ///         "size": {"enum": ["S", "M", "L"]}
+    ///     }
+    /// });
+    /// let compiled = JSONSchema::compile(&schema).expect("A valid schema");
+    /// let enums: Vec<_> = compiled
+    ///     .sub_schemas()
+    ///     .filter_map(|(pointer, sub_schema)| {
+    ///         sub_schema.to_value().get("enum").map(|options| (pointer, options.clone()))
+    ///     })
+    ///     .collect();
+    /// assert_eq!(enums.len(), 2);
+    /// assert_eq!(enums[0].0, "/properties/color");
+    /// assert!(enums[1].1.as_array().expect("An array").contains(&json!("M")));
+    /// ```
+    pub fn sub_schemas(&self) -> impl Iterator<Item = (String, JSONSchema<'_>)> + '_ {
+        SubSchemas::new(self)
+    }
+
+    /// Validate `schema` against the meta-schema of its draft.
+    ///
+    /// The draft is detected via the `$schema` keyword with fallback to Draft 7.
+    /// Only the Draft 4, 6 and 7 meta-schemas are bundled. Draft 2019-09 schemas are validated
+    /// against the Draft 7 meta-schema as only the Draft 7 vocabulary is supported for them,
+    /// therefore keywords introduced in Draft 2019-09, e.g. `minContains` or `dependentRequired`,
+    /// are not checked.
+    ///
+    /// ```rust
+    /// # use jsonschema::JSONSchema;
+    /// # use serde_json::json;
+    /// assert!(JSONSchema::validate_schema(&json!({"maxLength": 5})).is_ok());
+    /// assert!(JSONSchema::validate_schema(&json!({"maxLength": -1})).is_err());
+    /// // Not a valid Draft 2019-09 schema, but `minContains` is not checked
+    /// let schema = json!({
+    ///     "$schema": "https://json-schema.org/draft/2019-09/schema",
+    ///     "minContains": -1
+    /// });
+    /// assert!(JSONSchema::validate_schema(&schema).is_ok());
+    /// ```
+    pub fn validate_schema(schema: &Value) -> Result<(), Vec<ValidationError<'_>>> {
+        let compiled: &JSONSchema<'static> =
+            match schemas::draft_from_schema(schema).unwrap_or_default() {
+                Draft::Draft4 => &meta_schemas::DRAFT4_META_SCHEMA,
+                Draft::Draft6 => &meta_schemas::DRAFT6_META_SCHEMA,
+                Draft::Draft7 | Draft::Draft201909 => &meta_schemas::DRAFT7_META_SCHEMA,
+            };
+        let result = compiled.validate(schema);
+        match result {
+            Ok(_) => Ok(()),
+            Err(errors) => Err(errors.map(ValidationError::into_owned).collect()),
+        }
     }
 
     /// Run validation against `instance` and return an iterator over `ValidationError` in the error case.
     #[inline]
     pub fn validate(&'a self, instance: &'a Value) -> Result<(), ErrorIterator<'a>> {
+        self.validate_with_context(instance, &validation_context::EMPTY)
+    }
+
+    /// Run validation against `instance` and take ownership of it.
+    /// Errors own the relevant instance fragments, therefore they outlive the input instance.
+    /// All errors are collected upfront.
+    ///
+    /// ```rust
+    /// # use jsonschema::{JSONSchema, OwnedValidationError};
+    /// # use serde_json::json;
+    /// let schema = json!({"items": {"type": "integer"}});
+    /// let compiled = JSONSchema::compile(&schema).expect("A valid schema");
+    /// let errors: Vec<OwnedValidationError> = compiled
+    ///     .validate_owned(json!([1, "a", "b"]))
+    ///     .expect_err("Not integers")
+    ///     .collect();
+    /// assert_eq!(errors.len(), 2);
+    /// ```
+    #[allow(clippy::needless_pass_by_value)] // Taking ownership is the purpose of this method
+    pub fn validate_owned(&self, instance: Value) -> Result<(), OwnedErrorIterator> {
+        let errors: Vec<_> = match self.validate(&instance) {
+            Ok(()) => return Ok(()),
+            Err(errors) => errors.map(ValidationError::into_owned).collect(),
+        };
+        Err(Box::new(errors.into_iter()))
+    }
+
+    /// Run validation against `instance` with `context` available to custom keywords.
+    ///
+    /// ```rust
+    /// # use jsonschema::{CompilationOptions, Keyword, ValidationContext};
+    /// # use serde_json::{json, Value};
+    /// struct MaxAge(u64);
+    /// struct Adult;
+    ///
+    /// impl Keyword for Adult {
+    ///     fn validate(&self, instance: &Value, context: &ValidationContext) -> Result<(), String> {
+    ///         match (instance.as_u64(), context.get::<MaxAge>()) {
+    ///             (Some(age), Some(limit)) if age > limit.0 => Err(format!("{} is too old", age)),
+    ///             _ => Ok(()),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let schema = json!({"age": true});
+    /// let compiled = CompilationOptions::default()
+    ///     .with_keyword("age", |_, _| Ok(Box::new(Adult)))
+    ///     .compile(&schema)
+    ///     .expect("A valid schema");
+    /// let mut context = ValidationContext::new();
+    /// context.insert(MaxAge(100));
+    /// assert!(compiled.validate_with_context(&json!(42), &context).is_ok());
+    /// assert!(compiled.validate_with_context(&json!(142), &context).is_err());
+    /// ```
+    pub fn validate_with_context(
+        &'a self,
+        instance: &'a Value,
+        context: &'a ValidationContext,
+    ) -> Result<(), ErrorIterator<'a>> {
+        if let Some(transformed) = self.context.config.pre_validate(instance) {
+            let errors = self.collect_errors(instance, Some(&transformed), usize::MAX, context);
+            return if errors.is_empty() {
+                Ok(())
+            } else {
+                Err(Box::new(errors.into_iter()))
+            };
+        }
+        let instance_path = JSONPointer::default();
         let mut errors = self
             .validators
             .iter()
-            .flat_map(move |validator| validator.validate(self, instance))
+            .flat_map(move |validator| validator.validate(self, instance, &instance_path, context))
             .peekable();
         if errors.peek().is_none() {
             Ok(())
@@ -72,15 +299,290 @@ impl<'a> JSONSchema<'a> {
         }
     }
 
+    /// Like `is_valid`, but with `context` available to custom keywords.
+    #[must_use]
+    pub fn is_valid_with_context(&self, instance: &Value, context: &ValidationContext) -> bool {
+        let transformed = self.context.config.pre_validate(instance);
+        let instance = transformed.as_ref().unwrap_or(instance);
+        self.validators
+            .iter()
+            .all(|validator| validator.is_valid(self, instance, context))
+    }
+
+    /// Run validation against `instance` as if the schema was compiled for `draft`, e.g. when
+    /// documents embed schemas of different drafts. The schema is compiled again on each call
+    /// with the same options. The outer `Result` is an error if it is not a valid `draft` schema.
+    ///
+    /// ```rust
+    /// # use jsonschema::{CompilationError, Draft, JSONSchema};
+    /// # use serde_json::json;
+    /// # fn foo() -> Result<(), CompilationError> {
+    /// let schema = json!({"const": 1});
+    /// let compiled = JSONSchema::compile(&schema)?;
+    /// assert!(compiled.validate(&json!(2)).is_err());
+    /// // `const` is not a Draft 4 keyword
+    /// assert!(compiled.validate_with_draft(&json!(2), Draft::Draft4)?.is_ok());
+    /// # Ok(())
+    /// # }
+    /// # foo().expect("A valid schema");
+    /// ```
+    pub fn validate_with_draft(
+        &self,
+        instance: &'a Value,
+        draft: Draft,
+    ) -> Result<Result<(), ErrorIterator<'a>>, CompilationError> {
+        let mut options = self.context.config.clone().into_owned();
+        options.with_draft(draft);
+        let compiled = options.compile(&self.schema)?;
+        let errors: Vec<_> = match compiled.validate(instance) {
+            Ok(()) => return Ok(Ok(())),
+            // Errors borrow the instance, not the temporary schema
+            Err(errors) => errors.map(|error| error.rebind(instance, 0)).collect(),
+        };
+        Ok(Err(Box::new(errors.into_iter())))
+    }
+
+    /// Run validation against `instance` and return an iterator over at most `limit` errors.
+    /// The limit is applied to the total number of errors, not to errors from each keyword.
+    /// Nested validators, e.g. `properties` or `items`, stop once the limit is reached, so the rest
+    /// of the instance is not validated. For the same reason, errors of `anyOf`, `oneOf` and
+    /// `allOf` may have fewer sub-schema errors in their context.
+    /// The returned iterator is empty if the instance is valid.
+    ///
+    /// ```rust
+    /// # use jsonschema::JSONSchema;
+    /// # use serde_json::json;
+    /// let schema = json!({"items": {"type": "integer"}});
+    /// let compiled = JSONSchema::compile(&schema).expect("A valid schema");
+    /// let instance = json!(["a", "b", "c"]);
+    /// assert_eq!(compiled.validate_limited(&instance, 2).count(), 2);
+    /// ```
+    #[inline]
+    pub fn validate_limited(&'a self, instance: &'a Value, limit: usize) -> ErrorIterator<'a> {
+        self.validate_limited_with_context(instance, limit, &validation_context::EMPTY)
+    }
+
+    /// Like `validate_limited`, but with `context` available to custom keywords.
+    pub fn validate_limited_with_context(
+        &'a self,
+        instance: &'a Value,
+        limit: usize,
+        context: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        let transformed = self.context.config.pre_validate(instance);
+        // Nested validators stop collecting errors once the budget is spent
+        let context = context.with_budget(limit);
+        let errors = self.collect_errors(instance, transformed.as_ref(), limit, &context);
+        Box::new(errors.into_iter())
+    }
+
+    /// Run validation against `instance` and return the first `ValidationError` in the error case.
+    /// It is useful when only a single representative error is needed, e.g. with the `?` operator.
+    /// As with `validate_limited`, the context of the error may be incomplete.
+    ///
+    /// ```rust
+    /// # use jsonschema::{JSONSchema, ValidationError};
+    /// # use serde_json::{json, Value};
+    /// fn check<'a>(compiled: &'a JSONSchema, instance: &'a Value) -> Result<(), ValidationError<'a>> {
+    ///     compiled.first_error(instance)?;
+    ///     // Other checks
+    ///     Ok(())
+    /// }
+    ///
+    /// let schema = json!({"maxLength": 5});
+    /// let compiled = JSONSchema::compile(&schema).expect("A valid schema");
+    /// assert!(check(&compiled, &json!("foo")).is_ok());
+    /// assert!(check(&compiled, &json!("foo bar")).is_err());
+    /// ```
+    #[inline]
+    pub fn first_error(&'a self, instance: &'a Value) -> Result<(), ValidationError<'a>> {
+        self.first_error_with_context(instance, &validation_context::EMPTY)
+    }
+
+    /// Like `first_error`, but with `context` available to custom keywords.
+    pub fn first_error_with_context(
+        &'a self,
+        instance: &'a Value,
+        context: &'a ValidationContext,
+    ) -> Result<(), ValidationError<'a>> {
+        match self
+            .validate_limited_with_context(instance, 1, context)
+            .next()
+        {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+
+    /// Apply the schema to `instance` and return both validation errors and annotations.
+    ///
+    /// ```rust
+    /// # use jsonschema::JSONSchema;
+    /// # use serde_json::json;
+    /// let schema = json!({"maxLength": 5});
+    /// let compiled = JSONSchema::compile(&schema).expect("A valid schema");
+    /// let instance = json!("foo");
+    /// let output = compiled.apply(&instance);
+    /// assert!(output.is_valid());
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn apply(&'a self, instance: &'a Value) -> ValidationOutput<'a> {
+        self.apply_with_path(instance, &[])
+    }
+
+    /// Like `apply`, but with `context` available to custom keywords.
+    #[must_use]
+    pub fn apply_with_context(
+        &'a self,
+        instance: &'a Value,
+        context: &'a ValidationContext,
+    ) -> ValidationOutput<'a> {
+        self.apply_at(instance, &JSONPointer::default(), context)
+    }
+
+    /// Apply the schema to `instance` that is located at `path` within a larger document.
+    /// Instance locations of the reported errors are prefixed with `path`.
+    ///
+    /// ```rust
+    /// # use jsonschema::JSONSchema;
+    /// # use serde_json::json;
+    /// let schema = json!({"properties": {"name": {"type": "string"}}});
+    /// let compiled = JSONSchema::compile(&schema).expect("A valid schema");
+    /// let instance = json!({"name": 42});
+    /// let output = compiled.apply_with_path(&instance, &["users", "0"]);
+    /// let serialized = serde_json::to_value(&output).expect("Serializable");
+    /// assert_eq!(serialized["errors"][0]["instanceLocation"], "/users/0/name");
+    /// ```
+    #[must_use]
+    pub fn apply_with_path(&'a self, instance: &'a Value, path: &[&str]) -> ValidationOutput<'a> {
+        let instance_path = path.iter().fold(JSONPointer::default(), |pointer, chunk| {
+            pointer.extend_with(*chunk)
+        });
+        self.apply_at(instance, &instance_path, &validation_context::EMPTY)
+    }
+
+    fn apply_at(
+        &'a self,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        context: &'a ValidationContext,
+    ) -> ValidationOutput<'a> {
+        if let Some(transformed) = self.context.config.pre_validate(instance) {
+            let application = keywords::apply_validators(
+                &self.validators,
+                self,
+                &transformed,
+                instance_path,
+                context,
+            );
+            return PartialApplication {
+                errors: application
+                    .errors
+                    .into_iter()
+                    .map(|error| error.rebind(instance, instance_path.chunks().len()))
+                    .collect(),
+                annotations: application.annotations,
+            }
+            .into();
+        }
+        keywords::apply_validators(&self.validators, self, instance, instance_path, context).into()
+    }
+
     /// Run validation against `instance` but return a boolean result instead of an iterator.
     /// It is useful for cases, where it is important to only know the fact if the data is valid or not.
     /// This approach is much faster, than `validate`.
     #[must_use]
     #[inline]
     pub fn is_valid(&self, instance: &Value) -> bool {
-        self.validators
+        self.is_valid_with_context(instance, &validation_context::EMPTY)
+    }
+
+    /// Validate `transformed`, i.e. `instance` after pre-validation transforms, or `instance`
+    /// itself, and report at most `limit` errors against `instance`.
+    fn collect_errors(
+        &'a self,
+        instance: &'a Value,
+        transformed: Option<&Value>,
+        limit: usize,
+        context: &ValidationContext,
+    ) -> Vec<ValidationError<'a>> {
+        let instance_path = JSONPointer::default();
+        let errors = self
+            .validators
             .iter()
-            .all(|validator| validator.is_valid(self, instance))
+            .flat_map(|validator| {
+                validator.validate(
+                    self,
+                    transformed.unwrap_or(instance),
+                    &instance_path,
+                    context,
+                )
+            })
+            .take(limit)
+            .inspect(|_| context.spend());
+        if transformed.is_some() {
+            errors.map(|error| error.rebind(instance, 0)).collect()
+        } else {
+            errors.map(|error| error.reborrow(instance)).collect()
+        }
+    }
+
+    /// Whether the schema describes objects, i.e. it has `"type": "object"` or any of
+    /// the `properties`, `additionalProperties` and `required` keywords.
+    /// It is a heuristic for code generators - references and combinators are not inspected.
+    ///
+    /// ```rust
+    /// # use jsonschema::JSONSchema;
+    /// # use serde_json::json;
+    /// let schema = json!({"properties": {"id": {"type": "integer"}}});
+    /// let compiled = JSONSchema::compile(&schema).expect("A valid schema");
+    /// assert!(compiled.is_object_schema());
+    /// assert!(!compiled.is_array_schema());
+    /// ```
+    #[must_use]
+    pub fn is_object_schema(&self) -> bool {
+        self.describes(
+            "object",
+            &["properties", "additionalProperties", "required"],
+        )
+    }
+
+    /// Whether the schema describes arrays, i.e. it has `"type": "array"` or any of
+    /// the `items`, `minItems` and `maxItems` keywords.
+    /// It is a heuristic for code generators - references and combinators are not inspected.
+    #[must_use]
+    pub fn is_array_schema(&self) -> bool {
+        self.describes("array", &["items", "minItems", "maxItems"])
+    }
+
+    fn describes(&self, type_: &str, keywords: &[&str]) -> bool {
+        if let Value::Object(schema) = self.schema.as_ref() {
+            let has_type = match schema.get("type") {
+                Some(Value::String(name)) => name == type_,
+                Some(Value::Array(names)) => names.iter().any(|name| name == type_),
+                _ => false,
+            };
+            has_type || keywords.iter().any(|keyword| schema.contains_key(*keyword))
+        } else {
+            false
+        }
+    }
+}
+
+/// Pretty-printed source schema.
+///
+/// ```rust
+/// # use jsonschema::JSONSchema;
+/// # use serde_json::json;
+/// let schema = json!({"maxLength": 5});
+/// let compiled = JSONSchema::compile(&schema).expect("A valid schema");
+/// assert_eq!(compiled.to_string(), "{\n  \"maxLength\": 5\n}");
+/// ```
+impl fmt::Display for JSONSchema<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pretty = serde_json::to_string_pretty(self.to_value()).map_err(|_| fmt::Error)?;
+        f.write_str(&pretty)
     }
 }
 
@@ -89,44 +591,212 @@ impl<'a> JSONSchema<'a> {
 pub(crate) fn compile_validators(
     schema: &Value,
     context: &CompilationContext,
+) -> Result<Validators, CompilationError> {
+    // The root schema is always compiled upfront, sub-schemas could be deferred until their first use
+    if context.config.is_lazy() && !context.schema_path.is_empty() && schema.is_object() {
+        Ok(vec![keywords::lazy::LazyValidator::compile(
+            schema, context,
+        )])
+    } else {
+        compile_validators_eagerly(schema, context)
+    }
+}
+
+/// Compile JSON schema into a tree of validators without deferring its own compilation.
+#[inline]
+pub(crate) fn compile_validators_eagerly(
+    schema: &Value,
+    context: &CompilationContext,
 ) -> Result<Validators, CompilationError> {
     let context = context.push(schema)?;
     match schema {
         Value::Bool(value) => Ok(vec![
-            keywords::boolean::compile(*value).expect("Should always compile")?
+            keywords::boolean::compile(*value, &context).expect("Should always compile")?
         ]),
         Value::Object(object) => {
             if let Some(reference) = object.get("$ref") {
-                if let Value::String(reference) = reference {
-                    Ok(vec![keywords::ref_::compile(schema, reference, &context)
-                        .expect("Should always return Some")?])
+                let ref_context = context.with_path("$ref");
+                let validator = if let Value::String(reference) = reference {
+                    keywords::ref_::compile(schema, reference, &ref_context)
+                        .expect("Should always return Some")?
+                } else {
+                    return Err(CompilationError::invalid_keyword_value(
+                        "$ref",
+                        &ref_context.schema_path,
+                        reference,
+                    ));
+                };
+                if context.config.draft().ignores_ref_siblings() {
+                    Ok(vec![validator])
                 } else {
-                    Err(CompilationError::SchemaError)
+                    let mut validators = compile_keywords(object, &context)?;
+                    validators.push(validator);
+                    Ok(validators)
                 }
+            } else if context.config.is_openapi_30() {
+                compile_openapi_30_validators(object, &context)
             } else {
-                let mut validators = Vec::with_capacity(object.len());
-                for (keyword, subschema) in object {
-                    if let Some(compilation_func) = context.config.draft().get_validator(keyword) {
-                        if let Some(validator) = compilation_func(object, subschema, &context) {
-                            validators.push(validator?)
-                        }
+                compile_keywords(object, &context)
+            }
+        }
+        _ => Err(CompilationError::SchemaError),
+    }
+}
+
+#[inline]
+fn compile_keywords(
+    object: &Map<String, Value>,
+    context: &CompilationContext,
+) -> Result<Validators, CompilationError> {
+    if context.config.has_strict_additional_properties()
+        && !object.contains_key("additionalProperties")
+        && is_object_schema(object)
+    {
+        // `additionalProperties` changes how `properties` and `patternProperties` are compiled,
+        // therefore it is added to the schema itself
+        let mut strict = object.clone();
+        strict.insert("additionalProperties".to_string(), Value::Bool(false));
+        return compile_keywords(&strict, context);
+    }
+    let mut validators = Vec::with_capacity(object.len());
+    for (keyword, subschema) in object {
+        let context = context.with_path(keyword.as_str());
+        // Custom keywords take precedence over the built-in ones
+        let validator = if let Some(factory) = context.config.get_keyword_factory(keyword) {
+            keywords::custom::CustomKeywordValidator::compile(
+                factory,
+                keyword,
+                object,
+                subschema,
+                context.schema_path.clone(),
+            )
+        } else if !context.config.is_keyword_enabled(keyword) {
+            continue;
+        } else if let Some(compilation_func) = context.config.draft().get_validator(keyword) {
+            match compilation_func(object, subschema, &context) {
+                Some(validator) => validator,
+                None => continue,
+            }
+        } else {
+            if !is_known_keyword(keyword, &context) {
+                match context.config.unknown_keyword_behavior() {
+                    UnknownKeywordBehavior::Ignore => {}
+                    UnknownKeywordBehavior::Warn => {
+                        eprintln!("Unknown keyword '{}' at '{}'", keyword, context.schema_path)
+                    }
+                    UnknownKeywordBehavior::Error => {
+                        return Err(CompilationError::UnknownKeyword {
+                            keyword: keyword.to_string(),
+                            path: context.schema_path.to_string(),
+                        })
                     }
                 }
-                Ok(validators)
             }
+            continue;
+        };
+        // Errors from nested keywords already point to their location
+        validators.push(validator.map_err(|error| match error {
+            CompilationError::SchemaError => {
+                CompilationError::invalid_keyword_value(keyword, &context.schema_path, subschema)
+            }
+            error => error,
+        })?)
+    }
+    Ok(validators)
+}
+
+/// Whether `keyword` is defined by the draft or by Open API 3.0 in its mode.
+fn is_known_keyword(keyword: &str, context: &CompilationContext) -> bool {
+    context.config.draft().is_known_keyword(keyword)
+        || (context.config.is_openapi_30()
+            && (OPENAPI_30_KEYWORDS.contains(&keyword) || keyword.starts_with("x-")))
+}
+
+/// Whether the schema describes objects, i.e. declares properties or the `object` type.
+fn is_object_schema(object: &Map<String, Value>) -> bool {
+    if object.contains_key("properties") || object.contains_key("patternProperties") {
+        return true;
+    }
+    match object.get("type") {
+        Some(Value::String(type_)) => type_ == "object",
+        Some(Value::Array(types)) => types.iter().any(|type_| type_ == "object"),
+        _ => false,
+    }
+}
+
+fn compile_openapi_30_validators(
+    object: &Map<String, Value>,
+    context: &CompilationContext,
+) -> Result<Validators, CompilationError> {
+    let validators = if OPENAPI_30_UNSUPPORTED_KEYWORDS
+        .iter()
+        .any(|keyword| object.contains_key(*keyword))
+    {
+        // Other keywords may depend on the unsupported ones (e.g. `additionalProperties` on
+        // `patternProperties`), therefore they are removed from the schema entirely
+        let mut supported = object.clone();
+        for keyword in OPENAPI_30_UNSUPPORTED_KEYWORDS {
+            supported.remove(*keyword);
         }
-        _ => Err(CompilationError::SchemaError),
+        compile_keywords(&supported, context)?
+    } else {
+        compile_keywords(object, context)?
+    };
+    if let Some(Value::Bool(true)) = object.get("nullable") {
+        Ok(vec![keywords::nullable::NullableValidator::compile(
+            validators,
+        )])
+    } else {
+        Ok(validators)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::JSONSchema;
-    use crate::{error::ValidationError, schemas};
+    use crate::{
+        compilation::options::{CompilationOptions, UnknownKeywordBehavior},
+        error::{CompilationError, ValidationError},
+        schemas, tests_util,
+    };
     use serde_json::{from_str, json, Value};
-    use std::{borrow::Cow, fs::File, io::Read, path::Path};
+    use std::{borrow::Cow, fs::File, io::Read, path::Path, sync::atomic::Ordering};
+    use test_case::test_case;
     use url::Url;
 
+    #[test]
+    fn is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<JSONSchema<'static>>();
+        assert_send_sync::<JSONSchema<'_>>();
+    }
+
+    #[test_case(&json!({"properties": {"foo": {"type": 42}}}), "type", "/properties/foo/type", &json!(42))]
+    #[test_case(&json!({"items": [{"minLength": -1}]}), "minLength", "/items/0/minLength", &json!(-1))]
+    #[test_case(&json!({"pattern": 42}), "pattern", "/pattern", &json!(42))]
+    #[test_case(&json!({"$ref": 1}), "$ref", "/$ref", &json!(1))]
+    #[test_case(&json!({"allOf": [{"not": {"required": "a"}}]}), "required", "/allOf/0/not/required", &json!("a"))]
+    fn invalid_keyword_value(schema: &Value, keyword: &str, path: &str, value: &Value) {
+        let error = JSONSchema::compile(schema).unwrap_err();
+        assert_eq!(
+            error,
+            CompilationError::InvalidKeywordValue {
+                keyword: keyword.to_string(),
+                path: path.to_string(),
+                value: value.clone(),
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_keyword_value_message() {
+        let error = JSONSchema::compile(&json!({"properties": {"foo": {"type": 42}}})).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid value of the 'type' keyword at '/properties/foo/type': 42"
+        );
+    }
+
     fn load(path: &str, idx: usize) -> Value {
         let path = Path::new(path);
         let mut file = File::open(&path).unwrap();
@@ -175,6 +845,20 @@ mod tests {
         assert!(compiled.validate(&value).is_err());
     }
 
+    #[test]
+    fn compile_owned() {
+        let compiled = JSONSchema::compile_owned(json!({
+            "properties": {"a": {"$ref": "#/definitions/a"}},
+            "definitions": {"a": {"$id": "#a", "type": "integer"}},
+            "items": {"$ref": "#a"}
+        }))
+        .unwrap();
+        assert!(compiled.is_valid(&json!({"a": 1})));
+        assert!(!compiled.is_valid(&json!({"a": "b"})));
+        assert!(compiled.is_valid(&json!([1])));
+        assert!(!compiled.is_valid(&json!(["b"])));
+    }
+
     #[test]
     fn wrong_schema_type() {
         let schema = json!([1]);
@@ -192,11 +876,329 @@ mod tests {
         assert_eq!(errors.len(), 2);
         assert_eq!(
             errors[0].to_string(),
-            r#"{"a":3} has less than 2 properties"#
+            r#"/minProperties: {"a":3} has less than 2 properties (actual: 1)"#
         );
         assert_eq!(
             errors[1].to_string(),
-            r#"'"a"' is shorter than 3 characters"#
+            r#"/propertyNames/minLength: '"a"' is shorter than 3 characters (actual: 1)"#
+        );
+    }
+
+    #[test]
+    fn apply_with_path() {
+        let schema = json!({"items": {"type": "string"}});
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        let value = json!(["a", 1]);
+        let output = compiled.apply_with_path(&value, &["users", "0"]);
+        assert_eq!(output.errors()[0].instance_path().to_string(), "/users/0/1");
+        let output = compiled.apply(&value);
+        assert_eq!(output.errors()[0].instance_path().to_string(), "/1");
+    }
+
+    #[test]
+    fn first_error() {
+        let schema = json!({"minProperties": 2, "propertyNames": {"minLength": 3}});
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        let value = json!({"a": 3});
+        let error = compiled.first_error(&value).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"/minProperties: {"a":3} has less than 2 properties (actual: 1)"#
+        );
+        let value = json!({"abc": 3, "def": 4});
+        assert!(compiled.first_error(&value).is_ok());
+    }
+
+    #[test]
+    fn validate_owned() {
+        let schema = json!({"items": {"type": "integer"}});
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        assert!(compiled.validate_owned(json!([1, 2])).is_ok());
+        let errors: Vec<_> = {
+            let value = json!([1, "a"]);
+            compiled.validate_owned(value).unwrap_err().collect()
+        };
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].instance_path().to_string(), "/1");
+        assert_eq!(
+            errors[0].to_string(),
+            r#"/items/type: '"a"' is not of type 'integer'"#
         );
     }
+
+    #[test_case(0, 0)]
+    #[test_case(1, 1)]
+    #[test_case(2, 2)]
+    #[test_case(5, 3)]
+    fn validate_limited(limit: usize, expected: usize) {
+        let schema = json!({"items": {"type": "integer"}, "minItems": 4});
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        let value = json!(["a", "b"]);
+        assert_eq!(compiled.validate_limited(&value, limit).count(), expected);
+        let value = json!([1, 2, 3, 4]);
+        assert_eq!(compiled.validate_limited(&value, limit).count(), 0);
+    }
+
+    #[test]
+    fn validate_limited_stops_nested_validators() {
+        let (options, calls) = tests_util::with_counter();
+        let schema =
+            json!({"items": {"properties": {"a": {"counter": true}, "b": {"counter": true}}}});
+        let compiled = options.compile(&schema).unwrap();
+        // Each item has two invalid properties
+        let instance = Value::Array(vec![json!({"a": 1, "b": 1}); 100]);
+        let errors: Vec<_> = compiled.validate_limited(&instance, 3).collect();
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[2].instance_path().to_string(), "/1/a");
+        // Only the first two items are validated
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+        calls.store(0, Ordering::SeqCst);
+        assert_eq!(compiled.validate(&instance).unwrap_err().count(), 200);
+        assert_eq!(calls.load(Ordering::SeqCst), 200);
+    }
+
+    #[test]
+    fn validate_limited_error_instances() {
+        let schema =
+            json!({"properties": {"a": {"minimum": 5}}, "propertyNames": {"maxLength": 1}});
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        let instance = json!({"a": 1, "bc": 2});
+        let messages: Vec<_> = compiled
+            .validate_limited(&instance, 5)
+            .map(|error| error.to_string())
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "/properties/a/minimum: 1 is less than the minimum of 5",
+                r#"/propertyNames/maxLength: '"bc"' is longer than 1 character (actual: 2)"#,
+            ]
+        );
+    }
+
+    #[test_case(&json!({"type": "string"}))]
+    #[test_case(&json!({"$schema": "http://json-schema.org/draft-04/schema#", "exclusiveMaximum": true, "maximum": 5}))]
+    #[test_case(&json!({"$schema": "http://json-schema.org/draft-06/schema#", "exclusiveMaximum": 5}))]
+    #[test_case(&json!({"$schema": "https://json-schema.org/draft/2019-09/schema", "if": true}))]
+    fn validate_schema_valid(schema: &Value) {
+        assert!(JSONSchema::validate_schema(schema).is_ok())
+    }
+
+    #[test_case(&json!({"type": "unknown"}), 1)]
+    #[test_case(&json!({"minLength": -1, "required": "a"}), 2)]
+    #[test_case(&json!({"$schema": "http://json-schema.org/draft-04/schema#", "exclusiveMaximum": 5}), 2)]
+    #[test_case(&json!({"$schema": "http://json-schema.org/draft-06/schema#", "exclusiveMaximum": true}), 1)]
+    fn validate_schema_invalid(schema: &Value, expected: usize) {
+        let errors = JSONSchema::validate_schema(schema).unwrap_err();
+        assert_eq!(errors.len(), expected)
+    }
+
+    fn trim(instance: &Value) -> Value {
+        match instance {
+            Value::String(string) => Value::String(string.trim().to_string()),
+            Value::Object(object) => Value::Object(
+                object
+                    .iter()
+                    .map(|(key, value)| (key.clone(), trim(value)))
+                    .collect(),
+            ),
+            _ => instance.clone(),
+        }
+    }
+
+    fn parse_timestamp(instance: &Value) -> Value {
+        match instance.get("at") {
+            Some(Value::String(string)) => match string.parse::<u64>() {
+                Ok(timestamp) => json!({ "at": timestamp }),
+                Err(_) => instance.clone(),
+            },
+            _ => instance.clone(),
+        }
+    }
+
+    #[test]
+    fn pre_validate() {
+        let schema = json!({"properties": {"name": {"maxLength": 3}, "at": {"type": "integer"}}});
+        let compiled = JSONSchema::options()
+            .with_pre_validate(trim)
+            .compile(&schema)
+            .unwrap();
+        assert!(compiled.is_valid(&json!({"name": " foo "})));
+        let instance = json!({"name": " foobar "});
+        assert!(!compiled.is_valid(&instance));
+        let errors: Vec<_> = compiled.validate(&instance).unwrap_err().collect();
+        assert_eq!(errors.len(), 1);
+        // Errors refer to the original instance
+        let message =
+            r#"/properties/name/maxLength: '" foobar "' is longer than 3 characters (actual: 6)"#;
+        assert_eq!(errors[0].to_string(), message);
+        let error = compiled.first_error(&instance).unwrap_err();
+        assert_eq!(error.to_string(), message);
+        let errors: Vec<_> = compiled.validate_limited(&instance, 5).collect();
+        assert_eq!(errors[0].to_string(), message);
+        let output = compiled.apply_with_path(&instance, &["users", "0"]);
+        let serialized = serde_json::to_value(&output).unwrap();
+        assert_eq!(serialized["errors"][0]["instanceLocation"], "/users/0/name");
+        assert_eq!(
+            serialized["errors"][0]["error"],
+            r#"'" foobar "' is longer than 3 characters (actual: 6)"#
+        );
+    }
+
+    #[test]
+    fn pre_validate_order() {
+        let schema = json!({"properties": {"at": {"type": "integer"}}});
+        let instance = json!({"at": " 42 "});
+        // Timestamps are parsed after trimming
+        let compiled = JSONSchema::options()
+            .with_pre_validate(trim)
+            .with_pre_validate(parse_timestamp)
+            .compile(&schema)
+            .unwrap();
+        assert!(compiled.is_valid(&instance));
+        let compiled = JSONSchema::options()
+            .with_pre_validate(parse_timestamp)
+            .with_pre_validate(trim)
+            .compile(&schema)
+            .unwrap();
+        assert!(!compiled.is_valid(&instance));
+        let errors: Vec<_> = compiled
+            .validate_owned(instance.clone())
+            .unwrap_err()
+            .collect();
+        assert_eq!(
+            errors[0].to_string(),
+            r#"/properties/at/type: '" 42 "' is not of type 'integer'"#
+        );
+    }
+
+    #[test_case(&json!({"type": "object"}), true, false)]
+    #[test_case(&json!({"type": ["object", "null"]}), true, false)]
+    #[test_case(&json!({"required": ["a"]}), true, false)]
+    #[test_case(&json!({"additionalProperties": false}), true, false)]
+    #[test_case(&json!({"type": "array"}), false, true)]
+    #[test_case(&json!({"items": {"type": "string"}}), false, true)]
+    #[test_case(&json!({"maxItems": 3}), false, true)]
+    #[test_case(&json!({"type": "string"}), false, false)]
+    #[test_case(&json!(true), false, false)]
+    fn schema_kind(schema: &Value, is_object: bool, is_array: bool) {
+        let compiled = JSONSchema::compile(schema).unwrap();
+        assert_eq!(compiled.is_object_schema(), is_object);
+        assert_eq!(compiled.is_array_schema(), is_array);
+    }
+
+    #[test_case(schemas::Draft::Draft4, true)]
+    #[test_case(schemas::Draft::Draft6, true)]
+    #[test_case(schemas::Draft::Draft7, true)]
+    #[test_case(schemas::Draft::Draft201909, false)]
+    fn ref_siblings(draft: schemas::Draft, expected: bool) {
+        let schema = json!({
+            "$ref": "#/definitions/a",
+            "maximum": 5,
+            "definitions": {"a": {"type": "integer"}}
+        });
+        let compiled = JSONSchema::options()
+            .with_draft(draft)
+            .compile(&schema)
+            .unwrap();
+        assert!(compiled.is_valid(&json!(1)));
+        assert!(!compiled.is_valid(&json!("a")));
+        assert_eq!(compiled.is_valid(&json!(10)), expected);
+    }
+
+    #[test]
+    fn validate_with_draft() {
+        let schema = json!({"properties": {"a": {"minimum": 5, "const": 7}}});
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        let instance = json!({"a": 1});
+        assert_eq!(compiled.validate(&instance).unwrap_err().count(), 2);
+        let errors: Vec<_> = compiled
+            .validate_with_draft(&instance, schemas::Draft::Draft4)
+            .unwrap()
+            .unwrap_err()
+            .collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].instance_path().to_string(), "/a");
+        assert_eq!(
+            errors[0].to_string(),
+            "/properties/a/minimum: 1 is less than the minimum of 5"
+        );
+        // The draft of the compiled schema is not changed
+        assert_eq!(compiled.validate(&instance).unwrap_err().count(), 2);
+    }
+
+    #[test]
+    fn validate_with_draft_invalid_schema() {
+        let schema = json!({"maximum": 5, "exclusiveMaximum": true});
+        let compiled = JSONSchema::options()
+            .with_draft(schemas::Draft::Draft4)
+            .compile(&schema)
+            .unwrap();
+        let instance = json!(1);
+        assert!(compiled.validate(&instance).is_ok());
+        let error = compiled
+            .validate_with_draft(&instance, schemas::Draft::Draft7)
+            .err()
+            .unwrap();
+        assert_eq!(
+            error,
+            CompilationError::InvalidKeywordValue {
+                keyword: "exclusiveMaximum".to_string(),
+                path: "/exclusiveMaximum".to_string(),
+                value: json!(true),
+            }
+        );
+    }
+
+    #[test_case(&json!({"minimun": 5}), "minimun", "/minimun")]
+    #[test_case(&json!({"properties": {"a": {"tpye": "string"}}}), "tpye", "/properties/a/tpye")]
+    #[test_case(&json!({"items": [{"nullable": true}]}), "nullable", "/items/0/nullable")]
+    fn unknown_keyword_error(schema: &Value, keyword: &str, path: &str) {
+        let error = JSONSchema::options()
+            .unknown_keywords(UnknownKeywordBehavior::Error)
+            .compile(schema)
+            .expect_err("Unknown keyword");
+        assert_eq!(
+            error,
+            CompilationError::UnknownKeyword {
+                keyword: keyword.to_string(),
+                path: path.to_string()
+            }
+        );
+    }
+
+    #[test_case(&json!({"title": "A", "$comment": "B", "definitions": {}, "examples": [1]}))]
+    #[test_case(&json!({"if": {"type": "string"}, "then": {}, "else": {}}))]
+    #[test_case(&json!({"$ref": "#/definitions/a", "definitions": {"a": {}}}))]
+    fn known_keywords(schema: &Value) {
+        assert!(JSONSchema::options()
+            .unknown_keywords(UnknownKeywordBehavior::Error)
+            .compile(schema)
+            .is_ok());
+    }
+
+    #[test]
+    fn unknown_keyword_ignored_by_default() {
+        let schema = json!({"minimun": 5});
+        assert!(JSONSchema::compile(&schema).is_ok());
+    }
+
+    #[test_case(&json!({"nullable": true, "example": 1, "x-internal": true}))]
+    #[test_case(&json!({"discriminator": {"propertyName": "a"}, "xml": {}}))]
+    fn unknown_keyword_openapi_30(schema: &Value) {
+        assert!(CompilationOptions::openapi_30()
+            .unknown_keywords(UnknownKeywordBehavior::Error)
+            .compile(schema)
+            .is_ok());
+    }
+
+    #[test]
+    fn unknown_keyword_warn() {
+        let schema = json!({"minimun": 5});
+        let compiled = JSONSchema::options()
+            .unknown_keywords(UnknownKeywordBehavior::Warn)
+            .compile(&schema)
+            .expect("Only a warning");
+        assert!(compiled.is_valid(&json!(1)));
+    }
 }
@@ -6,30 +6,45 @@ use crate::{
     },
     content_media_type::{ContentMediaTypeCheckType, DEFAULT_CONTENT_MEDIA_TYPE_CHECKS},
     error::CompilationError,
+    format_registry::{self, FormatCheckType},
+    keywords::custom::{Keyword, KeywordFactory},
+    meta_schemas::META_SCHEMAS,
     resolver::Resolver,
     schemas,
+    vocabularies::Vocabularies,
 };
-use ahash::AHashMap;
-use serde_json::Value;
-use std::{borrow::Cow, fmt};
-
-lazy_static::lazy_static! {
-    static ref META_SCHEMAS: AHashMap<String, Value> = {
-        let mut store = AHashMap::with_capacity(3);
-        store.insert(
-            "http://json-schema.org/draft-04/schema".to_string(),
-            serde_json::from_str(include_str!("../../meta_schemas/draft4.json")).expect("Valid schema!")
-        );
-        store.insert(
-            "http://json-schema.org/draft-06/schema".to_string(),
-            serde_json::from_str(include_str!("../../meta_schemas/draft6.json")).expect("Valid schema!")
-        );
-        store.insert(
-            "http://json-schema.org/draft-07/schema".to_string(),
-            serde_json::from_str(include_str!("../../meta_schemas/draft7.json")).expect("Valid schema!")
-        );
-        store
-    };
+use ahash::{AHashMap, AHashSet};
+use serde_json::{Map, Value};
+use std::{borrow::Cow, fmt, sync::Arc};
+
+/// How to handle keywords that are not defined by the draft, e.g. typos like `minimun`.
+///
+/// ```rust
+/// # use jsonschema::{CompilationOptions, UnknownKeywordBehavior};
+/// # use serde_json::json;
+/// let schema = json!({"minimun": 5});
+/// assert!(CompilationOptions::default().compile(&schema).is_ok());
+/// assert!(CompilationOptions::default()
+///     .unknown_keywords(UnknownKeywordBehavior::Error)
+///     .compile(&schema)
+///     .is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnknownKeywordBehavior {
+    /// Skip unknown keywords silently.
+    Ignore,
+    /// Skip unknown keywords, but print a warning with their location to stderr.
+    Warn,
+    /// Fail with `CompilationError::UnknownKeyword`.
+    Error,
+}
+
+// `#[default]` on enum variants requires Rust 1.62
+#[allow(clippy::derivable_impls)]
+impl Default for UnknownKeywordBehavior {
+    fn default() -> Self {
+        UnknownKeywordBehavior::Ignore
+    }
 }
 
 /// Full configuration to guide the `JSONSchema` compilation.
@@ -43,15 +58,124 @@ pub struct CompilationOptions {
     content_encoding_checks_and_converters:
         AHashMap<&'static str, Option<(ContentEncodingCheckType, ContentEncodingConverterType)>>,
     store: AHashMap<String, Value>,
+    openapi_30: bool,
+    lazy: bool,
+    coerce_types: bool,
+    strict_keywords: bool,
+    ecma_regex_compat: bool,
+    strict_additional_properties: bool,
+    vocabularies: Option<Vocabularies>,
+    unknown_keywords: UnknownKeywordBehavior,
+    keywords: AHashMap<String, KeywordFactory>,
+    formats: AHashMap<&'static str, FormatCheckType>,
+    disabled_formats: AHashSet<String>,
+    pre_validate: Vec<fn(&Value) -> Value>,
 }
 
 impl CompilationOptions {
+    /// Options preset for validating Open API 3.0 Schema Objects.
+    ///
+    /// Open API 3.0 schemas are based on the Wright Draft 00 which has the same semantic as Draft 4
+    /// for the keywords it supports (e.g. boolean `exclusiveMaximum`), therefore Draft 4 is used.
+    /// Additionally:
+    ///   - `nullable: true` allows `null` values, as if the schema was wrapped into
+    ///     `{"anyOf": [<schema>, {"type": "null"}]}`;
+    ///   - `discriminator` is a hint for tooling and does not affect validation;
+    ///   - Keywords that are not supported by Open API 3.0 (`additionalItems`, `dependencies` and
+    ///     `patternProperties`) are ignored.
+    ///
+    /// ```rust
+    /// # use jsonschema::CompilationOptions;
+    /// # use serde_json::json;
+    /// let schema = json!({"type": "string", "nullable": true});
+    /// let compiled = CompilationOptions::openapi_30()
+    ///     .compile(&schema)
+    ///     .expect("A valid schema");
+    /// assert!(compiled.is_valid(&json!(null)));
+    /// ```
+    #[must_use]
+    pub fn openapi_30() -> Self {
+        let mut options = CompilationOptions::default();
+        options.with_draft(schemas::Draft::Draft4);
+        options.openapi_30 = true;
+        options
+    }
+
     pub(crate) fn draft(&self) -> schemas::Draft {
         self.draft.unwrap_or_default()
     }
 
+    #[inline]
+    pub(crate) fn is_openapi_30(&self) -> bool {
+        self.openapi_30
+    }
+
+    #[inline]
+    pub(crate) fn is_lazy(&self) -> bool {
+        self.lazy
+    }
+
+    #[inline]
+    pub(crate) fn coerces_types(&self) -> bool {
+        self.coerce_types
+    }
+
+    #[inline]
+    pub(crate) fn has_strict_keywords(&self) -> bool {
+        self.strict_keywords
+    }
+
+    #[inline]
+    pub(crate) fn has_ecma_regex_compat(&self) -> bool {
+        self.ecma_regex_compat
+    }
+
+    #[inline]
+    pub(crate) fn has_strict_additional_properties(&self) -> bool {
+        self.strict_additional_properties
+    }
+
+    #[inline]
+    pub(crate) fn unknown_keyword_behavior(&self) -> UnknownKeywordBehavior {
+        self.unknown_keywords
+    }
+
+    /// Whether `keyword` is enabled by the vocabularies of a custom meta-schema, if there is one.
+    #[inline]
+    pub(crate) fn is_keyword_enabled(&self, keyword: &str) -> bool {
+        match &self.vocabularies {
+            Some(vocabularies) => vocabularies.allows(keyword),
+            None => true,
+        }
+    }
+
+    /// `instance` after all pre-validation transforms or `None` if there are no transforms.
+    #[inline]
+    pub(crate) fn pre_validate(&self, instance: &Value) -> Option<Value> {
+        let (first, rest) = self.pre_validate.split_first()?;
+        Some(
+            rest.iter()
+                .fold(first(instance), |instance, transform| transform(&instance)),
+        )
+    }
+
+    #[inline]
+    pub(crate) fn get_keyword_factory(&self, keyword: &str) -> Option<&KeywordFactory> {
+        self.keywords.get(keyword)
+    }
+
     /// Compile `schema` into `JSONSchema` using the currently defined options.
     pub fn compile<'a>(&self, schema: &'a Value) -> Result<JSONSchema<'a>, CompilationError> {
+        self.compile_cow(Cow::Borrowed(schema))
+    }
+
+    /// Compile `schema` into `JSONSchema` using the currently defined options.
+    /// The schema is moved into the resulting `JSONSchema` instead of being borrowed.
+    pub fn compile_owned(&self, schema: Value) -> Result<JSONSchema<'static>, CompilationError> {
+        self.compile_cow(Cow::Owned(schema))
+    }
+
+    fn compile_cow<'a>(&self, schema: Cow<'a, Value>) -> Result<JSONSchema<'a>, CompilationError> {
         // Draft is detected in the following precedence order:
         //   - Explicitly specified;
         //   - $schema field in the document;
@@ -64,32 +188,56 @@ impl CompilationOptions {
         // options might contain heap-related objects (ie. an HashMap) and we want the
         // memory-related operations to be explicit
         let mut config = self.clone();
+        // Checks installed for the current thread take precedence. They are captured here, so
+        // that lazily compiled sub-schemas do not depend on the thread that validates them
+        config.formats.extend(format_registry::snapshot());
         if self.draft.is_none() {
-            if let Some(draft) = schemas::draft_from_schema(schema) {
+            if let Some(draft) = schemas::draft_from_schema(&schema) {
                 config.with_draft(draft);
             }
         }
+        // Custom meta-schemas are looked up in the store only
+        if let Some(meta_schema) = self.custom_meta_schema(&schema) {
+            if self.draft.is_none() {
+                if let Some(draft) = schemas::draft_from_schema(meta_schema) {
+                    config.with_draft(draft);
+                }
+            }
+            config.vocabularies = Vocabularies::from_meta_schema(meta_schema)?;
+        }
         let processed_config: Cow<'_, CompilationOptions> = Cow::Owned(config);
         let draft = processed_config.draft();
 
-        let scope = match schemas::id_of(draft, schema) {
+        let scope = match schemas::id_of(draft, &schema) {
             Some(url) => url::Url::parse(url)?,
             None => DEFAULT_SCOPE.clone(),
         };
-        let resolver = Resolver::new(draft, &scope, schema, self.store.clone())?;
+        let resolver = Resolver::new(draft, &scope, &schema, self.store.clone())?;
         let context = CompilationContext::new(scope, processed_config);
 
-        let mut validators = compile_validators(schema, &context)?;
+        let mut validators = compile_validators(&schema, &context)?;
         validators.shrink_to_fit();
 
         Ok(JSONSchema {
             schema,
-            resolver,
+            document: None,
+            resolver: Arc::new(resolver),
             validators,
             context,
         })
     }
 
+    /// The document referenced by `$schema`, unless it is one of the known drafts.
+    fn custom_meta_schema(&self, schema: &Value) -> Option<&Value> {
+        let url = schema.get("$schema").and_then(Value::as_str)?;
+        if schemas::draft_from_url(url).is_some() {
+            return None;
+        }
+        self.store
+            .get(url)
+            .or_else(|| self.store.get(url.trim_end_matches('#')))
+    }
+
     /// Ensure that the schema is going to be compiled using the defined Draft.
     ///
     /// ```rust
@@ -158,6 +306,70 @@ impl CompilationOptions {
         self
     }
 
+    /// A custom check for `format`, including the ones installed via `FormatRegistry` for the
+    /// thread that compiles the schema.
+    pub(crate) fn format_check(&self, format: &str) -> Option<(&'static str, FormatCheckType)> {
+        self.formats
+            .get_key_value(format)
+            .map(|(format, check)| (*format, *check))
+    }
+
+    /// Ensure that compiled schema is going to support the provided format. The check replaces
+    /// the built-in one if there is any.
+    ///
+    /// Arguments:
+    /// * `format`: Name of the format (ie. "currency")
+    /// * `format_check`: Method checking the validity of the input string according to the format.
+    ///   The method should return `true` if the input is valid, `false` otherwise.
+    ///
+    /// Example:
+    /// ```rust
+    /// # use jsonschema::CompilationOptions;
+    /// # use serde_json::json;
+    /// fn check_currency(instance_string: &str) -> bool {
+    ///     instance_string.len() == 3 && instance_string.chars().all(|c| c.is_ascii_uppercase())
+    /// }
+    /// let schema = json!({"format": "currency"});
+    /// let compiled = CompilationOptions::default()
+    ///     .with_format("currency", check_currency)
+    ///     .compile(&schema)
+    ///     .expect("A valid schema");
+    /// assert!(compiled.is_valid(&json!("USD")));
+    /// assert!(!compiled.is_valid(&json!("usd")));
+    /// ```
+    pub fn with_format(
+        &mut self,
+        format: &'static str,
+        format_check: fn(&str) -> bool,
+    ) -> &mut Self {
+        self.formats.insert(format, format_check);
+        self
+    }
+
+    /// Skip validation of `format` for the given format name, while other formats are still
+    /// validated. It applies to built-in formats as well as to the ones added via `with_format`.
+    ///
+    /// ```rust
+    /// # use jsonschema::CompilationOptions;
+    /// # use serde_json::json;
+    /// let schema = json!({"properties": {"email": {"format": "email"}, "ip": {"format": "ipv4"}}});
+    /// let compiled = CompilationOptions::default()
+    ///     .disable_format("email")
+    ///     .compile(&schema)
+    ///     .expect("A valid schema");
+    /// assert!(compiled.is_valid(&json!({"email": "not an email"})));
+    /// assert!(!compiled.is_valid(&json!({"ip": "not an ip"})));
+    /// ```
+    pub fn disable_format(&mut self, format: &str) -> &mut Self {
+        self.disabled_formats.insert(format.to_string());
+        self
+    }
+
+    #[inline]
+    pub(crate) fn is_format_disabled(&self, format: &str) -> bool {
+        self.disabled_formats.contains(format)
+    }
+
     #[inline]
     fn content_encoding_check_and_converter(
         &self,
@@ -296,6 +508,202 @@ impl CompilationOptions {
         self
     }
 
+    /// Defer compilation of sub-schemas until they are used for the first time.
+    ///
+    /// It reduces compilation time for big schemas where only a small part is used for
+    /// a typical document, e.g. schemas with many conditional branches.
+    /// Note that invalid sub-schemas are not reported by `compile` in this mode - they make
+    /// instances invalid during validation instead.
+    ///
+    /// ```rust
+    /// # use jsonschema::CompilationOptions;
+    /// # use serde_json::json;
+    /// let schema = json!({"properties": {"name": {"type": "string"}}});
+    /// let compiled = CompilationOptions::default()
+    ///     .with_lazy_compilation()
+    ///     .compile(&schema)
+    ///     .expect("A valid schema");
+    /// assert!(compiled.is_valid(&json!({"name": "foo"})));
+    /// ```
+    #[inline]
+    pub fn with_lazy_compilation(&mut self) -> &mut Self {
+        self.lazy = true;
+        self
+    }
+
+    /// Coerce strings during `type` checks, e.g. for query string parameters that are always
+    /// strings. Strings that are valid JSON numbers satisfy `number` (and `integer` if they
+    /// represent an integer), `"true"` and `"false"` satisfy `boolean`.
+    ///
+    /// Coercion affects only the `type` keyword and does not modify the instance - e.g.
+    /// `minimum` still ignores strings.
+    ///
+    /// ```rust
+    /// # use jsonschema::CompilationOptions;
+    /// # use serde_json::json;
+    /// let schema = json!({"type": "integer"});
+    /// let compiled = CompilationOptions::default()
+    ///     .coerce_types(true)
+    ///     .compile(&schema)
+    ///     .expect("A valid schema");
+    /// assert!(compiled.is_valid(&json!("42")));
+    /// assert!(!compiled.is_valid(&json!("4.2")));
+    /// ```
+    #[inline]
+    pub fn coerce_types(&mut self, coerce: bool) -> &mut Self {
+        self.coerce_types = coerce;
+        self
+    }
+
+    /// Reject keyword values that are allowed, but are likely mistakes, e.g. duplicates in
+    /// the array form of `type`. By default they are accepted.
+    ///
+    /// ```rust
+    /// # use jsonschema::CompilationOptions;
+    /// # use serde_json::json;
+    /// let schema = json!({"type": ["string", "string"]});
+    /// assert!(CompilationOptions::default().compile(&schema).is_ok());
+    /// assert!(CompilationOptions::default()
+    ///     .strict_keywords(true)
+    ///     .compile(&schema)
+    ///     .is_err());
+    /// ```
+    #[inline]
+    pub fn strict_keywords(&mut self, strict: bool) -> &mut Self {
+        self.strict_keywords = strict;
+        self
+    }
+
+    /// Reject `pattern` values and `patternProperties` keys that are not ECMA 262 regular
+    /// expressions or that would match differently than in ECMA 262, e.g. Unicode-aware word
+    /// boundaries or inline flags. They are reported as `CompilationError::InvalidPattern`.
+    /// By default such patterns are accepted and matched with Rust's `regex` semantics.
+    /// Character classes like `\d` or `\w` are always matched as in ECMA 262.
+    ///
+    /// ```rust
+    /// # use jsonschema::CompilationOptions;
+    /// # use serde_json::json;
+    /// let schema = json!({"pattern": "(?i)^foo$"});
+    /// assert!(CompilationOptions::default().compile(&schema).is_ok());
+    /// assert!(CompilationOptions::default()
+    ///     .ecma_regex_compat(true)
+    ///     .compile(&schema)
+    ///     .is_err());
+    /// ```
+    #[inline]
+    pub fn ecma_regex_compat(&mut self, compat: bool) -> &mut Self {
+        self.ecma_regex_compat = compat;
+        self
+    }
+
+    /// Disallow properties that are not declared, as if `additionalProperties: false` was
+    /// present in every object schema without an explicit `additionalProperties`. Object schemas
+    /// are the ones with `properties`, `patternProperties` or the `object` type.
+    /// Errors from this implicit keyword point to `additionalProperties` of such schemas.
+    ///
+    /// This mode is not compliant with the specification. Note that sub-schemas are checked on
+    /// their own, therefore properties declared in different `allOf` branches are rejected.
+    ///
+    /// ```rust
+    /// # use jsonschema::CompilationOptions;
+    /// # use serde_json::json;
+    /// let schema = json!({"properties": {"name": {"type": "string"}}});
+    /// let compiled = CompilationOptions::default()
+    ///     .strict_additional_properties(true)
+    ///     .compile(&schema)
+    ///     .expect("A valid schema");
+    /// assert!(compiled.is_valid(&json!({"name": "foo"})));
+    /// assert!(!compiled.is_valid(&json!({"name": "foo", "age": 42})));
+    /// ```
+    #[inline]
+    pub fn strict_additional_properties(&mut self, strict: bool) -> &mut Self {
+        self.strict_additional_properties = strict;
+        self
+    }
+
+    /// Set how keywords that are not defined by the draft are handled. They are ignored by
+    /// default. Annotations like `title`, custom keywords and keywords that are specific to
+    /// Open API 3.0 in its mode, including `x-` extensions, are not considered unknown.
+    ///
+    /// ```rust
+    /// # use jsonschema::{CompilationError, CompilationOptions, UnknownKeywordBehavior};
+    /// # use serde_json::json;
+    /// let schema = json!({"properties": {"age": {"minimun": 0}}});
+    /// let error = CompilationOptions::default()
+    ///     .unknown_keywords(UnknownKeywordBehavior::Error)
+    ///     .compile(&schema)
+    ///     .expect_err("Unknown keyword");
+    /// assert_eq!(error.to_string(), "Unknown keyword 'minimun' at '/properties/age/minimun'");
+    /// ```
+    #[inline]
+    pub fn unknown_keywords(&mut self, behavior: UnknownKeywordBehavior) -> &mut Self {
+        self.unknown_keywords = behavior;
+        self
+    }
+
+    /// Register a custom keyword. `factory` builds its validator from the parent schema and
+    /// the keyword value; an error makes the whole schema invalid.
+    ///
+    /// Custom keywords take precedence over built-in ones with the same name. They have access
+    /// to the data passed to `JSONSchema::validate_with_context` and other `_with_context` methods,
+    /// see `Keyword`.
+    #[inline]
+    pub fn with_keyword<F>(&mut self, keyword: impl Into<String>, factory: F) -> &mut Self
+    where
+        F: Fn(&Map<String, Value>, &Value) -> Result<Box<dyn Keyword>, CompilationError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.keywords.insert(keyword.into(), Arc::new(factory));
+        self
+    }
+
+    /// Enable formats specific to `locale`, e.g. `us-ssn`. See `Locale` for the full list.
+    #[cfg(feature = "custom-formats")]
+    #[inline]
+    pub fn with_locale_formats(&mut self, locale: crate::Locale) -> &mut Self {
+        for (format, format_check) in locale.formats() {
+            self.with_format(format, *format_check);
+        }
+        self
+    }
+
+    /// Transform instances before validation, e.g. to normalize them. Transforms are applied to
+    /// the root instance in the registration order.
+    ///
+    /// Validators see only the transformed instance, but errors refer to the original one where
+    /// it has a value at the same location.
+    ///
+    /// ```rust
+    /// # use jsonschema::CompilationOptions;
+    /// # use serde_json::{json, Value};
+    /// fn trim(instance: &Value) -> Value {
+    ///     match instance {
+    ///         Value::String(string) => Value::String(string.trim().to_string()),
+    ///         _ => instance.clone(),
+    ///     }
+    /// }
+    ///
+    /// let schema = json!({"maxLength": 3});
+    /// let compiled = CompilationOptions::default()
+    ///     .with_pre_validate(trim)
+    ///     .compile(&schema)
+    ///     .expect("A valid schema");
+    /// assert!(compiled.is_valid(&json!("  foo  ")));
+    /// let instance = json!(" foobar ");
+    /// let errors: Vec<_> = compiled.validate(&instance).expect_err("Too long").collect();
+    /// assert_eq!(
+    ///     errors[0].to_string(),
+    ///     r#"/maxLength: '" foobar "' is longer than 3 characters (actual: 6)"#
+    /// );
+    /// ```
+    #[inline]
+    pub fn with_pre_validate(&mut self, transform: fn(&Value) -> Value) -> &mut Self {
+        self.pre_validate.push(transform);
+        self
+    }
+
     /// Add a new document to the store. It works as a cache to avoid making additional network
     /// calls to remote schemas via the `$ref` keyword.
     #[inline]
@@ -309,6 +717,21 @@ impl fmt::Debug for CompilationOptions {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt.debug_struct("CompilationConfig")
             .field("draft", &self.draft)
+            .field("openapi_30", &self.openapi_30)
+            .field("lazy", &self.lazy)
+            .field("coerce_types", &self.coerce_types)
+            .field("strict_keywords", &self.strict_keywords)
+            .field("ecma_regex_compat", &self.ecma_regex_compat)
+            .field(
+                "strict_additional_properties",
+                &self.strict_additional_properties,
+            )
+            .field("vocabularies", &self.vocabularies)
+            .field("unknown_keywords", &self.unknown_keywords)
+            .field("keywords", &self.keywords.keys())
+            .field("formats", &self.formats.keys())
+            .field("disabled_formats", &self.disabled_formats)
+            .field("pre_validate", &self.pre_validate.len())
             .field("content_media_type", &self.content_media_type_checks.keys())
             .field(
                 "content_encoding",
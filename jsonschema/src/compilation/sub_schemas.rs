@@ -0,0 +1,245 @@
+//! Depth-first traversal over sub-schemas of a compiled schema.
+use super::{compile_validators, context::CompilationContext, JSONSchema};
+use crate::{
+    resolver::{join_folders, pointer},
+    schemas::{id_of, Draft},
+};
+use ahash::AHashSet;
+use serde_json::Value;
+use std::borrow::Cow;
+use url::Url;
+
+/// Keywords holding a single sub-schema.
+const SINGLE_KEYWORDS: &[&str] = &[
+    "additionalItems",
+    "additionalProperties",
+    "contains",
+    "else",
+    "if",
+    "items",
+    "not",
+    "propertyNames",
+    "then",
+];
+/// Keywords holding an array of sub-schemas.
+const ARRAY_KEYWORDS: &[&str] = &["allOf", "anyOf", "items", "oneOf"];
+/// Keywords holding an object of sub-schemas.
+const OBJECT_KEYWORDS: &[&str] = &[
+    "$defs",
+    "definitions",
+    "dependencies",
+    "patternProperties",
+    "properties",
+];
+
+/// Lazily compiles sub-schemas of `root`, one location at a time.
+pub(crate) struct SubSchemas<'s, 'a> {
+    root: &'s JSONSchema<'a>,
+    /// Scope of the root schema, including its own identifier.
+    scope: Url,
+    /// JSON pointers to the locations that are not visited yet.
+    stack: Vec<String>,
+    /// Visited locations. Each location is compiled once, even if it is referenced many times.
+    visited: AHashSet<String>,
+}
+
+impl<'s, 'a> SubSchemas<'s, 'a> {
+    pub(crate) fn new(root: &'s JSONSchema<'a>) -> Self {
+        let scope = match id_of(root.context.config.draft(), &root.schema) {
+            Some(id) => root
+                .context
+                .build_url(id)
+                .unwrap_or_else(|_| root.context.scope.clone().into_owned()),
+            None => root.context.scope.clone().into_owned(),
+        };
+        SubSchemas {
+            root,
+            scope,
+            stack: vec![String::new()],
+            visited: AHashSet::new(),
+        }
+    }
+
+    fn draft(&self) -> Draft {
+        self.root.context.config.draft()
+    }
+
+    /// Compile the sub-schema at `location` and schedule its own sub-schemas.
+    fn visit(&mut self, location: &str) -> Option<JSONSchema<'s>> {
+        let root: &'s JSONSchema<'a> = self.root;
+        let (folders, schema) = pointer(self.draft(), &root.schema, location)?;
+        let scope = join_folders(self.scope.clone(), &folders).ok()?;
+        if let Value::Object(object) = schema {
+            // References are followed only from the root document, as their targets are
+            // addressed by JSON pointers within it
+            if root.document.is_none() {
+                if let Some(Value::String(reference)) = object.get("$ref") {
+                    if let Some(target) = self.reference_target(schema, &scope, reference) {
+                        self.stack.push(target);
+                    }
+                }
+            }
+            let start = self.stack.len();
+            for (keyword, value) in object {
+                let keyword = keyword.as_str();
+                match value {
+                    Value::Object(_) | Value::Bool(_) if SINGLE_KEYWORDS.contains(&keyword) => {
+                        self.stack.push(child(location, keyword, None));
+                    }
+                    Value::Array(items) if ARRAY_KEYWORDS.contains(&keyword) => {
+                        for idx in 0..items.len() {
+                            self.stack
+                                .push(child(location, keyword, Some(&idx.to_string())));
+                        }
+                    }
+                    Value::Object(map) if OBJECT_KEYWORDS.contains(&keyword) => {
+                        for (name, subschema) in map {
+                            // Skips the array form of `dependencies`
+                            if subschema.is_object() || subschema.is_boolean() {
+                                self.stack.push(child(location, keyword, Some(name)));
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            // Sub-schemas are visited in the same order as they are iterated in `object`
+            self.stack[start..].reverse();
+        }
+        let context = CompilationContext::new(scope, Cow::Borrowed(&*root.context.config));
+        let validators = compile_validators(schema, &context).ok()?;
+        Some(JSONSchema {
+            schema: Cow::Borrowed(schema),
+            document: Some(root.document()),
+            validators,
+            resolver: root.resolver.clone(),
+            context,
+        })
+    }
+
+    /// JSON pointer to the target of `reference` if it is within the root document.
+    fn reference_target(&self, schema: &Value, scope: &Url, reference: &str) -> Option<String> {
+        let scope = match id_of(self.draft(), schema) {
+            Some(id) => scope.join(id).ok()?,
+            None => scope.clone(),
+        };
+        let url = scope.join(reference).ok()?;
+        let mut resource = url.clone();
+        resource.set_fragment(None);
+        let base = self.root.resolver.pointer_of(resource.as_str())?;
+        let fragment = percent_encoding::percent_decode_str(url.fragment().unwrap_or(""))
+            .decode_utf8()
+            .ok()?;
+        if fragment.is_empty() || fragment.starts_with('/') {
+            Some(format!("{}{}", base, fragment))
+        } else {
+            // Location-independent identifiers, e.g. `#foo`
+            self.root
+                .resolver
+                .pointer_of(url.as_str())
+                .map(str::to_string)
+        }
+    }
+}
+
+/// JSON pointer to a sub-schema of the schema at `location`.
+fn child(location: &str, keyword: &str, name: Option<&str>) -> String {
+    let mut pointer = format!("{}/{}", location, escape(keyword));
+    if let Some(name) = name {
+        pointer.push('/');
+        pointer.push_str(&escape(name));
+    }
+    pointer
+}
+
+fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+impl<'s, 'a> Iterator for SubSchemas<'s, 'a> {
+    type Item = (String, JSONSchema<'s>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(location) = self.stack.pop() {
+            if !self.visited.insert(location.clone()) {
+                continue;
+            }
+            if let Some(compiled) = self.visit(&location) {
+                return Some((location, compiled));
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::JSONSchema;
+    use serde_json::{json, Value};
+    use test_case::test_case;
+
+    fn pointers(schema: &Value) -> Vec<String> {
+        let compiled = JSONSchema::compile(schema).unwrap();
+        let pointers = compiled.sub_schemas().map(|(pointer, _)| pointer).collect();
+        pointers
+    }
+
+    #[test_case(&json!({"type": "string"}), &[""]; "no sub-schemas")]
+    #[test_case(
+        &json!({"properties": {"a": {"items": [true, {}]}}, "allOf": [{"not": {}}]}),
+        &["", "/allOf/0", "/allOf/0/not", "/properties/a", "/properties/a/items/0", "/properties/a/items/1"]
+    )]
+    #[test_case(
+        &json!({"dependencies": {"a": ["b"], "c": {}}, "patternProperties": {"a/b~": {}}}),
+        &["", "/dependencies/c", "/patternProperties/a~1b~0"]
+    )]
+    #[test_case(
+        &json!({"$ref": "#/definitions/node", "definitions": {"node": {"properties": {"next": {"$ref": "#/definitions/node"}}}}}),
+        &["", "/definitions/node", "/definitions/node/properties/next"];
+        "recursive reference"
+    )]
+    #[test_case(
+        &json!({"$ref": "#/components/a", "components": {"a": {"type": "integer"}}}),
+        &["", "/components/a"];
+        "reference to unknown keyword"
+    )]
+    #[test_case(
+        &json!({"$id": "http://example.com/root.json", "items": {"$ref": "folder/#/b"}, "x": {"$id": "folder/", "b": {}}}),
+        &["", "/items", "/x/b"];
+        "reference with id"
+    )]
+    fn traversal(schema: &Value, expected: &[&str]) {
+        assert_eq!(pointers(schema), expected);
+    }
+
+    #[test]
+    fn references_are_resolved_against_document() {
+        let schema = json!({
+            "$id": "http://example.com/root.json",
+            "properties": {"a": {"$ref": "#/definitions/int"}},
+            "definitions": {
+                "int": {"type": "integer"},
+                "folder": {
+                    "$id": "folder/",
+                    "properties": {"b": {"$ref": "#/definitions/string"}},
+                    "definitions": {"string": {"type": "string"}}
+                }
+            }
+        });
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        for (pointer, sub_schema) in compiled.sub_schemas() {
+            match pointer.as_str() {
+                "/properties/a" => {
+                    assert!(sub_schema.is_valid(&json!(1)));
+                    assert!(!sub_schema.is_valid(&json!("a")));
+                }
+                "/definitions/folder/properties/b" => {
+                    assert!(sub_schema.is_valid(&json!("a")));
+                    assert!(!sub_schema.is_valid(&json!(1)));
+                }
+                _ => {}
+            }
+            assert_eq!(schema.pointer(&pointer), Some(sub_schema.to_value()));
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use crate::primitive_type::{PrimitiveType, PrimitiveTypesBitMap};
+use crate::{
+    paths::{JSONPointer, PathChunk},
+    primitive_type::{PrimitiveType, PrimitiveTypesBitMap},
+};
 use serde_json::{Map, Number, Value};
 use std::{
     borrow::Cow,
@@ -6,8 +9,10 @@ use std::{
     fmt::{Error, Formatter},
     io,
     iter::{empty, once},
+    ptr,
     str::Utf8Error,
     string::FromUtf8Error,
+    sync::Arc,
 };
 
 /// The error type that happens when the input schema is not valid.
@@ -15,9 +20,80 @@ use std::{
 /// It includes cases when during validation a reference is resolved into an invalid schema,
 /// which we can't know upfront because schemas can be in remote locations.
 #[derive(Debug, PartialEq)]
+#[non_exhaustive]
 pub enum CompilationError {
     /// Invalid schema structure
     SchemaError,
+    /// Invalid value of a schema keyword
+    InvalidKeywordValue {
+        /// The keyword name, e.g. `type`.
+        keyword: String,
+        /// JSON Pointer to the keyword within the schema, e.g. `/properties/foo/type`.
+        path: String,
+        /// The invalid value.
+        value: Value,
+    },
+    /// Invalid regular expression in the `pattern` keyword
+    InvalidPattern {
+        /// The pattern as written in the schema.
+        pattern: String,
+        /// JSON Pointer to the keyword within the schema, e.g. `/properties/foo/pattern`.
+        path: String,
+        /// Why the regular expression failed to compile, e.g. `unclosed character class`.
+        message: String,
+    },
+    /// Keyword that is not defined by the draft, with `UnknownKeywordBehavior::Error`
+    UnknownKeyword {
+        /// The keyword name, e.g. `minimun`.
+        keyword: String,
+        /// JSON Pointer to the keyword within the schema, e.g. `/properties/foo/minimun`.
+        path: String,
+    },
+    /// The meta-schema requires a vocabulary that is not supported
+    UnknownVocabulary {
+        /// The vocabulary URI from `$vocabulary` of the meta-schema.
+        uri: String,
+    },
+}
+
+impl CompilationError {
+    pub(crate) fn invalid_keyword_value(
+        keyword: &str,
+        path: &JSONPointer,
+        value: &Value,
+    ) -> CompilationError {
+        CompilationError::InvalidKeywordValue {
+            keyword: keyword.to_string(),
+            path: path.to_string(),
+            value: value.clone(),
+        }
+    }
+
+    pub(crate) fn invalid_pattern(
+        pattern: &str,
+        path: &JSONPointer,
+        message: impl Into<String>,
+    ) -> CompilationError {
+        CompilationError::InvalidPattern {
+            pattern: pattern.to_string(),
+            path: path.to_string(),
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn from_regex(pattern: &str, path: &JSONPointer, error: &regex::Error) -> Self {
+        let message = match error {
+            // The full message repeats the pattern with a caret under the error position, which
+            // does not match the original pattern after its conversion from ECMA 262 syntax
+            regex::Error::Syntax(message) => message
+                .lines()
+                .find_map(|line| line.strip_prefix("error: "))
+                .unwrap_or(message)
+                .to_string(),
+            _ => error.to_string(),
+        };
+        CompilationError::invalid_pattern(pattern, path, message)
+    }
 }
 
 impl error::Error for CompilationError {}
@@ -25,7 +101,33 @@ impl error::Error for CompilationError {}
 impl fmt::Display for CompilationError {
     #[inline]
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
-        write!(f, "Schema compilation error")
+        match self {
+            CompilationError::SchemaError => write!(f, "Schema compilation error"),
+            CompilationError::InvalidKeywordValue {
+                keyword,
+                path,
+                value,
+            } => write!(
+                f,
+                "Invalid value of the '{}' keyword at '{}': {}",
+                keyword, path, value
+            ),
+            CompilationError::InvalidPattern {
+                pattern,
+                path,
+                message,
+            } => write!(
+                f,
+                "Pattern '{}' at '{}' failed to compile: {}",
+                pattern, path, message
+            ),
+            CompilationError::UnknownKeyword { keyword, path } => {
+                write!(f, "Unknown keyword '{}' at '{}'", keyword, path)
+            }
+            CompilationError::UnknownVocabulary { uri } => {
+                write!(f, "Unknown required vocabulary: '{}'", uri)
+            }
+        }
     }
 }
 
@@ -43,12 +145,116 @@ impl From<url::ParseError> for CompilationError {
 }
 
 /// An error that can occur during validation.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ValidationError<'a> {
     instance: Cow<'a, Value>,
     kind: ValidationErrorKind,
+    details: Box<ErrorDetails<'a>>,
+    instance_path: JSONPointer,
 }
 
+/// Errors of a single sub-schema that caused a `ValidationError`, see `ValidationError::context`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubSchemaErrors<'a> {
+    index: usize,
+    errors: Vec<ValidationError<'a>>,
+}
+
+impl<'a> SubSchemaErrors<'a> {
+    #[inline]
+    pub(crate) fn new(index: usize, errors: Vec<ValidationError<'a>>) -> SubSchemaErrors<'a> {
+        SubSchemaErrors { index, errors }
+    }
+
+    /// Position of the sub-schema in its keyword, e.g. `1` for `anyOf/1`.
+    /// It is `0` for keywords with a single sub-schema, like `then`.
+    #[must_use]
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Errors of the sub-schema.
+    #[must_use]
+    #[inline]
+    pub fn errors(&self) -> &[ValidationError<'a>] {
+        &self.errors
+    }
+
+    fn into_owned(self) -> SubSchemaErrors<'static> {
+        SubSchemaErrors {
+            index: self.index,
+            errors: self
+                .errors
+                .into_iter()
+                .map(ValidationError::into_owned)
+                .collect(),
+        }
+    }
+
+    fn rebind<'b>(self, original: &'b Value, skip: usize) -> SubSchemaErrors<'b> {
+        SubSchemaErrors {
+            index: self.index,
+            errors: self
+                .errors
+                .into_iter()
+                .map(|error| error.rebind(original, skip))
+                .collect(),
+        }
+    }
+
+    fn reborrow<'b>(self, original: &'b Value) -> SubSchemaErrors<'b> {
+        SubSchemaErrors {
+            index: self.index,
+            errors: self
+                .errors
+                .into_iter()
+                .map(|error| error.reborrow(original))
+                .collect(),
+        }
+    }
+}
+
+/// The failed keyword, its location in the schema and errors of sub-schemas that caused it.
+/// Kept out of line, so `ValidationError` stays small enough to be returned by value cheaply.
+#[derive(Debug, Clone, PartialEq)]
+struct ErrorDetails<'a> {
+    path: JSONPointer,
+    keyword: &'static str,
+    context: Vec<SubSchemaErrors<'a>>,
+}
+
+impl<'a> ErrorDetails<'a> {
+    #[inline]
+    fn new(path: JSONPointer, keyword: &'static str) -> Box<ErrorDetails<'a>> {
+        Box::new(ErrorDetails {
+            path,
+            keyword,
+            context: Vec::new(),
+        })
+    }
+}
+
+/// A `ValidationError` that owns the invalid instance and doesn't borrow the validated document.
+///
+/// ```rust
+/// use jsonschema::{JSONSchema, OwnedValidationError};
+/// use serde_json::json;
+///
+/// let schema = json!({"maxLength": 5});
+/// let compiled = JSONSchema::compile(&schema).expect("A valid schema");
+/// let errors: Vec<OwnedValidationError> = {
+///     let instance = json!("foo bar");
+///     compiled
+///         .validate(&instance)
+///         .expect_err("Too long")
+///         .map(|error| error.into_owned())
+///         .collect()
+/// };
+/// assert_eq!(errors.len(), 1);
+/// ```
+pub type OwnedValidationError = ValidationError<'static>;
+
 /// An iterator over instances of `ValidationError` that represent validation error for the
 /// input instance.
 ///
@@ -71,6 +277,21 @@ pub struct ValidationError<'a> {
 /// ```
 pub type ErrorIterator<'a> = Box<dyn Iterator<Item = ValidationError<'a>> + Sync + Send + 'a>;
 
+/// An iterator over owned validation errors. It does not borrow the instance or the schema.
+///
+/// ```rust
+/// use jsonschema::{JSONSchema, OwnedErrorIterator};
+/// use serde_json::json;
+///
+/// let schema = json!({"maxLength": 5});
+/// let compiled = JSONSchema::compile(&schema).expect("A valid schema");
+/// let errors: OwnedErrorIterator = compiled
+///     .validate_owned(json!("foo bar"))
+///     .expect_err("Too long");
+/// assert_eq!(errors.count(), 1);
+/// ```
+pub type OwnedErrorIterator = Box<dyn Iterator<Item = OwnedValidationError> + Sync + Send>;
+
 // Empty iterator means no error happened
 pub(crate) fn no_error<'a>() -> ErrorIterator<'a> {
     Box::new(empty())
@@ -80,11 +301,45 @@ pub(crate) fn error(instance: ValidationError) -> ErrorIterator {
     Box::new(once(instance))
 }
 
-/// Kinds of errors that may happen during validation
+/// A wrapper for errors from other crates that implement neither `Clone` nor `PartialEq`.
+/// Such errors are equal if they have the same textual representation.
 #[derive(Debug)]
+pub(crate) struct SharedError<E>(Arc<E>);
+
+impl<E> Clone for SharedError<E> {
+    #[inline]
+    fn clone(&self) -> Self {
+        SharedError(Arc::clone(&self.0))
+    }
+}
+
+impl<E> From<E> for SharedError<E> {
+    #[inline]
+    fn from(error: E) -> Self {
+        SharedError(Arc::new(error))
+    }
+}
+
+impl<E: fmt::Display> PartialEq for SharedError<E> {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0) || self.0.to_string() == other.0.to_string()
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for SharedError<E> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Kinds of errors that may happen during validation
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) enum ValidationErrorKind {
     /// The input array contain more items than expected.
     AdditionalItems { limit: usize },
+    /// The input value is not valid under some of the `allOf` schemas.
+    AllOf,
     /// The input value is not valid under any of the given schemas.
     AnyOf,
     /// The input value doesn't match expected constant.
@@ -95,16 +350,23 @@ pub(crate) enum ValidationErrorKind {
     ContentEncoding { content_encoding: String },
     /// Ths input value does not respect the defined contentMediaType
     ContentMediaType { content_media_type: String },
+    /// The input value is not valid under a custom keyword.
+    Custom { message: String },
+    /// When a property listed in `dependencies` for another present property is missing.
+    Dependency {
+        property: String,
+        dependency: String,
+    },
     /// The input value doesn't match any of specified options.
     Enum { options: Value },
     /// Value is too large.
-    ExclusiveMaximum { limit: f64 },
+    ExclusiveMaximum { limit: Value },
     /// Value is too small.
-    ExclusiveMinimum { limit: f64 },
+    ExclusiveMinimum { limit: Value },
     /// Everything is invalid for `false` schema.
     FalseSchema,
     /// If the referenced file is not found during ref resolution.
-    FileNotFound { error: io::Error },
+    FileNotFound { error: SharedError<io::Error> },
     /// When the input doesn't match to the specified format.
     Format { format: &'static str },
     /// May happen in `contentEncoding` validation if `base64` encoded data is invalid.
@@ -112,33 +374,43 @@ pub(crate) enum ValidationErrorKind {
     /// Invalid UTF-8 string during percent encoding when resolving happens
     Utf8 { error: Utf8Error },
     /// May happen during ref resolution when remote document is not a valid JSON.
-    JSONParse { error: serde_json::Error },
+    JSONParse {
+        error: SharedError<serde_json::Error>,
+    },
     /// `ref` value is not valid.
     InvalidReference { reference: String },
     /// Invalid URL, e.g. invalid port number or IP address
     InvalidURL { error: url::ParseError },
     /// Too many items in an array.
-    MaxItems { limit: u64 },
+    MaxItems { limit: u64, actual: u64 },
     /// Value is too large.
-    Maximum { limit: f64 },
-    /// String is too long.
-    MaxLength { limit: u64 },
+    Maximum { limit: Value },
+    /// String is too long. With `contentEncoding`, the length of the decoded content in `bytes`.
+    MaxLength {
+        limit: u64,
+        actual: u64,
+        bytes: bool,
+    },
     /// Too many properties in an object.
-    MaxProperties { limit: u64 },
+    MaxProperties { limit: u64, actual: u64 },
     /// Too few items in an array.
-    MinItems { limit: u64 },
+    MinItems { limit: u64, actual: u64 },
     /// Value is too small.
-    Minimum { limit: f64 },
-    /// String is too short.
-    MinLength { limit: u64 },
+    Minimum { limit: Value },
+    /// String is too short. With `contentEncoding`, the length of the decoded content in `bytes`.
+    MinLength {
+        limit: u64,
+        actual: u64,
+        bytes: bool,
+    },
     /// Not enough properties in an object.
-    MinProperties { limit: u64 },
+    MinProperties { limit: u64, actual: u64 },
     /// When some number is not a multiple of another number.
     MultipleOf { multiple_of: f64 },
     /// Negated schema failed validation.
-    Not { schema: Value },
+    Not { description: String },
     /// The given schema is valid under more than one of the given schemas.
-    OneOfMultipleValid,
+    OneOfMultipleValid { matched: Vec<usize> },
     /// The given schema is not valid under any on the given schemas.
     OneOfNotValid,
     /// When the input doesn't match to a pattern.
@@ -147,9 +419,13 @@ pub(crate) enum ValidationErrorKind {
     Required { property: String },
     /// Any error that happens during network request via `reqwest` crate
     #[cfg(any(feature = "reqwest", test))]
-    Reqwest { error: reqwest::Error },
+    Reqwest { error: SharedError<reqwest::Error> },
     /// Resolved schema failed to compile.
     Schema,
+    /// The input value matches the `if` schema, but not the `then` one.
+    Then,
+    /// The input value does not match the `if` schema, nor the `else` one.
+    Else,
     /// When the input value doesn't match one or multiple required types.
     Type { kind: TypeKind },
     /// When the input array has non-unique elements.
@@ -161,261 +437,757 @@ pub(crate) enum ValidationErrorKind {
     Unexpected { validator_representation: String },
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) enum TypeKind {
     Single(PrimitiveType),
     Multiple(PrimitiveTypesBitMap),
 }
 
+/// Predicates for each error kind, e.g. `is_type_error`.
+macro_rules! kind_predicates {
+    ($($(#[$meta:meta])* $name:ident => $kind:ident,)*) => {
+        impl ValidationError<'_> {
+            $(
+                $(#[$meta])*
+                #[must_use]
+                #[inline]
+                pub fn $name(&self) -> bool {
+                    matches!(self.kind, ValidationErrorKind::$kind { .. })
+                }
+            )*
+        }
+    };
+}
+
+kind_predicates! {
+    /// Too many items for `additionalItems`.
+    is_additional_items_error => AdditionalItems,
+    /// Not valid under some of the `allOf` schemas.
+    is_all_of_error => AllOf,
+    /// Not valid under any of the `anyOf` schemas.
+    is_any_of_error => AnyOf,
+    /// Not equal to `const`.
+    is_constant_error => Constant,
+    /// No items matching `contains`.
+    is_contains_error => Contains,
+    /// Not encoded according to `contentEncoding`.
+    is_content_encoding_error => ContentEncoding,
+    /// Not matching `contentMediaType`.
+    is_content_media_type_error => ContentMediaType,
+    /// Reported by a custom keyword.
+    is_custom_error => Custom,
+    /// A property required by the array form of `dependencies` is missing.
+    is_dependency_error => Dependency,
+    /// Not one of the `enum` options.
+    is_enum_error => Enum,
+    /// Not below `exclusiveMaximum`.
+    is_exclusive_maximum_error => ExclusiveMaximum,
+    /// Not above `exclusiveMinimum`.
+    is_exclusive_minimum_error => ExclusiveMinimum,
+    /// Rejected by the `false` schema.
+    is_false_schema_error => FalseSchema,
+    /// A referenced file is not found.
+    is_file_not_found_error => FileNotFound,
+    /// Not matching `format`.
+    is_format_error => Format,
+    /// Decoded `contentEncoding` data is not valid UTF-8.
+    is_from_utf8_error => FromUtf8,
+    /// A percent-encoded reference is not valid UTF-8.
+    is_utf8_error => Utf8,
+    /// A referenced document is not valid JSON.
+    is_json_parse_error => JSONParse,
+    /// `$ref` value is not valid.
+    is_invalid_reference_error => InvalidReference,
+    /// A reference is not a valid URL.
+    is_invalid_url_error => InvalidURL,
+    /// Too many items for `maxItems`.
+    is_max_items_error => MaxItems,
+    /// Above `maximum`.
+    is_maximum_error => Maximum,
+    /// Too long for `maxLength`.
+    is_max_length_error => MaxLength,
+    /// Too many properties for `maxProperties`.
+    is_max_properties_error => MaxProperties,
+    /// Too few items for `minItems`.
+    is_min_items_error => MinItems,
+    /// Below `minimum`.
+    is_minimum_error => Minimum,
+    /// Too short for `minLength`.
+    is_min_length_error => MinLength,
+    /// Too few properties for `minProperties`.
+    is_min_properties_error => MinProperties,
+    /// Not a multiple of `multipleOf`.
+    is_multiple_of_error => MultipleOf,
+    /// Valid under the `not` schema.
+    is_not_error => Not,
+    /// Valid under more than one of the `oneOf` schemas.
+    is_one_of_multiple_valid_error => OneOfMultipleValid,
+    /// Not valid under any of the `oneOf` schemas.
+    is_one_of_not_valid_error => OneOfNotValid,
+    /// Not matching `pattern`.
+    is_pattern_error => Pattern,
+    /// A property from `required` is missing.
+    is_required_error => Required,
+    #[cfg(any(feature = "reqwest", test))]
+    /// A remote document could not be fetched.
+    is_reqwest_error => Reqwest,
+    /// A referenced schema failed to compile.
+    is_schema_error => Schema,
+    /// Matching the `if` schema, but not the `then` one.
+    is_then_error => Then,
+    /// Matching neither the `if` schema, nor the `else` one.
+    is_else_error => Else,
+    /// Not matching `type`.
+    ///
+    /// ```rust
+    /// # use jsonschema::JSONSchema;
+    /// # use serde_json::json;
+    /// let schema = json!({"type": "string", "maxLength": 2});
+    /// let compiled = JSONSchema::compile(&schema).expect("A valid schema");
+    /// let instance = json!(42);
+    /// let error = compiled.first_error(&instance).expect_err("Invalid instance");
+    /// assert!(error.is_type_error());
+    /// assert!(!error.is_max_length_error());
+    /// ```
+    is_type_error => Type,
+    /// Non-unique items for `uniqueItems`.
+    is_unique_items_error => UniqueItems,
+    /// A reference has an unsupported scheme.
+    is_unknown_reference_scheme_error => UnknownReferenceScheme,
+    /// An unexpected error, usually a bug.
+    is_unexpected_error => Unexpected,
+}
+
 /// Shortcuts for creation of specific error kinds.
 impl<'a> ValidationError<'a> {
-    pub(crate) fn into_owned(self) -> ValidationError<'static> {
+    /// Errors of sub-schemas that caused this error, grouped by sub-schema, e.g. errors of each
+    /// `anyOf` branch. Only failed sub-schemas are listed, e.g. a single one for `then`.
+    /// `None` if the error is not caused by other errors, as for `not`.
+    ///
+    /// ```rust
+    /// # use jsonschema::JSONSchema;
+    /// # use serde_json::json;
+    /// let schema = json!({"anyOf": [{"type": "string"}, {"minimum": 5}]});
+    /// let compiled = JSONSchema::compile(&schema).expect("A valid schema");
+    /// let instance = json!(3);
+    /// let error = compiled.first_error(&instance).expect_err("Invalid instance");
+    /// assert_eq!(error.context().map(<[_]>::len), Some(2));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn context(&self) -> Option<&[SubSchemaErrors<'a>]> {
+        if self.details.context.is_empty() {
+            None
+        } else {
+            Some(&self.details.context)
+        }
+    }
+
+    /// Attach errors of sub-schemas that caused this error.
+    #[inline]
+    pub(crate) fn with_context(mut self, context: Vec<SubSchemaErrors<'a>>) -> ValidationError<'a> {
+        self.details.context = context;
+        self
+    }
+
+    /// Convert the error into an `OwnedValidationError` that doesn't borrow the validated instance.
+    #[must_use]
+    pub fn into_owned(self) -> OwnedValidationError {
         ValidationError {
             instance: Cow::Owned(self.instance.into_owned()),
+            details: Box::new(ErrorDetails {
+                path: self.details.path,
+                keyword: self.details.keyword,
+                context: self
+                    .details
+                    .context
+                    .into_iter()
+                    .map(SubSchemaErrors::into_owned)
+                    .collect(),
+            }),
+            kind: self.kind,
+            instance_path: self.instance_path,
+        }
+    }
+
+    /// Point the error to the value at the same location within `original`, e.g. the instance
+    /// before pre-validation transforms. The current value is kept if there is no such location.
+    /// The first `skip` chunks of the instance path are outside of `original`.
+    pub(crate) fn rebind<'b>(self, original: &'b Value, skip: usize) -> ValidationError<'b> {
+        let instance = match self.instance_path.resolve(original, skip) {
+            Some(instance) => Cow::Borrowed(instance),
+            None => Cow::Owned(self.instance.into_owned()),
+        };
+        ValidationError {
+            instance,
+            details: Box::new(ErrorDetails {
+                path: self.details.path,
+                keyword: self.details.keyword,
+                context: self
+                    .details
+                    .context
+                    .into_iter()
+                    .map(|errors| errors.rebind(original, skip))
+                    .collect(),
+            }),
             kind: self.kind,
+            instance_path: self.instance_path,
         }
     }
 
-    pub(crate) fn additional_items(instance: &'a Value, limit: usize) -> ValidationError<'a> {
+    /// Borrow the value from `original` if the error refers to the very same value, e.g. after
+    /// validation with a shorter-lived context. Other values are copied.
+    pub(crate) fn reborrow<'b>(self, original: &'b Value) -> ValidationError<'b> {
+        let instance = match (self.instance_path.resolve(original, 0), &self.instance) {
+            (Some(value), Cow::Borrowed(current)) if ptr::eq(value, *current) => {
+                Cow::Borrowed(value)
+            }
+            _ => Cow::Owned(self.instance.into_owned()),
+        };
+        ValidationError {
+            instance,
+            details: Box::new(ErrorDetails {
+                path: self.details.path,
+                keyword: self.details.keyword,
+                context: self
+                    .details
+                    .context
+                    .into_iter()
+                    .map(|errors| errors.reborrow(original))
+                    .collect(),
+            }),
+            kind: self.kind,
+            instance_path: self.instance_path,
+        }
+    }
+
+    pub(crate) fn additional_items(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+        limit: usize,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "additionalItems"),
+            instance_path,
             kind: ValidationErrorKind::AdditionalItems { limit },
         }
     }
-    pub(crate) fn any_of(instance: &'a Value) -> ValidationError<'a> {
+    pub(crate) fn all_of(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+    ) -> ValidationError<'a> {
+        ValidationError {
+            instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "allOf"),
+            instance_path,
+            kind: ValidationErrorKind::AllOf,
+        }
+    }
+    pub(crate) fn any_of(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "anyOf"),
+            instance_path,
             kind: ValidationErrorKind::AnyOf,
         }
     }
     pub(crate) fn constant_array(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
         instance: &'a Value,
         expected_value: &[Value],
     ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "const"),
+            instance_path,
             kind: ValidationErrorKind::Constant {
                 expected_value: Value::Array(expected_value.to_vec()),
             },
         }
     }
     pub(crate) fn constant_boolean(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
         instance: &'a Value,
         expected_value: bool,
     ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "const"),
+            instance_path,
             kind: ValidationErrorKind::Constant {
                 expected_value: Value::Bool(expected_value),
             },
         }
     }
-    pub(crate) fn constant_null(instance: &'a Value) -> ValidationError<'a> {
+    pub(crate) fn constant_null(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "const"),
+            instance_path,
             kind: ValidationErrorKind::Constant {
                 expected_value: Value::Null,
             },
         }
     }
     pub(crate) fn constant_number(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
         instance: &'a Value,
         expected_value: &Number,
     ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "const"),
+            instance_path,
             kind: ValidationErrorKind::Constant {
                 expected_value: Value::Number(expected_value.clone()),
             },
         }
     }
     pub(crate) fn constant_object(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
         instance: &'a Value,
         expected_value: &Map<String, Value>,
     ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "const"),
+            instance_path,
             kind: ValidationErrorKind::Constant {
                 expected_value: Value::Object(expected_value.clone()),
             },
         }
     }
     pub(crate) fn constant_string(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
         instance: &'a Value,
         expected_value: &str,
     ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "const"),
+            instance_path,
             kind: ValidationErrorKind::Constant {
                 expected_value: Value::String(expected_value.to_string()),
             },
         }
     }
-    pub(crate) fn contains(instance: &'a Value) -> ValidationError<'a> {
+    pub(crate) fn contains(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "contains"),
+            instance_path,
             kind: ValidationErrorKind::Contains,
         }
     }
-    pub(crate) fn content_encoding(instance: &'a Value, encoding: &str) -> ValidationError<'a> {
+    pub(crate) fn content_encoding(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+        encoding: &str,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "contentEncoding"),
+            instance_path,
             kind: ValidationErrorKind::ContentEncoding {
                 content_encoding: encoding.to_string(),
             },
         }
     }
-    pub(crate) fn content_media_type(instance: &'a Value, media_type: &str) -> ValidationError<'a> {
+    pub(crate) fn content_media_type(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+        media_type: &str,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "contentMediaType"),
+            instance_path,
             kind: ValidationErrorKind::ContentMediaType {
                 content_media_type: media_type.to_string(),
             },
         }
     }
-    pub(crate) fn enumeration(instance: &'a Value, options: &Value) -> ValidationError<'a> {
+    pub(crate) fn custom(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+        message: String,
+    ) -> ValidationError<'a> {
+        ValidationError {
+            instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, ""),
+            instance_path,
+            kind: ValidationErrorKind::Custom { message },
+        }
+    }
+    pub(crate) fn dependency(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+        property: String,
+        dependency: String,
+    ) -> ValidationError<'a> {
+        ValidationError {
+            instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "dependencies"),
+            instance_path,
+            kind: ValidationErrorKind::Dependency {
+                property,
+                dependency,
+            },
+        }
+    }
+    pub(crate) fn enumeration(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+        options: &Value,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "enum"),
+            instance_path,
             kind: ValidationErrorKind::Enum {
                 options: options.clone(),
             },
         }
     }
-    pub(crate) fn exclusive_maximum(instance: &'a Value, limit: f64) -> ValidationError<'a> {
+    pub(crate) fn exclusive_maximum(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+        limit: Value,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "exclusiveMaximum"),
+            instance_path,
             kind: ValidationErrorKind::ExclusiveMaximum { limit },
         }
     }
-    pub(crate) fn exclusive_minimum(instance: &'a Value, limit: f64) -> ValidationError<'a> {
+    pub(crate) fn exclusive_minimum(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+        limit: Value,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "exclusiveMinimum"),
+            instance_path,
             kind: ValidationErrorKind::ExclusiveMinimum { limit },
         }
     }
-    pub(crate) fn false_schema(instance: &'a Value) -> ValidationError<'a> {
+    pub(crate) fn false_schema(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+        keyword: &'static str,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            instance_path,
+            details: ErrorDetails::new(schema_path, keyword),
             kind: ValidationErrorKind::FalseSchema,
         }
     }
     pub(crate) fn file_not_found(error: io::Error) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Owned(Value::Null),
-            kind: ValidationErrorKind::FileNotFound { error },
+            details: ErrorDetails::new(JSONPointer::default(), "$ref"),
+            instance_path: JSONPointer::default(),
+            kind: ValidationErrorKind::FileNotFound {
+                error: error.into(),
+            },
         }
     }
-    pub(crate) fn format(instance: &'a Value, format: &'static str) -> ValidationError<'a> {
+    pub(crate) fn format(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+        format: &'static str,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "format"),
+            instance_path,
             kind: ValidationErrorKind::Format { format },
         }
     }
     pub(crate) fn from_utf8(error: FromUtf8Error) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Owned(Value::Null),
+            details: ErrorDetails::new(JSONPointer::default(), "contentEncoding"),
+            instance_path: JSONPointer::default(),
             kind: ValidationErrorKind::FromUtf8 { error },
         }
     }
+    pub(crate) fn if_else(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+    ) -> ValidationError<'a> {
+        ValidationError {
+            instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "else"),
+            instance_path,
+            kind: ValidationErrorKind::Else,
+        }
+    }
+    pub(crate) fn if_then(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+    ) -> ValidationError<'a> {
+        ValidationError {
+            instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "then"),
+            instance_path,
+            kind: ValidationErrorKind::Then,
+        }
+    }
     pub(crate) fn json_parse(error: serde_json::Error) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Owned(Value::Null),
-            kind: ValidationErrorKind::JSONParse { error },
+            details: ErrorDetails::new(JSONPointer::default(), "$ref"),
+            instance_path: JSONPointer::default(),
+            kind: ValidationErrorKind::JSONParse {
+                error: error.into(),
+            },
         }
     }
     pub(crate) fn invalid_reference(reference: String) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Owned(Value::Null),
+            details: ErrorDetails::new(JSONPointer::default(), "$ref"),
+            instance_path: JSONPointer::default(),
             kind: ValidationErrorKind::InvalidReference { reference },
         }
     }
     pub(crate) fn invalid_url(error: url::ParseError) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Owned(Value::Null),
+            details: ErrorDetails::new(JSONPointer::default(), "$ref"),
+            instance_path: JSONPointer::default(),
             kind: ValidationErrorKind::InvalidURL { error },
         }
     }
-    pub(crate) fn max_items(instance: &'a Value, limit: u64) -> ValidationError<'a> {
+    pub(crate) fn max_items(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+        limit: u64,
+        actual: u64,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
-            kind: ValidationErrorKind::MaxItems { limit },
+            details: ErrorDetails::new(schema_path, "maxItems"),
+            instance_path,
+            kind: ValidationErrorKind::MaxItems { limit, actual },
         }
     }
-    pub(crate) fn maximum(instance: &'a Value, limit: f64) -> ValidationError<'a> {
+    pub(crate) fn maximum(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+        limit: Value,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "maximum"),
+            instance_path,
             kind: ValidationErrorKind::Maximum { limit },
         }
     }
-    pub(crate) fn max_length(instance: &'a Value, limit: u64) -> ValidationError<'a> {
+    pub(crate) fn max_length(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+        limit: u64,
+        actual: u64,
+        bytes: bool,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
-            kind: ValidationErrorKind::MaxLength { limit },
+            details: ErrorDetails::new(schema_path, "maxLength"),
+            instance_path,
+            kind: ValidationErrorKind::MaxLength {
+                limit,
+                actual,
+                bytes,
+            },
         }
     }
-    pub(crate) fn max_properties(instance: &'a Value, limit: u64) -> ValidationError<'a> {
+    pub(crate) fn max_properties(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+        limit: u64,
+        actual: u64,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
-            kind: ValidationErrorKind::MaxProperties { limit },
+            details: ErrorDetails::new(schema_path, "maxProperties"),
+            instance_path,
+            kind: ValidationErrorKind::MaxProperties { limit, actual },
         }
     }
-    pub(crate) fn min_items(instance: &'a Value, limit: u64) -> ValidationError<'a> {
+    pub(crate) fn min_items(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+        limit: u64,
+        actual: u64,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
-            kind: ValidationErrorKind::MinItems { limit },
+            details: ErrorDetails::new(schema_path, "minItems"),
+            instance_path,
+            kind: ValidationErrorKind::MinItems { limit, actual },
         }
     }
-    pub(crate) fn minimum(instance: &'a Value, limit: f64) -> ValidationError<'a> {
+    pub(crate) fn minimum(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+        limit: Value,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "minimum"),
+            instance_path,
             kind: ValidationErrorKind::Minimum { limit },
         }
     }
-    pub(crate) fn min_length(instance: &'a Value, limit: u64) -> ValidationError<'a> {
+    pub(crate) fn min_length(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+        limit: u64,
+        actual: u64,
+        bytes: bool,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
-            kind: ValidationErrorKind::MinLength { limit },
+            details: ErrorDetails::new(schema_path, "minLength"),
+            instance_path,
+            kind: ValidationErrorKind::MinLength {
+                limit,
+                actual,
+                bytes,
+            },
         }
     }
-    pub(crate) fn min_properties(instance: &'a Value, limit: u64) -> ValidationError<'a> {
+    pub(crate) fn min_properties(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+        limit: u64,
+        actual: u64,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
-            kind: ValidationErrorKind::MinProperties { limit },
+            details: ErrorDetails::new(schema_path, "minProperties"),
+            instance_path,
+            kind: ValidationErrorKind::MinProperties { limit, actual },
         }
     }
-    pub(crate) fn multiple_of(instance: &'a Value, multiple_of: f64) -> ValidationError<'a> {
+    pub(crate) fn multiple_of(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+        multiple_of: f64,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "multipleOf"),
+            instance_path,
             kind: ValidationErrorKind::MultipleOf { multiple_of },
         }
     }
-    pub(crate) fn not(instance: &'a Value, schema: Value) -> ValidationError<'a> {
+    pub(crate) fn not(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+        description: String,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
-            kind: ValidationErrorKind::Not { schema },
+            details: ErrorDetails::new(schema_path, "not"),
+            instance_path,
+            kind: ValidationErrorKind::Not { description },
         }
     }
-    pub(crate) fn one_of_multiple_valid(instance: &'a Value) -> ValidationError<'a> {
+    pub(crate) fn one_of_multiple_valid(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+        matched: Vec<usize>,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
-            kind: ValidationErrorKind::OneOfMultipleValid,
+            details: ErrorDetails::new(schema_path, "oneOf"),
+            instance_path,
+            kind: ValidationErrorKind::OneOfMultipleValid { matched },
         }
     }
-    pub(crate) fn one_of_not_valid(instance: &'a Value) -> ValidationError<'a> {
+    pub(crate) fn one_of_not_valid(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "oneOf"),
+            instance_path,
             kind: ValidationErrorKind::OneOfNotValid,
         }
     }
-    pub(crate) fn pattern(instance: &'a Value, pattern: String) -> ValidationError<'a> {
+    pub(crate) fn pattern(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+        pattern: String,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "pattern"),
+            instance_path,
             kind: ValidationErrorKind::Pattern { pattern },
         }
     }
-    pub(crate) fn required(instance: &'a Value, property: String) -> ValidationError<'a> {
+    pub(crate) fn required(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+        property: String,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "required"),
+            instance_path,
             kind: ValidationErrorKind::Required { property },
         }
     }
@@ -423,46 +1195,68 @@ impl<'a> ValidationError<'a> {
     pub(crate) fn reqwest(error: reqwest::Error) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Owned(Value::Null),
-            kind: ValidationErrorKind::Reqwest { error },
+            details: ErrorDetails::new(JSONPointer::default(), "$ref"),
+            instance_path: JSONPointer::default(),
+            kind: ValidationErrorKind::Reqwest {
+                error: error.into(),
+            },
         }
     }
     pub(crate) fn schema() -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Owned(Value::Null),
+            details: ErrorDetails::new(JSONPointer::default(), "$ref"),
+            instance_path: JSONPointer::default(),
             kind: ValidationErrorKind::Schema,
         }
     }
     pub(crate) fn single_type_error(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
         instance: &'a Value,
         type_name: PrimitiveType,
     ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "type"),
+            instance_path,
             kind: ValidationErrorKind::Type {
                 kind: TypeKind::Single(type_name),
             },
         }
     }
     pub(crate) fn multiple_type_error(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
         instance: &'a Value,
         types: PrimitiveTypesBitMap,
     ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "type"),
+            instance_path,
             kind: ValidationErrorKind::Type {
                 kind: TypeKind::Multiple(types),
             },
         }
     }
-    pub(crate) fn unique_items(instance: &'a Value) -> ValidationError<'a> {
+    pub(crate) fn unique_items(
+        schema_path: JSONPointer,
+        instance_path: JSONPointer,
+        instance: &'a Value,
+    ) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(schema_path, "uniqueItems"),
+            instance_path,
             kind: ValidationErrorKind::UniqueItems,
         }
     }
     pub(crate) fn unknown_reference_scheme(scheme: String) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Owned(Value::Null),
+            details: ErrorDetails::new(JSONPointer::default(), "$ref"),
+            instance_path: JSONPointer::default(),
             kind: ValidationErrorKind::UnknownReferenceScheme { scheme },
         }
     }
@@ -472,6 +1266,8 @@ impl<'a> ValidationError<'a> {
     pub fn unexpected(instance: &'a Value, validator_representation: &str) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Borrowed(instance),
+            details: ErrorDetails::new(JSONPointer::default(), ""),
+            instance_path: JSONPointer::default(),
             kind: ValidationErrorKind::Unexpected {
                 validator_representation: validator_representation.to_string(),
             },
@@ -480,6 +1276,8 @@ impl<'a> ValidationError<'a> {
     pub(crate) fn utf8(error: Utf8Error) -> ValidationError<'a> {
         ValidationError {
             instance: Cow::Owned(Value::Null),
+            details: ErrorDetails::new(JSONPointer::default(), "$ref"),
+            instance_path: JSONPointer::default(),
             kind: ValidationErrorKind::Utf8 { error },
         }
     }
@@ -531,10 +1329,156 @@ impl From<reqwest::Error> for ValidationError<'_> {
 }
 
 /// Textual representation of various validation errors.
+/// The message is prefixed with a JSON Pointer to the failed keyword, e.g.
+/// `/properties/name/minLength: '"a"' is shorter than 3 characters (actual: 1)`.
 impl fmt::Display for ValidationError<'_> {
-    #[allow(clippy::too_many_lines)] // The function is long but it does formatting only
     #[inline]
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if !self.details.path.is_empty() {
+            write!(f, "{}: ", self.details.path)?;
+        }
+        self.fmt_message(f)
+    }
+}
+
+/// The error message without the schema location, see `ValidationError::message`.
+#[derive(Debug)]
+pub struct ErrorMessage<'e, 'a>(&'e ValidationError<'a>);
+
+impl fmt::Display for ErrorMessage<'_, '_> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt_message(f)
+    }
+}
+
+/// A numeric limit of a keyword. Integers are displayed exactly, floats without a trailing `.0`.
+struct Limit<'v>(&'v Value);
+
+impl fmt::Display for Limit<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.0.as_f64() {
+            Some(limit) if self.0.is_f64() => write!(f, "{}", limit),
+            _ => write!(f, "{}", self.0),
+        }
+    }
+}
+
+impl ValidationError<'_> {
+    /// The error message without the schema location, unlike the `Display` implementation.
+    ///
+    /// ```rust
+    /// # use jsonschema::JSONSchema;
+    /// # use serde_json::json;
+    /// let schema = json!({"properties": {"age": {"minimum": 5}}});
+    /// let compiled = JSONSchema::compile(&schema).expect("A valid schema");
+    /// let instance = json!({"age": 3});
+    /// let error = compiled.first_error(&instance).expect_err("Too small");
+    /// assert_eq!(error.to_string(), "/properties/age/minimum: 3 is less than the minimum of 5");
+    /// assert_eq!(error.message().to_string(), "3 is less than the minimum of 5");
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn message(&self) -> ErrorMessage<'_, '_> {
+        ErrorMessage(self)
+    }
+
+    /// JSON Pointer to the keyword that failed, relative to the schema root.
+    /// For custom keywords it points to the keyword itself.
+    ///
+    /// ```rust
+    /// # use jsonschema::JSONSchema;
+    /// # use serde_json::json;
+    /// let schema = json!({"properties": {"name": {"type": "string"}}});
+    /// let compiled = JSONSchema::compile(&schema).expect("A valid schema");
+    /// let instance = json!({"name": 42});
+    /// let error = compiled.first_error(&instance).expect_err("Invalid instance");
+    /// assert_eq!(error.schema_path().to_string(), "/properties/name/type");
+    /// assert_eq!(error.instance_path().to_string(), "/name");
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn schema_path(&self) -> &JSONPointer {
+        &self.details.path
+    }
+
+    /// JSON Pointer to the invalid part of the validated document.
+    #[must_use]
+    #[inline]
+    pub fn instance_path(&self) -> &JSONPointer {
+        &self.instance_path
+    }
+
+    /// Segments of `instance_path`, without escaping. Array indices are formatted as numbers.
+    ///
+    /// Segments are `Cow<str>` rather than `&str`: property names are borrowed, but array indices
+    /// are stored as numbers, so their textual form is created on demand.
+    ///
+    /// ```rust
+    /// # use jsonschema::JSONSchema;
+    /// # use serde_json::json;
+    /// let schema = json!({"properties": {"tags": {"items": {"type": "string"}}}});
+    /// let compiled = JSONSchema::compile(&schema).expect("A valid schema");
+    /// let instance = json!({"tags": ["a", 1]});
+    /// let error = compiled.first_error(&instance).expect_err("Invalid instance");
+    /// assert_eq!(error.path_segments().collect::<Vec<_>>(), ["tags", "1"]);
+    /// assert_eq!(error.path_len(), 2);
+    /// ```
+    #[inline]
+    pub fn path_segments(&self) -> impl Iterator<Item = Cow<'_, str>> {
+        self.instance_path.chunks().iter().map(|chunk| match chunk {
+            PathChunk::Property(name) => Cow::Borrowed(&**name),
+            PathChunk::Index(idx) => Cow::Owned(idx.to_string()),
+        })
+    }
+
+    /// Number of segments in `instance_path`.
+    #[must_use]
+    #[inline]
+    pub fn path_len(&self) -> usize {
+        self.instance_path.chunks().len()
+    }
+
+    /// Whether the error is about the validated document itself, not about a part of it.
+    #[must_use]
+    #[inline]
+    pub fn path_is_empty(&self) -> bool {
+        self.instance_path.is_empty()
+    }
+
+    /// Name of the keyword that failed, e.g. `type` or `minimum`. It is empty if the error is not
+    /// caused by a keyword, e.g. for the `false` schema.
+    ///
+    /// ```rust
+    /// # use jsonschema::JSONSchema;
+    /// # use serde_json::json;
+    /// let schema = json!({"properties": {"age": {"minimum": 0}}});
+    /// let compiled = JSONSchema::compile(&schema).expect("A valid schema");
+    /// let instance = json!({"age": -1});
+    /// let error = compiled.first_error(&instance).expect_err("Invalid instance");
+    /// assert_eq!(error.keyword(), "minimum");
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn keyword(&self) -> &str {
+        match (&self.kind, self.details.path.chunks().last()) {
+            // Names of custom keywords are known only at runtime
+            (ValidationErrorKind::Custom { .. }, Some(PathChunk::Property(name))) => name,
+            _ => self.details.keyword,
+        }
+    }
+
+    /// `instance_path` formatted as defined in RFC 6901, e.g. `/items/0/name`.
+    /// Array indices are numbers, `~` and `/` in property names are escaped as `~0` and `~1`.
+    #[must_use]
+    #[inline]
+    pub fn path_string(&self) -> String {
+        self.instance_path.to_string()
+    }
+
+    #[allow(clippy::too_many_lines)] // The function is long but it does formatting only
+    fn fmt_message(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match &self.kind {
             ValidationErrorKind::Schema => write!(f, "Schema error"),
             ValidationErrorKind::JSONParse { error } => write!(f, "{}", error),
@@ -576,6 +1520,21 @@ impl fmt::Display for ValidationError<'_> {
                     verb
                 )
             }
+            ValidationErrorKind::AllOf => write!(
+                f,
+                "'{}' is not valid under all of the given schemas",
+                self.instance
+            ),
+            ValidationErrorKind::Then => write!(
+                f,
+                "'{}' is valid under the 'if' schema, but not under the 'then' schema",
+                self.instance
+            ),
+            ValidationErrorKind::Else => write!(
+                f,
+                "'{}' is not valid under the 'if' schema, nor under the 'else' schema",
+                self.instance
+            ),
             ValidationErrorKind::AnyOf | ValidationErrorKind::OneOfNotValid => write!(
                 f,
                 "'{}' is not valid under any of the given schemas",
@@ -586,6 +1545,15 @@ impl fmt::Display for ValidationError<'_> {
                 "None of '{}' are valid under the given schema",
                 self.instance
             ),
+            ValidationErrorKind::Custom { message } => f.write_str(message),
+            ValidationErrorKind::Dependency {
+                property,
+                dependency,
+            } => write!(
+                f,
+                "'{}' is a required property when '{}' is present",
+                dependency, property
+            ),
             ValidationErrorKind::Constant { expected_value } => {
                 write!(f, "'{}' was expected", expected_value)
             }
@@ -603,12 +1571,12 @@ impl fmt::Display for ValidationError<'_> {
             ValidationErrorKind::ExclusiveMaximum { limit } => write!(
                 f,
                 "{} is greater than or equal to the maximum of {}",
-                self.instance, limit
+                self.instance, Limit(limit)
             ),
             ValidationErrorKind::ExclusiveMinimum { limit } => write!(
                 f,
                 "{} is less than or equal to the minimum of {}",
-                self.instance, limit
+                self.instance, Limit(limit)
             ),
             ValidationErrorKind::FalseSchema => {
                 write!(f, "False schema does not allow '{}'", self.instance)
@@ -619,60 +1587,81 @@ impl fmt::Display for ValidationError<'_> {
             ValidationErrorKind::Maximum { limit } => write!(
                 f,
                 "{} is greater than the maximum of {}",
-                self.instance, limit
+                self.instance, Limit(limit)
             ),
             ValidationErrorKind::Minimum { limit } => {
-                write!(f, "{} is less than the minimum of {}", self.instance, limit)
+                write!(f, "{} is less than the minimum of {}", self.instance, Limit(limit))
             }
-            ValidationErrorKind::MaxLength { limit } => write!(
+            ValidationErrorKind::MaxLength {
+                limit,
+                actual,
+                bytes,
+            } => write!(
                 f,
-                "'{}' is longer than {} character{}",
+                "'{}' is longer than {} {}{} (actual: {})",
                 self.instance,
                 limit,
-                if *limit == 1 { "" } else { "s" }
+                if *bytes { "byte" } else { "character" },
+                if *limit == 1 { "" } else { "s" },
+                actual
             ),
-            ValidationErrorKind::MinLength { limit } => write!(
+            ValidationErrorKind::MinLength {
+                limit,
+                actual,
+                bytes,
+            } => write!(
                 f,
-                "'{}' is shorter than {} character{}",
+                "'{}' is shorter than {} {}{} (actual: {})",
                 self.instance,
                 limit,
-                if *limit == 1 { "" } else { "s" }
+                if *bytes { "byte" } else { "character" },
+                if *limit == 1 { "" } else { "s" },
+                actual
             ),
-            ValidationErrorKind::MaxItems { limit } => write!(
+            ValidationErrorKind::MaxItems { limit, actual } => write!(
                 f,
-                "{} has more than {} item{}",
+                "{} has more than {} item{} (actual: {})",
                 self.instance,
                 limit,
-                if *limit == 1 { "" } else { "s" }
+                if *limit == 1 { "" } else { "s" },
+                actual
             ),
-            ValidationErrorKind::MinItems { limit } => write!(
+            ValidationErrorKind::MinItems { limit, actual } => write!(
                 f,
-                "{} has less than {} item{}",
+                "{} has less than {} item{} (actual: {})",
                 self.instance,
                 limit,
-                if *limit == 1 { "" } else { "s" }
+                if *limit == 1 { "" } else { "s" },
+                actual
             ),
-            ValidationErrorKind::MaxProperties { limit } => write!(
+            ValidationErrorKind::MaxProperties { limit, actual } => write!(
                 f,
-                "{} has more than {} propert{}",
+                "{} has more than {} propert{} (actual: {})",
                 self.instance,
                 limit,
-                if *limit == 1 { "y" } else { "ies" }
+                if *limit == 1 { "y" } else { "ies" },
+                actual
             ),
-            ValidationErrorKind::MinProperties { limit } => write!(
+            ValidationErrorKind::MinProperties { limit, actual } => write!(
                 f,
-                "{} has less than {} propert{}",
+                "{} has less than {} propert{} (actual: {})",
                 self.instance,
                 limit,
-                if *limit == 1 { "y" } else { "ies" }
+                if *limit == 1 { "y" } else { "ies" },
+                actual
             ),
-            ValidationErrorKind::Not { schema } => {
-                write!(f, "{} is not allowed for {}", schema, self.instance)
+            ValidationErrorKind::Not { description } => {
+                write!(f, "{} must NOT match: ({})", self.instance, description)
             }
-            ValidationErrorKind::OneOfMultipleValid => write!(
+            ValidationErrorKind::OneOfMultipleValid { matched } => write!(
                 f,
-                "'{}' is valid under more than one of the given schemas",
-                self.instance
+                "'{}' is valid under more than one of the given schemas (matched: {})",
+                self.instance,
+                matched
+                    .iter()
+                    .map(usize::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
             ),
             ValidationErrorKind::Pattern { pattern } => {
                 write!(f, "'{}' does not match '{}'", self.instance, pattern)
@@ -716,11 +1705,17 @@ impl fmt::Display for ValidationError<'_> {
 mod tests {
     use super::*;
     use serde_json::json;
+    use test_case::test_case;
 
     #[test]
     fn single_type_error() {
         let instance = json!(42);
-        let err = ValidationError::single_type_error(&instance, PrimitiveType::String);
+        let err = ValidationError::single_type_error(
+            JSONPointer::default(),
+            JSONPointer::default(),
+            &instance,
+            PrimitiveType::String,
+        );
         assert_eq!(err.to_string(), "'42' is not of type 'string'")
     }
 
@@ -728,9 +1723,154 @@ mod tests {
     fn multiple_types_error() {
         let instance = json!(42);
         let err = ValidationError::multiple_type_error(
+            JSONPointer::default(),
+            JSONPointer::default(),
             &instance,
             vec![PrimitiveType::String, PrimitiveType::Number].into(),
         );
         assert_eq!(err.to_string(), "'42' is not of types 'number', 'string'")
     }
+
+    #[test]
+    fn errors_are_comparable() {
+        let instance = json!(42);
+        let err = ValidationError::single_type_error(
+            JSONPointer::default(),
+            JSONPointer::default(),
+            &instance,
+            PrimitiveType::String,
+        );
+        assert_eq!(err, err.clone());
+        assert_ne!(
+            err,
+            ValidationError::single_type_error(
+                JSONPointer::default(),
+                JSONPointer::default(),
+                &instance,
+                PrimitiveType::Array
+            )
+        );
+        let other = json!(43);
+        assert_ne!(
+            err,
+            ValidationError::single_type_error(
+                JSONPointer::default(),
+                JSONPointer::default(),
+                &other,
+                PrimitiveType::String
+            )
+        );
+    }
+
+    #[test]
+    fn into_owned() {
+        let owned: OwnedValidationError = {
+            let instance = json!(42);
+            ValidationError::minimum(
+                JSONPointer::default(),
+                JSONPointer::default(),
+                &instance,
+                json!(50),
+            )
+            .into_owned()
+        };
+        let instance = json!(42);
+        assert_eq!(
+            owned,
+            ValidationError::minimum(
+                JSONPointer::default(),
+                JSONPointer::default(),
+                &instance,
+                json!(50),
+            )
+        );
+        assert_eq!(owned.to_string(), "42 is less than the minimum of 50");
+    }
+
+    #[test]
+    fn shared_errors_are_comparable() {
+        let first = ValidationError::file_not_found(io::Error::new(io::ErrorKind::Other, "foo"));
+        let second = ValidationError::file_not_found(io::Error::new(io::ErrorKind::Other, "foo"));
+        let third = ValidationError::file_not_found(io::Error::new(io::ErrorKind::Other, "bar"));
+        assert_eq!(first, first.clone());
+        assert_eq!(first, second);
+        assert_ne!(first, third);
+    }
+
+    #[test]
+    fn kind_predicates() {
+        let schema =
+            json!({"properties": {"a": {"pattern": "^b"}}, "required": ["c"], "anyOf": [false]});
+        let compiled = crate::JSONSchema::compile(&schema).unwrap();
+        let instance = json!({"a": "x"});
+        let errors: Vec<_> = compiled.validate(&instance).unwrap_err().collect();
+        assert_eq!(errors.len(), 3);
+        assert!(errors.iter().any(ValidationError::is_pattern_error));
+        assert!(errors.iter().any(ValidationError::is_required_error));
+        assert!(errors.iter().any(ValidationError::is_any_of_error));
+        assert!(errors
+            .iter()
+            .all(|error| !error.is_type_error() && !error.is_false_schema_error()));
+    }
+
+    #[test_case(&json!({"items": {"properties": {"name": {"type": "string"}}}}), &json!([{"name": 1}]), "/0/name")]
+    #[test_case(
+        &json!({"properties": {"a": {"items": {"properties": {"b": {"items": {"type": "string"}}}}}}}),
+        &json!({"a": [{}, {"b": ["x", 2]}]}),
+        "/a/1/b/1"
+    )]
+    #[test_case(&json!({"items": [{"items": [true, {"type": "string"}]}]}), &json!([[1, 2]]), "/0/1"; "tuples")]
+    #[test_case(&json!({"properties": {"a/b": {"properties": {"c~d": {"type": "string"}}}}}), &json!({"a/b": {"c~d": 1}}), "/a~1b/c~0d")]
+    #[test_case(&json!({"additionalProperties": {"items": {"type": "string"}}}), &json!({"0": [1]}), "/0/0"; "numeric property")]
+    #[test_case(&json!({"type": "string"}), &json!(1), ""; "root")]
+    fn path_string(schema: &Value, instance: &Value, expected: &str) {
+        let compiled = crate::JSONSchema::compile(schema).unwrap();
+        let error = compiled.first_error(instance).unwrap_err();
+        assert_eq!(error.path_string(), expected);
+    }
+
+    #[test_case(&json!({"type": "string"}), &json!(1), "type")]
+    #[test_case(&json!({"type": ["string", "null"]}), &json!(1), "type"; "multiple types")]
+    #[test_case(&json!({"minimum": 5}), &json!(1), "minimum")]
+    #[test_case(&json!({"const": 5}), &json!(1), "const")]
+    #[test_case(&json!({"required": ["a"]}), &json!({}), "required")]
+    #[test_case(&json!({"oneOf": [{}, {}]}), &json!(1), "oneOf")]
+    #[test_case(&json!({"properties": {"a": {"maxLength": 1}}}), &json!({"a": "ab"}), "maxLength")]
+    #[test_case(&json!({"additionalProperties": false}), &json!({"a": 1}), "additionalProperties")]
+    #[test_case(&json!({"items": false}), &json!([1]), "items")]
+    #[test_case(&json!({"properties": {"a": false}}), &json!({"a": 1}), ""; "false schema")]
+    fn keyword(schema: &Value, instance: &Value, expected: &str) {
+        let compiled = crate::JSONSchema::compile(schema).unwrap();
+        let error = compiled.first_error(instance).unwrap_err();
+        assert_eq!(error.keyword(), expected);
+    }
+
+    #[test]
+    fn custom_keyword() {
+        struct Never;
+        impl crate::Keyword for Never {
+            fn validate(&self, _: &Value, _: &crate::ValidationContext) -> Result<(), String> {
+                Err("Never valid".to_string())
+            }
+        }
+        let schema = json!({"properties": {"a": {"never": true}}});
+        let compiled = crate::JSONSchema::options()
+            .with_keyword("never", |_, _| Ok(Box::new(Never)))
+            .compile(&schema)
+            .unwrap();
+        let instance = json!({"a": 1});
+        let error = compiled.first_error(&instance).unwrap_err();
+        assert_eq!(error.keyword(), "never");
+    }
+
+    #[test_case(&json!({"type": "string"}), &json!(1), &[]; "root")]
+    #[test_case(&json!({"items": {"type": "string"}}), &json!([1]), &["0"])]
+    #[test_case(&json!({"properties": {"a/b~": {"items": {"type": "string"}}}}), &json!({"a/b~": ["x", 1]}), &["a/b~", "1"])]
+    fn path_segments(schema: &Value, instance: &Value, expected: &[&str]) {
+        let compiled = crate::JSONSchema::compile(schema).unwrap();
+        let error = compiled.first_error(instance).unwrap_err();
+        assert_eq!(error.path_segments().collect::<Vec<_>>(), expected);
+        assert_eq!(error.path_len(), expected.len());
+        assert_eq!(error.path_is_empty(), expected.is_empty());
+    }
 }
@@ -0,0 +1,96 @@
+//! Per-thread overrides of `format` checks.
+use ahash::AHashMap;
+use std::{cell::RefCell, fmt, marker::PhantomData};
+
+pub(crate) type FormatCheckType = fn(&str) -> bool;
+
+thread_local! {
+    static FORMAT_REGISTRY: RefCell<AHashMap<&'static str, FormatCheckType>> =
+        RefCell::new(AHashMap::new());
+}
+
+/// A handle to format checks installed for the current thread.
+///
+/// They take precedence over checks from `CompilationOptions::with_format` and built-in ones,
+/// which makes it possible to override a format in a single test without affecting others.
+/// Checks are captured when compilation starts, therefore already compiled schemas are not
+/// affected. It includes sub-schemas compiled lazily, even if they are validated on another thread.
+///
+/// ```rust
+/// # use jsonschema::{FormatRegistry, JSONSchema};
+/// # use serde_json::json;
+/// fn any_email(_: &str) -> bool {
+///     true
+/// }
+///
+/// let schema = json!({"format": "email"});
+/// let registry = FormatRegistry::current();
+/// registry.insert("email", any_email);
+/// let compiled = JSONSchema::compile(&schema).expect("A valid schema");
+/// assert!(compiled.is_valid(&json!("not an email")));
+/// registry.remove("email");
+/// let compiled = JSONSchema::compile(&schema).expect("A valid schema");
+/// assert!(!compiled.is_valid(&json!("not an email")));
+/// ```
+#[derive(Clone, Copy)]
+pub struct FormatRegistry {
+    // The registry belongs to the current thread, the handle should not leave it
+    _thread_bound: PhantomData<*const ()>,
+}
+
+impl FormatRegistry {
+    /// The registry of the current thread.
+    #[must_use]
+    #[inline]
+    pub fn current() -> Self {
+        FormatRegistry {
+            _thread_bound: PhantomData,
+        }
+    }
+
+    /// Install a check for `format`. It should return `true` for valid strings.
+    /// Returns the previously installed check, if any.
+    #[inline]
+    pub fn insert(
+        &self,
+        format: &'static str,
+        format_check: fn(&str) -> bool,
+    ) -> Option<fn(&str) -> bool> {
+        FORMAT_REGISTRY.with(|registry| registry.borrow_mut().insert(format, format_check))
+    }
+
+    /// Remove the check for `format` installed for the current thread.
+    #[inline]
+    pub fn remove(&self, format: &str) -> Option<fn(&str) -> bool> {
+        FORMAT_REGISTRY.with(|registry| registry.borrow_mut().remove(format))
+    }
+
+    /// Remove all checks installed for the current thread.
+    #[inline]
+    pub fn clear(&self) {
+        FORMAT_REGISTRY.with(|registry| registry.borrow_mut().clear())
+    }
+
+    /// The check for `format` installed for the current thread.
+    #[must_use]
+    #[inline]
+    pub fn get(&self, format: &str) -> Option<fn(&str) -> bool> {
+        FORMAT_REGISTRY.with(|registry| registry.borrow().get(format).copied())
+    }
+}
+
+impl fmt::Debug for FormatRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        FORMAT_REGISTRY.with(|registry| {
+            f.debug_struct("FormatRegistry")
+                .field("formats", &registry.borrow().keys())
+                .finish()
+        })
+    }
+}
+
+/// All checks installed for the current thread.
+#[inline]
+pub(crate) fn snapshot() -> AHashMap<&'static str, FormatCheckType> {
+    FORMAT_REGISTRY.with(|registry| registry.borrow().clone())
+}
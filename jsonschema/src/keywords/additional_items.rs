@@ -5,9 +5,12 @@ use crate::{
         boolean::{FalseValidator, TrueValidator},
         format_validators, CompilationResult, Validators,
     },
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use serde_json::{Map, Value};
+use std::fmt;
 
 pub(crate) struct AdditionalItemsObjectValidator {
     validators: Validators,
@@ -28,28 +31,37 @@ impl AdditionalItemsObjectValidator {
     }
 }
 impl Validate for AdditionalItemsObjectValidator {
-    fn is_valid(&self, schema: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
         if let Value::Array(items) = instance {
             items.iter().skip(self.items_count).all(|item| {
                 self.validators
                     .iter()
-                    .all(move |validator| validator.is_valid(schema, item))
+                    .all(move |validator| validator.is_valid(schema, item, ctx))
             })
         } else {
             true
         }
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::Array(items) = instance {
             let errors: Vec<_> = items
                 .iter()
+                .enumerate()
                 .skip(self.items_count)
-                .flat_map(|item| {
-                    self.validators
-                        .iter()
-                        .flat_map(move |validator| validator.validate(schema, item))
+                .flat_map(|(idx, item)| {
+                    let instance_path = instance_path.extend_with(idx);
+                    self.validators.iter().flat_map(move |validator| {
+                        validator.validate(schema, item, &instance_path, ctx)
+                    })
                 })
+                .take(ctx.error_budget())
                 .collect();
             Box::new(errors.into_iter())
         } else {
@@ -57,23 +69,31 @@ impl Validate for AdditionalItemsObjectValidator {
         }
     }
 }
-impl ToString for AdditionalItemsObjectValidator {
-    fn to_string(&self) -> String {
-        format!("additionalItems: {}", format_validators(&self.validators))
+impl fmt::Display for AdditionalItemsObjectValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "additionalItems: {}",
+            format_validators(&self.validators)
+        )
     }
 }
 
 pub(crate) struct AdditionalItemsBooleanValidator {
     items_count: usize,
+    schema_path: JSONPointer,
 }
 impl AdditionalItemsBooleanValidator {
     #[inline]
-    pub(crate) fn compile(items_count: usize) -> CompilationResult {
-        Ok(Box::new(AdditionalItemsBooleanValidator { items_count }))
+    pub(crate) fn compile(items_count: usize, schema_path: JSONPointer) -> CompilationResult {
+        Ok(Box::new(AdditionalItemsBooleanValidator {
+            items_count,
+            schema_path,
+        }))
     }
 }
 impl Validate for AdditionalItemsBooleanValidator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         if let Value::Array(items) = instance {
             if items.len() > self.items_count {
                 return false;
@@ -82,10 +102,18 @@ impl Validate for AdditionalItemsBooleanValidator {
         true
     }
 
-    fn validate<'a>(&self, _: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        _: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        _: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::Array(items) = instance {
             if items.len() > self.items_count {
                 return error(ValidationError::additional_items(
+                    self.schema_path.clone(),
+                    instance_path.clone(),
                     instance,
                     self.items_count,
                 ));
@@ -94,9 +122,9 @@ impl Validate for AdditionalItemsBooleanValidator {
         no_error()
     }
 }
-impl ToString for AdditionalItemsBooleanValidator {
-    fn to_string(&self) -> String {
-        "additionalItems: false".to_string()
+impl fmt::Display for AdditionalItemsBooleanValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("additionalItems: false")
     }
 }
 
@@ -118,9 +146,10 @@ pub(crate) fn compile(
                         context,
                     )),
                     Value::Bool(true) => Some(TrueValidator::compile()),
-                    Value::Bool(false) => {
-                        Some(AdditionalItemsBooleanValidator::compile(items_count))
-                    }
+                    Value::Bool(false) => Some(AdditionalItemsBooleanValidator::compile(
+                        items_count,
+                        context.schema_path.clone(),
+                    )),
                     _ => None,
                 }
             }
@@ -128,7 +157,7 @@ pub(crate) fn compile(
                 if *value {
                     Some(TrueValidator::compile())
                 } else {
-                    Some(FalseValidator::compile())
+                    Some(FalseValidator::compile(context.schema_path.clone()))
                 }
             }
             _ => Some(Err(CompilationError::SchemaError)),
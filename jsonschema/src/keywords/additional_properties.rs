@@ -9,26 +9,32 @@
 use crate::{
     compilation::{compile_validators, context::CompilationContext, JSONSchema},
     error::{error, no_error, CompilationError, ErrorIterator, ValidationError},
-    keywords::{format_validators, CompilationResult, Validators},
+    keywords::{
+        format_validators, pattern, properties::annotate_properties, CompilationResult, Validators,
+    },
+    output::PartialApplication,
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use ahash::AHashMap;
 use regex::Regex;
 use serde_json::{Map, Value};
+use std::fmt;
 
 pub(crate) type PatternedValidators = Vec<(Regex, Validators)>;
 
 macro_rules! is_valid {
-    ($validators:expr, $schema:ident, $value:ident) => {{
+    ($validators:expr, $schema:ident, $value:ident, $ctx:ident) => {{
         $validators
             .iter()
-            .all(|validator| validator.is_valid($schema, $value))
+            .all(|validator| validator.is_valid($schema, $value, $ctx))
     }};
 }
 
 macro_rules! is_valid_pattern_schema {
-    ($validators:expr, $schema:ident, $value:ident) => {{
-        if is_valid!($validators, $schema, $value) {
+    ($validators:expr, $schema:ident, $value:ident, $ctx:ident) => {{
+        if is_valid!($validators, $schema, $value, $ctx) {
             // Matched & valid - check the next pattern
             continue;
         } else {
@@ -39,14 +45,14 @@ macro_rules! is_valid_pattern_schema {
 }
 
 macro_rules! is_valid_patterns {
-    ($schema:ident, $patterns:expr, $property:ident, $value:ident) => {{
+    ($schema:ident, $patterns:expr, $property:ident, $value:ident, $ctx:ident) => {{
         // One property may match multiple patterns, therefore we need to check them all
         let mut has_match = false;
         for (re, validators) in $patterns {
             // If there is a match, then the value should match the sub-schema
             if re.is_match($property) {
                 has_match = true;
-                is_valid_pattern_schema!(validators, $schema, $value)
+                is_valid_pattern_schema!(validators, $schema, $value, $ctx)
             }
         }
         if !has_match {
@@ -57,27 +63,69 @@ macro_rules! is_valid_patterns {
 }
 
 macro_rules! validate {
-    ($validators:expr, $schema:ident, $value:ident) => {{
+    ($validators:expr, $schema:ident, $value:ident, $instance_path:expr, $ctx:ident) => {{
+        let instance_path = $instance_path;
         $validators
             .iter()
-            .flat_map(move |validator| validator.validate($schema, $value))
+            .flat_map(move |validator| validator.validate($schema, $value, &instance_path, $ctx))
     }};
 }
 
 macro_rules! disallow_property {
-    ($errors:ident, $property:ident) => {{
+    ($errors:ident, $property:ident, $schema_path:expr, $instance_path:expr) => {{
         let property_value = Value::String($property.to_string());
-        $errors.push(ValidationError::false_schema(&property_value).into_owned());
+        $errors.push(
+            ValidationError::false_schema(
+                $schema_path.clone(),
+                $instance_path.clone(),
+                &property_value,
+                "additionalProperties",
+            )
+            .into_owned(),
+        );
     }};
 }
 
+/// Collect errors and annotate instance properties evaluated by the sibling `properties` keyword.
+macro_rules! apply_properties {
+    () => {
+        fn apply<'a>(
+            &self,
+            schema: &'a JSONSchema,
+            instance: &'a Value,
+            instance_path: &JSONPointer,
+            ctx: &'a ValidationContext,
+        ) -> PartialApplication<'a> {
+            let result = self
+                .validate(schema, instance, instance_path, ctx)
+                .collect::<Vec<_>>()
+                .into();
+            if let Value::Object(item) = instance {
+                annotate_properties(
+                    result,
+                    item.keys()
+                        .filter(|property| self.properties.contains_key(*property)),
+                )
+            } else {
+                result
+            }
+        }
+    };
+}
+
+/// Compile sub-schemas of the sibling `properties` keyword.
 fn compile_properties(
     map: &Map<String, Value>,
     context: &CompilationContext,
 ) -> Result<AHashMap<String, Validators>, CompilationError> {
+    let properties_context = context.with_sibling("properties");
     let mut properties = AHashMap::with_capacity(map.len());
     for (key, subschema) in map {
-        properties.insert(key.clone(), compile_validators(subschema, context)?);
+        let property_context = properties_context.with_path(key.as_str());
+        properties.insert(
+            key.clone(),
+            compile_validators(subschema, &property_context)?,
+        );
     }
     Ok(properties)
 }
@@ -109,10 +157,10 @@ impl AdditionalPropertiesValidator {
     }
 }
 impl Validate for AdditionalPropertiesValidator {
-    fn is_valid(&self, schema: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
         if let Value::Object(item) = instance {
             for value in item.values() {
-                if !is_valid!(self.validators, schema, value) {
+                if !is_valid!(self.validators, schema, value, ctx) {
                     return false;
                 }
             }
@@ -120,11 +168,26 @@ impl Validate for AdditionalPropertiesValidator {
         true
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::Object(item) = instance {
             let errors: Vec<_> = item
-                .values()
-                .flat_map(|value| validate!(&self.validators, schema, value))
+                .iter()
+                .flat_map(|(property, value)| {
+                    validate!(
+                        &self.validators,
+                        schema,
+                        value,
+                        instance_path.extend_with(property.as_str()),
+                        ctx
+                    )
+                })
+                .take(ctx.error_budget())
                 .collect();
             Box::new(errors.into_iter())
         } else {
@@ -133,9 +196,10 @@ impl Validate for AdditionalPropertiesValidator {
     }
 }
 
-impl ToString for AdditionalPropertiesValidator {
-    fn to_string(&self) -> String {
-        format!(
+impl fmt::Display for AdditionalPropertiesValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
             "additionalProperties: {}",
             format_validators(&self.validators)
         )
@@ -155,15 +219,17 @@ impl ToString for AdditionalPropertiesValidator {
 /// ```json
 /// {}
 /// ```
-pub(crate) struct AdditionalPropertiesFalseValidator {}
+pub(crate) struct AdditionalPropertiesFalseValidator {
+    schema_path: JSONPointer,
+}
 impl AdditionalPropertiesFalseValidator {
     #[inline]
-    pub(crate) fn compile() -> CompilationResult {
-        Ok(Box::new(AdditionalPropertiesFalseValidator {}))
+    pub(crate) fn compile(schema_path: JSONPointer) -> CompilationResult {
+        Ok(Box::new(AdditionalPropertiesFalseValidator { schema_path }))
     }
 }
 impl Validate for AdditionalPropertiesFalseValidator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         if let Value::Object(item) = instance {
             item.iter().next().is_none()
         } else {
@@ -171,18 +237,29 @@ impl Validate for AdditionalPropertiesFalseValidator {
         }
     }
 
-    fn validate<'a>(&self, _: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        _: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        _: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::Object(item) = instance {
             if let Some((_, value)) = item.iter().next() {
-                return error(ValidationError::false_schema(value));
+                return error(ValidationError::false_schema(
+                    self.schema_path.clone(),
+                    instance_path.clone(),
+                    value,
+                    "additionalProperties",
+                ));
             }
         }
         no_error()
     }
 }
-impl ToString for AdditionalPropertiesFalseValidator {
-    fn to_string(&self) -> String {
-        "additionalProperties: false".to_string()
+impl fmt::Display for AdditionalPropertiesFalseValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("additionalProperties: false")
     }
 }
 
@@ -206,6 +283,7 @@ impl ToString for AdditionalPropertiesFalseValidator {
 /// ```
 pub(crate) struct AdditionalPropertiesNotEmptyFalseValidator {
     properties: AHashMap<String, Validators>,
+    schema_path: JSONPointer,
 }
 impl AdditionalPropertiesNotEmptyFalseValidator {
     #[inline]
@@ -213,17 +291,20 @@ impl AdditionalPropertiesNotEmptyFalseValidator {
         match properties {
             Value::Object(map) => Ok(Box::new(AdditionalPropertiesNotEmptyFalseValidator {
                 properties: compile_properties(map, context)?,
+                schema_path: context.schema_path.clone(),
             })),
             _ => Err(CompilationError::SchemaError),
         }
     }
 }
 impl Validate for AdditionalPropertiesNotEmptyFalseValidator {
-    fn is_valid(&self, schema: &JSONSchema, instance: &Value) -> bool {
+    apply_properties!();
+
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
         if let Value::Object(item) = instance {
             for (property, value) in item {
                 if let Some(validators) = self.properties.get(property) {
-                    is_valid_pattern_schema!(validators, schema, value)
+                    is_valid_pattern_schema!(validators, schema, value, ctx)
                 } else {
                     // No extra properties are allowed
                     return false;
@@ -233,16 +314,31 @@ impl Validate for AdditionalPropertiesNotEmptyFalseValidator {
         true
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::Object(item) = instance {
             let mut errors = vec![];
             for (property, value) in item {
+                if errors.len() >= ctx.error_budget() {
+                    break;
+                }
                 if let Some(validators) = self.properties.get(property) {
                     // When a property is in `properties`, then it should be VALID
-                    errors.extend(validate!(validators, schema, value));
+                    errors.extend(validate!(
+                        validators,
+                        schema,
+                        value,
+                        instance_path.extend_with(property.as_str()),
+                        ctx
+                    ));
                 } else {
                     // No extra properties are allowed
-                    disallow_property!(errors, property)
+                    disallow_property!(errors, property, self.schema_path, instance_path)
                 }
             }
             Box::new(errors.into_iter())
@@ -252,9 +348,9 @@ impl Validate for AdditionalPropertiesNotEmptyFalseValidator {
     }
 }
 
-impl ToString for AdditionalPropertiesNotEmptyFalseValidator {
-    fn to_string(&self) -> String {
-        "additionalProperties: false".to_string()
+impl fmt::Display for AdditionalPropertiesNotEmptyFalseValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("additionalProperties: false")
     }
 }
 
@@ -299,14 +395,16 @@ impl AdditionalPropertiesNotEmptyValidator {
     }
 }
 impl Validate for AdditionalPropertiesNotEmptyValidator {
-    fn is_valid(&self, schema: &JSONSchema, instance: &Value) -> bool {
+    apply_properties!();
+
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
         if let Value::Object(map) = instance {
             for (property, value) in map {
                 if let Some(property_validators) = self.properties.get(property) {
-                    is_valid_pattern_schema!(property_validators, schema, value)
+                    is_valid_pattern_schema!(property_validators, schema, value, ctx)
                 } else {
                     for validator in &self.validators {
-                        if !validator.is_valid(schema, value) {
+                        if !validator.is_valid(schema, value, ctx) {
                             return false;
                         }
                     }
@@ -316,14 +414,35 @@ impl Validate for AdditionalPropertiesNotEmptyValidator {
         true
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::Object(map) = instance {
             let mut errors = vec![];
             for (property, value) in map {
+                if errors.len() >= ctx.error_budget() {
+                    break;
+                }
                 if let Some(property_validators) = self.properties.get(property) {
-                    errors.extend(validate!(property_validators, schema, value))
+                    errors.extend(validate!(
+                        property_validators,
+                        schema,
+                        value,
+                        instance_path.extend_with(property.as_str()),
+                        ctx
+                    ))
                 } else {
-                    errors.extend(validate!(self.validators, schema, value))
+                    errors.extend(validate!(
+                        self.validators,
+                        schema,
+                        value,
+                        instance_path.extend_with(property.as_str()),
+                        ctx
+                    ))
                 }
             }
             Box::new(errors.into_iter())
@@ -333,9 +452,10 @@ impl Validate for AdditionalPropertiesNotEmptyValidator {
     }
 }
 
-impl ToString for AdditionalPropertiesNotEmptyValidator {
-    fn to_string(&self) -> String {
-        format!(
+impl fmt::Display for AdditionalPropertiesNotEmptyValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
             "additionalProperties: {}",
             format_validators(&self.validators)
         )
@@ -381,17 +501,17 @@ impl AdditionalPropertiesWithPatternsValidator {
     }
 }
 impl Validate for AdditionalPropertiesWithPatternsValidator {
-    fn is_valid(&self, schema: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
         if let Value::Object(item) = instance {
             for (property, value) in item.iter() {
                 let mut has_match = false;
                 for (re, validators) in &self.patterns {
                     if re.is_match(property) {
                         has_match = true;
-                        is_valid_pattern_schema!(validators, schema, value)
+                        is_valid_pattern_schema!(validators, schema, value, ctx)
                     }
                 }
-                if !has_match && !is_valid!(self.validators, schema, value) {
+                if !has_match && !is_valid!(self.validators, schema, value, ctx) {
                     return false;
                 }
             }
@@ -399,10 +519,19 @@ impl Validate for AdditionalPropertiesWithPatternsValidator {
         true
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::Object(item) = instance {
             let mut errors = vec![];
             for (property, value) in item.iter() {
+                if errors.len() >= ctx.error_budget() {
+                    break;
+                }
                 let mut has_match = false;
                 errors.extend(
                     self.patterns
@@ -410,11 +539,23 @@ impl Validate for AdditionalPropertiesWithPatternsValidator {
                         .filter(|(re, _)| re.is_match(property))
                         .flat_map(|(_, validators)| {
                             has_match = true;
-                            validate!(validators, schema, value)
+                            validate!(
+                                validators,
+                                schema,
+                                value,
+                                instance_path.extend_with(property.as_str()),
+                                ctx
+                            )
                         }),
                 );
                 if !has_match {
-                    errors.extend(validate!(self.validators, schema, value))
+                    errors.extend(validate!(
+                        self.validators,
+                        schema,
+                        value,
+                        instance_path.extend_with(property.as_str()),
+                        ctx
+                    ))
                 }
             }
             Box::new(errors.into_iter())
@@ -424,9 +565,10 @@ impl Validate for AdditionalPropertiesWithPatternsValidator {
     }
 }
 
-impl ToString for AdditionalPropertiesWithPatternsValidator {
-    fn to_string(&self) -> String {
-        format!(
+impl fmt::Display for AdditionalPropertiesWithPatternsValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
             "additionalProperties: {}",
             format_validators(&self.validators)
         )
@@ -456,30 +598,44 @@ impl ToString for AdditionalPropertiesWithPatternsValidator {
 /// ```
 pub(crate) struct AdditionalPropertiesWithPatternsFalseValidator {
     patterns: PatternedValidators,
+    schema_path: JSONPointer,
 }
 impl AdditionalPropertiesWithPatternsFalseValidator {
     #[inline]
-    pub(crate) fn compile(patterns: PatternedValidators) -> CompilationResult {
+    pub(crate) fn compile(
+        patterns: PatternedValidators,
+        schema_path: JSONPointer,
+    ) -> CompilationResult {
         Ok(Box::new(AdditionalPropertiesWithPatternsFalseValidator {
             patterns,
+            schema_path,
         }))
     }
 }
 impl Validate for AdditionalPropertiesWithPatternsFalseValidator {
-    fn is_valid(&self, schema: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
         if let Value::Object(item) = instance {
             // No properties are allowed, except ones defined in `patternProperties`
             for (property, value) in item {
-                is_valid_patterns!(schema, &self.patterns, property, value);
+                is_valid_patterns!(schema, &self.patterns, property, value, ctx);
             }
         }
         true
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::Object(item) = instance {
             let mut errors = vec![];
             for (property, value) in item {
+                if errors.len() >= ctx.error_budget() {
+                    break;
+                }
                 let mut has_match = false;
                 errors.extend(
                     self.patterns
@@ -487,11 +643,17 @@ impl Validate for AdditionalPropertiesWithPatternsFalseValidator {
                         .filter(|(re, _)| re.is_match(property))
                         .flat_map(|(_, validators)| {
                             has_match = true;
-                            validate!(validators, schema, value)
+                            validate!(
+                                validators,
+                                schema,
+                                value,
+                                instance_path.extend_with(property.as_str()),
+                                ctx
+                            )
                         }),
                 );
                 if !has_match {
-                    disallow_property!(errors, property)
+                    disallow_property!(errors, property, self.schema_path, instance_path)
                 }
             }
             Box::new(errors.into_iter())
@@ -501,9 +663,9 @@ impl Validate for AdditionalPropertiesWithPatternsFalseValidator {
     }
 }
 
-impl ToString for AdditionalPropertiesWithPatternsFalseValidator {
-    fn to_string(&self) -> String {
-        "additionalProperties: false".to_string()
+impl fmt::Display for AdditionalPropertiesWithPatternsFalseValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("additionalProperties: false")
     }
 }
 
@@ -560,16 +722,18 @@ impl AdditionalPropertiesWithPatternsNotEmptyValidator {
     }
 }
 impl Validate for AdditionalPropertiesWithPatternsNotEmptyValidator {
-    fn is_valid(&self, schema: &JSONSchema, instance: &Value) -> bool {
+    apply_properties!();
+
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
         if let Value::Object(item) = instance {
             for (property, value) in item.iter() {
                 if let Some(validators) = self.properties.get(property) {
-                    if is_valid!(validators, schema, value) {
+                    if is_valid!(validators, schema, value, ctx) {
                         // Valid for `properties`, check `patternProperties`
                         for (re, validators) in &self.patterns {
                             // If there is a match, then the value should match the sub-schema
                             if re.is_match(property) {
-                                is_valid_pattern_schema!(validators, schema, value)
+                                is_valid_pattern_schema!(validators, schema, value, ctx)
                             }
                         }
                     } else {
@@ -582,10 +746,10 @@ impl Validate for AdditionalPropertiesWithPatternsNotEmptyValidator {
                         // If there is a match, then the value should match the sub-schema
                         if re.is_match(property) {
                             has_match = true;
-                            is_valid_pattern_schema!(validators, schema, value)
+                            is_valid_pattern_schema!(validators, schema, value, ctx)
                         }
                     }
-                    if !has_match && !is_valid!(self.validators, schema, value) {
+                    if !has_match && !is_valid!(self.validators, schema, value, ctx) {
                         return false;
                     }
                 }
@@ -596,17 +760,40 @@ impl Validate for AdditionalPropertiesWithPatternsNotEmptyValidator {
         }
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::Object(item) = instance {
             let mut errors = vec![];
             for (property, value) in item.iter() {
+                if errors.len() >= ctx.error_budget() {
+                    break;
+                }
                 if let Some(validators) = self.properties.get(property) {
-                    errors.extend(validate!(validators, schema, value));
+                    errors.extend(validate!(
+                        validators,
+                        schema,
+                        value,
+                        instance_path.extend_with(property.as_str()),
+                        ctx
+                    ));
                     errors.extend(
                         self.patterns
                             .iter()
                             .filter(|(re, _)| re.is_match(property))
-                            .flat_map(|(_, validators)| validate!(validators, schema, value)),
+                            .flat_map(|(_, validators)| {
+                                validate!(
+                                    validators,
+                                    schema,
+                                    value,
+                                    instance_path.extend_with(property.as_str()),
+                                    ctx
+                                )
+                            }),
                     );
                 } else {
                     let mut has_match = false;
@@ -616,11 +803,23 @@ impl Validate for AdditionalPropertiesWithPatternsNotEmptyValidator {
                             .filter(|(re, _)| re.is_match(property))
                             .flat_map(|(_, validators)| {
                                 has_match = true;
-                                validate!(validators, schema, value)
+                                validate!(
+                                    validators,
+                                    schema,
+                                    value,
+                                    instance_path.extend_with(property.as_str()),
+                                    ctx
+                                )
                             }),
                     );
                     if !has_match {
-                        errors.extend(validate!(self.validators, schema, value))
+                        errors.extend(validate!(
+                            self.validators,
+                            schema,
+                            value,
+                            instance_path.extend_with(property.as_str()),
+                            ctx
+                        ))
                     }
                 }
             }
@@ -630,9 +829,10 @@ impl Validate for AdditionalPropertiesWithPatternsNotEmptyValidator {
         }
     }
 }
-impl ToString for AdditionalPropertiesWithPatternsNotEmptyValidator {
-    fn to_string(&self) -> String {
-        format!(
+impl fmt::Display for AdditionalPropertiesWithPatternsNotEmptyValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
             "additionalProperties: {}",
             format_validators(&self.validators)
         )
@@ -667,6 +867,7 @@ impl ToString for AdditionalPropertiesWithPatternsNotEmptyValidator {
 pub(crate) struct AdditionalPropertiesWithPatternsNotEmptyFalseValidator {
     properties: AHashMap<String, Validators>,
     patterns: PatternedValidators,
+    schema_path: JSONPointer,
 }
 impl AdditionalPropertiesWithPatternsNotEmptyFalseValidator {
     #[inline]
@@ -680,6 +881,7 @@ impl AdditionalPropertiesWithPatternsNotEmptyFalseValidator {
                 AdditionalPropertiesWithPatternsNotEmptyFalseValidator {
                     properties: compile_properties(map, context)?,
                     patterns,
+                    schema_path: context.schema_path.clone(),
                 },
             ))
         } else {
@@ -688,17 +890,19 @@ impl AdditionalPropertiesWithPatternsNotEmptyFalseValidator {
     }
 }
 impl Validate for AdditionalPropertiesWithPatternsNotEmptyFalseValidator {
-    fn is_valid(&self, schema: &JSONSchema, instance: &Value) -> bool {
+    apply_properties!();
+
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
         if let Value::Object(item) = instance {
             // No properties are allowed, except ones defined in `properties` or `patternProperties`
             for (property, value) in item.iter() {
                 if let Some(validators) = self.properties.get(property) {
-                    if is_valid!(validators, schema, value) {
+                    if is_valid!(validators, schema, value, ctx) {
                         // Valid for `properties`, check `patternProperties`
                         for (re, validators) in &self.patterns {
                             // If there is a match, then the value should match the sub-schema
                             if re.is_match(property) {
-                                is_valid_pattern_schema!(validators, schema, value)
+                                is_valid_pattern_schema!(validators, schema, value, ctx)
                             }
                         }
                     } else {
@@ -706,25 +910,48 @@ impl Validate for AdditionalPropertiesWithPatternsNotEmptyFalseValidator {
                         return false;
                     }
                 } else {
-                    is_valid_patterns!(schema, &self.patterns, property, value);
+                    is_valid_patterns!(schema, &self.patterns, property, value, ctx);
                 }
             }
         }
         true
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::Object(item) = instance {
             let mut errors = vec![];
             // No properties are allowed, except ones defined in `properties` or `patternProperties`
             for (property, value) in item.iter() {
+                if errors.len() >= ctx.error_budget() {
+                    break;
+                }
                 if let Some(validators) = self.properties.get(property) {
-                    errors.extend(validate!(validators, schema, value));
+                    errors.extend(validate!(
+                        validators,
+                        schema,
+                        value,
+                        instance_path.extend_with(property.as_str()),
+                        ctx
+                    ));
                     errors.extend(
                         self.patterns
                             .iter()
                             .filter(|(re, _)| re.is_match(property))
-                            .flat_map(|(_, validators)| validate!(validators, schema, value)),
+                            .flat_map(|(_, validators)| {
+                                validate!(
+                                    validators,
+                                    schema,
+                                    value,
+                                    instance_path.extend_with(property.as_str()),
+                                    ctx
+                                )
+                            }),
                     );
                 } else {
                     let mut has_match = false;
@@ -734,11 +961,17 @@ impl Validate for AdditionalPropertiesWithPatternsNotEmptyFalseValidator {
                             .filter(|(re, _)| re.is_match(property))
                             .flat_map(|(_, validators)| {
                                 has_match = true;
-                                validate!(validators, schema, value)
+                                validate!(
+                                    validators,
+                                    schema,
+                                    value,
+                                    instance_path.extend_with(property.as_str()),
+                                    ctx
+                                )
                             }),
                     );
                     if !has_match {
-                        disallow_property!(errors, property)
+                        disallow_property!(errors, property, self.schema_path, instance_path)
                     }
                 }
             }
@@ -749,9 +982,9 @@ impl Validate for AdditionalPropertiesWithPatternsNotEmptyFalseValidator {
     }
 }
 
-impl ToString for AdditionalPropertiesWithPatternsNotEmptyFalseValidator {
-    fn to_string(&self) -> String {
-        "additionalProperties: false".to_string()
+impl fmt::Display for AdditionalPropertiesWithPatternsNotEmptyFalseValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("additionalProperties: false")
     }
 }
 #[inline]
@@ -764,7 +997,10 @@ pub(crate) fn compile(
     if let Some(patterns) = parent.get("patternProperties") {
         if let Value::Object(obj) = patterns {
             // Compile all patterns & their validators to avoid doing work in the `patternProperties` validator
-            let compiled_patterns = compile_patterns(obj, context).ok()?;
+            let compiled_patterns = match compile_patterns(obj, context) {
+                Ok(compiled_patterns) => compiled_patterns,
+                Err(error) => return Some(Err(error)),
+            };
             match schema {
                 Value::Bool(true) => None, // "additionalProperties" are "true" by default
                 Value::Bool(false) => {
@@ -779,6 +1015,7 @@ pub(crate) fn compile(
                     } else {
                         Some(AdditionalPropertiesWithPatternsFalseValidator::compile(
                             compiled_patterns,
+                            context.schema_path.clone(),
                         ))
                     }
                 }
@@ -811,7 +1048,9 @@ pub(crate) fn compile(
                         properties, context,
                     ))
                 } else {
-                    Some(AdditionalPropertiesFalseValidator::compile())
+                    Some(AdditionalPropertiesFalseValidator::compile(
+                        context.schema_path.clone(),
+                    ))
                 }
             }
             _ => {
@@ -833,24 +1072,21 @@ fn compile_patterns(
     obj: &Map<String, Value>,
     context: &CompilationContext,
 ) -> Result<PatternedValidators, CompilationError> {
+    let patterns_context = context.with_sibling("patternProperties");
     let mut compiled_patterns = Vec::with_capacity(obj.len());
     for (pattern, subschema) in obj {
-        if let Ok(compiled_pattern) = Regex::new(pattern) {
-            if let Ok(validators) = compile_validators(subschema, context) {
-                compiled_patterns.push((compiled_pattern, validators));
-            } else {
-                return Err(CompilationError::SchemaError);
-            }
-        } else {
-            return Err(CompilationError::SchemaError);
-        }
+        let context = patterns_context.with_path(pattern.as_str());
+        compiled_patterns.push((
+            pattern::compile_pattern_key(pattern, &context)?,
+            compile_validators(subschema, &context)?,
+        ));
     }
     Ok(compiled_patterns)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::tests_util;
+    use crate::{tests_util, CompilationOptions, JSONSchema};
     use serde_json::{json, Value};
     use test_case::test_case;
 
@@ -1220,4 +1456,56 @@ mod tests {
         tests_util::is_not_valid(&schema, instance);
         tests_util::expect_errors(&schema, instance, expected)
     }
+
+    #[test_case(&json!({"additionalProperties": false, "patternProperties": {"[": {}}}))]
+    #[test_case(&json!({"additionalProperties": {"type": "string"}, "patternProperties": {"^a": {"type": 42}}}))]
+    fn invalid_patterns_are_reported(schema: &Value) {
+        assert!(JSONSchema::compile(schema).is_err())
+    }
+
+    fn is_valid_strict(schema: &Value, instance: &Value) -> bool {
+        CompilationOptions::default()
+            .strict_additional_properties(true)
+            .compile(schema)
+            .unwrap()
+            .is_valid(instance)
+    }
+
+    #[test_case(&json!({"properties": {"a": {}}}), &json!({"a": 1}))]
+    #[test_case(&json!({"patternProperties": {"^a": {}}}), &json!({"ab": 1}))]
+    #[test_case(&json!({"properties": {"a": {}}, "additionalProperties": true}), &json!({"b": 1}); "explicitly allowed")]
+    #[test_case(&json!({"properties": {"a": {}}, "additionalProperties": {"type": "integer"}}), &json!({"b": 1}); "explicit schema")]
+    #[test_case(&json!({"items": {"minimum": 1}}), &json!([{"b": 1}]); "not an object schema")]
+    #[test_case(&json!({"type": ["object", "array"]}), &json!([{"b": 1}]); "not an object")]
+    fn strict_valid(schema: &Value, instance: &Value) {
+        assert!(is_valid_strict(schema, instance))
+    }
+
+    #[test_case(&json!({"properties": {"a": {}}}), &json!({"b": 1}))]
+    #[test_case(&json!({"patternProperties": {"^a": {}}}), &json!({"b": 1}))]
+    #[test_case(&json!({"type": "object"}), &json!({"b": 1}))]
+    #[test_case(&json!({"type": ["object", "null"]}), &json!({"b": 1}))]
+    #[test_case(&json!({"properties": {"a": {"properties": {"b": {}}}}}), &json!({"a": {"c": 1}}); "nested")]
+    #[test_case(&json!({"properties": {"a": {}}, "additionalProperties": {"type": "integer"}}), &json!({"b": "c"}); "explicit schema")]
+    fn strict_invalid(schema: &Value, instance: &Value) {
+        assert!(!is_valid_strict(schema, instance))
+    }
+
+    #[test]
+    fn strict_error_path() {
+        let schema = json!({"properties": {"a": {"type": "object"}}});
+        let compiled = CompilationOptions::default()
+            .strict_additional_properties(true)
+            .compile(&schema)
+            .unwrap();
+        let instance = json!({"a": {"b": 1}});
+        let errors: Vec<_> = compiled.validate(&instance).unwrap_err().collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].schema_path().to_string(),
+            "/properties/a/additionalProperties"
+        );
+        // Not strict by default
+        assert!(JSONSchema::compile(&schema).unwrap().is_valid(&instance))
+    }
 }
@@ -1,57 +1,185 @@
 use crate::{
     compilation::{compile_validators, context::CompilationContext, JSONSchema},
-    error::{CompilationError, ErrorIterator},
-    keywords::{format_vec_of_validators, CompilationResult, Validators},
+    error::{error, no_error, CompilationError, ErrorIterator, SubSchemaErrors, ValidationError},
+    keywords::{
+        format_vec_of_validators, type_::MultipleTypesValidator, BoxedValidator, CompilationResult,
+        Validators,
+    },
+    paths::JSONPointer,
+    primitive_type::{PrimitiveType, PrimitiveTypesBitMap},
+    schemas::Draft,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use serde_json::{Map, Value};
+use std::{convert::TryFrom, fmt};
 
 pub(crate) struct AllOfValidator {
     schemas: Vec<Validators>,
+    schema_path: JSONPointer,
 }
 
 impl AllOfValidator {
     #[inline]
     pub(crate) fn compile(schema: &Value, context: &CompilationContext) -> CompilationResult {
         if let Value::Array(items) = schema {
-            let mut schemas = Vec::with_capacity(items.len());
-            for item in items {
-                let validators = compile_validators(item, context)?;
-                schemas.push(validators)
+            let all_of = AllOfValidator::compile_branches(items, context)?;
+            if let Some(types) = merged_types(items, context) {
+                return Ok(Box::new(MergedTypesValidator {
+                    types: MultipleTypesValidator::compile_types(
+                        types,
+                        context.schema_path.clone(),
+                    )?,
+                    all_of,
+                }));
             }
-            Ok(Box::new(AllOfValidator { schemas }))
+            Ok(Box::new(all_of))
         } else {
             Err(CompilationError::SchemaError)
         }
     }
+
+    fn compile_branches(
+        items: &[Value],
+        context: &CompilationContext,
+    ) -> Result<AllOfValidator, CompilationError> {
+        let mut schemas = Vec::with_capacity(items.len());
+        for (idx, item) in items.iter().enumerate() {
+            let validators = compile_validators(item, &context.with_path(idx))?;
+            schemas.push(validators)
+        }
+        Ok(AllOfValidator {
+            schemas,
+            schema_path: context.schema_path.clone(),
+        })
+    }
+}
+
+/// `allOf` where each branch has only the `type` keyword. Instances are checked against
+/// the intersection of the branch types, which may be empty. Errors are the same as for `allOf`,
+/// i.e. the types of the failed branches are in the context.
+pub(crate) struct MergedTypesValidator {
+    types: BoxedValidator,
+    all_of: AllOfValidator,
+}
+
+impl Validate for MergedTypesValidator {
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
+        self.types.is_valid(schema, instance, ctx)
+    }
+
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
+            no_error()
+        } else {
+            self.all_of.validate(schema, instance, instance_path, ctx)
+        }
+    }
+}
+
+impl fmt::Display for MergedTypesValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.types.fmt(f)
+    }
+}
+
+/// Types allowed by all of `items` if each of them has only the `type` keyword.
+/// `None` if some item has other keywords or if options change how `type` is compiled.
+fn merged_types(items: &[Value], context: &CompilationContext) -> Option<PrimitiveTypesBitMap> {
+    let config = &context.config;
+    // `1.0` is not an integer in Draft 4, unlike in `MultipleTypesValidator`
+    if items.is_empty()
+        || config.draft() == Draft::Draft4
+        || config.coerces_types()
+        || config.has_strict_keywords()
+        || config.has_strict_additional_properties()
+        || !config.is_keyword_enabled("type")
+        || config.get_keyword_factory("type").is_some()
+    {
+        return None;
+    }
+    let mut merged = None;
+    for item in items {
+        let types = match item {
+            Value::Object(object) if object.len() == 1 => match object.get("type")? {
+                Value::String(type_) => vec![type_.as_str()],
+                Value::Array(types) => types
+                    .iter()
+                    .map(Value::as_str)
+                    .collect::<Option<Vec<_>>>()?,
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let mut bitmap = PrimitiveTypesBitMap::new();
+        for type_ in types {
+            bitmap |= PrimitiveType::try_from(type_).ok()?;
+        }
+        // `number` includes `integer`, otherwise their intersection would be empty
+        if bitmap.contains_type(PrimitiveType::Number) {
+            bitmap |= PrimitiveType::Integer;
+        }
+        merged = Some(match merged {
+            Some(merged) => bitmap.intersection(merged),
+            None => bitmap,
+        });
+    }
+    merged
 }
 
 impl Validate for AllOfValidator {
-    fn is_valid(&self, schema: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
         self.schemas.iter().all(move |validators| {
             validators
                 .iter()
-                .all(move |validator| validator.is_valid(schema, instance))
+                .all(move |validator| validator.is_valid(schema, instance, ctx))
         })
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        let errors: Vec<_> = self
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        let context: Vec<_> = self
             .schemas
             .iter()
-            .flat_map(move |validators| {
-                validators
+            .enumerate()
+            .filter_map(|(idx, validators)| {
+                let errors: Vec<_> = validators
                     .iter()
-                    .flat_map(move |validator| validator.validate(schema, instance))
+                    .flat_map(|validator| validator.validate(schema, instance, instance_path, ctx))
+                    .take(ctx.error_budget())
+                    .collect();
+                if errors.is_empty() {
+                    None
+                } else {
+                    Some(SubSchemaErrors::new(idx, errors))
+                }
             })
             .collect();
-        Box::new(errors.into_iter())
+        if context.is_empty() {
+            no_error()
+        } else {
+            error(
+                ValidationError::all_of(self.schema_path.clone(), instance_path.clone(), instance)
+                    .with_context(context),
+            )
+        }
     }
 }
 
-impl ToString for AllOfValidator {
-    fn to_string(&self) -> String {
-        format!("allOf: [{}]", format_vec_of_validators(&self.schemas))
+impl fmt::Display for AllOfValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "allOf: [{}]", format_vec_of_validators(&self.schemas))
     }
 }
 #[inline]
@@ -62,3 +190,101 @@ pub(crate) fn compile(
 ) -> Option<CompilationResult> {
     Some(AllOfValidator::compile(schema, context))
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        compilation::{options::CompilationOptions, JSONSchema},
+        schemas::Draft,
+        tests_util,
+    };
+    use serde_json::{json, Value};
+    use std::sync::atomic::Ordering;
+    use test_case::test_case;
+
+    #[test]
+    fn is_valid_short_circuits() {
+        let (options, calls) = tests_util::with_counter();
+        let schema = json!({"allOf": [{"type": "string"}, {"counter": true}]});
+        let compiled = options.compile(&schema).unwrap();
+        let instance = json!(1);
+        assert!(!compiled.is_valid(&instance));
+        // The second branch is not evaluated once the first one fails
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        // But errors of all branches are reported
+        let errors: Vec<_> = compiled.validate(&instance).unwrap_err().collect();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].is_all_of_error());
+        assert_eq!(errors[0].context().map(<[_]>::len), Some(2));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test_case(&json!([{"type": "number"}, {"type": "integer"}]), &json!(1), &json!(1.5), "type: [integer]")]
+    #[test_case(&json!([{"type": ["string", "null"]}, {"type": "string"}]), &json!("a"), &json!(null), "type: [string]")]
+    #[test_case(&json!([{"type": ["integer", "array"]}, {"type": ["number", "array"]}]), &json!([]), &json!("a"), "type: [array, integer]")]
+    #[test_case(&json!([{"type": "integer"}, {"minimum": 5}]), &json!(5), &json!(1), "allOf: [{type: integer}, {minimum: 5}]"; "not only types")]
+    fn merged_types(schema: &Value, valid: &Value, invalid: &Value, expected: &str) {
+        let schema = json!({ "allOf": schema });
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        assert_eq!(compiled.validators[0].to_string(), expected);
+        tests_util::is_valid(&schema, valid);
+        tests_util::is_not_valid(&schema, invalid);
+    }
+
+    #[test]
+    fn merged_types_without_intersection() {
+        let schema = json!({"allOf": [{"type": "string"}, {"type": "number"}]});
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        assert_eq!(compiled.validators[0].to_string(), "type: []");
+        tests_util::is_not_valid(&schema, &json!("a"));
+        tests_util::is_not_valid(&schema, &json!(1));
+        // The failed branch names its type
+        let instance = json!("a");
+        let errors: Vec<_> = compiled.validate(&instance).unwrap_err().collect();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].is_all_of_error());
+        assert_eq!(errors[0].schema_path().to_string(), "/allOf");
+        let context = errors[0].context().unwrap();
+        assert_eq!(context.len(), 1);
+        assert_eq!(context[0].index(), 1);
+        let error = &context[0].errors()[0];
+        assert!(error.is_type_error());
+        assert_eq!(error.schema_path().to_string(), "/allOf/1/type");
+        assert_eq!(
+            error.message().to_string(),
+            r#"'"a"' is not of type 'number'"#
+        );
+    }
+
+    #[test]
+    fn merged_types_error() {
+        let schema =
+            json!({"allOf": [{"type": ["string", "null"]}, {"type": ["string", "integer"]}]});
+        let instance = json!(1);
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        let errors: Vec<_> = compiled.validate(&instance).unwrap_err().collect();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].is_all_of_error());
+        assert_eq!(errors[0].keyword(), "allOf");
+        assert_eq!(errors[0].schema_path().to_string(), "/allOf");
+        let context = errors[0].context().unwrap();
+        assert_eq!(context.len(), 1);
+        assert_eq!(context[0].index(), 0);
+        assert_eq!(
+            context[0].errors()[0].schema_path().to_string(),
+            "/allOf/0/type"
+        );
+    }
+
+    #[test]
+    fn merged_types_draft4() {
+        // Not merged, as `1.0` is not an integer in Draft 4
+        let schema = json!({"allOf": [{"type": "number"}, {"type": "integer"}]});
+        let compiled = CompilationOptions::default()
+            .with_draft(Draft::Draft4)
+            .compile(&schema)
+            .unwrap();
+        assert!(compiled.is_valid(&json!(1)));
+        assert!(!compiled.is_valid(&json!(1.0)));
+    }
+}
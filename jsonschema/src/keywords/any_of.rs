@@ -1,13 +1,17 @@
 use crate::{
     compilation::{compile_validators, context::CompilationContext, JSONSchema},
-    error::{error, no_error, CompilationError, ErrorIterator, ValidationError},
+    error::{error, no_error, CompilationError, ErrorIterator, SubSchemaErrors, ValidationError},
     keywords::{format_vec_of_validators, CompilationResult, Validators},
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use serde_json::{Map, Value};
+use std::fmt;
 
 pub(crate) struct AnyOfValidator {
     schemas: Vec<Validators>,
+    schema_path: JSONPointer,
 }
 
 impl AnyOfValidator {
@@ -15,11 +19,14 @@ impl AnyOfValidator {
     pub(crate) fn compile(schema: &Value, context: &CompilationContext) -> CompilationResult {
         if let Value::Array(items) = schema {
             let mut schemas = Vec::with_capacity(items.len());
-            for item in items {
-                let validators = compile_validators(item, context)?;
+            for (idx, item) in items.iter().enumerate() {
+                let validators = compile_validators(item, &context.with_path(idx))?;
                 schemas.push(validators)
             }
-            Ok(Box::new(AnyOfValidator { schemas }))
+            Ok(Box::new(AnyOfValidator {
+                schemas,
+                schema_path: context.schema_path.clone(),
+            }))
         } else {
             Err(CompilationError::SchemaError)
         }
@@ -27,11 +34,11 @@ impl AnyOfValidator {
 }
 
 impl Validate for AnyOfValidator {
-    fn is_valid(&self, schema: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
         for validators in &self.schemas {
             if validators
                 .iter()
-                .all(|validator| validator.is_valid(schema, instance))
+                .all(|validator| validator.is_valid(schema, instance, ctx))
             {
                 return true;
             }
@@ -39,18 +46,36 @@ impl Validate for AnyOfValidator {
         false
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        if self.is_valid(schema, instance) {
-            no_error()
-        } else {
-            error(ValidationError::any_of(instance))
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        // Each branch is evaluated once - errors of failed branches are kept for the context
+        let mut context = Vec::with_capacity(self.schemas.len());
+        for (idx, validators) in self.schemas.iter().enumerate() {
+            let errors: Vec<_> = validators
+                .iter()
+                .flat_map(|validator| validator.validate(schema, instance, instance_path, ctx))
+                .take(ctx.error_budget())
+                .collect();
+            if errors.is_empty() {
+                return no_error();
+            }
+            context.push(SubSchemaErrors::new(idx, errors));
         }
+        error(
+            ValidationError::any_of(self.schema_path.clone(), instance_path.clone(), instance)
+                .with_context(context),
+        )
     }
 }
 
-impl ToString for AnyOfValidator {
-    fn to_string(&self) -> String {
-        format!("anyOf: [{}]", format_vec_of_validators(&self.schemas))
+impl fmt::Display for AnyOfValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "anyOf: [{}]", format_vec_of_validators(&self.schemas))
     }
 }
 #[inline]
@@ -61,3 +86,49 @@ pub(crate) fn compile(
 ) -> Option<CompilationResult> {
     Some(AnyOfValidator::compile(schema, context))
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{tests_util, JSONSchema};
+    use serde_json::{json, Value};
+    use std::sync::atomic::Ordering;
+    use test_case::test_case;
+
+    #[test_case(&json!(1), 1, 1; "no match")]
+    #[test_case(&json!("a"), 1, 0; "first branch matches")]
+    fn branches_are_evaluated_once(instance: &Value, calls: usize, errors: usize) {
+        let (options, counter) = tests_util::with_counter();
+        let schema = json!({"anyOf": [{"counter": true}, {"type": "array"}]});
+        let compiled = options.compile(&schema).unwrap();
+        let result: Vec<_> = compiled
+            .validate(instance)
+            .err()
+            .into_iter()
+            .flatten()
+            .collect();
+        assert_eq!(result.len(), errors);
+        assert_eq!(counter.load(Ordering::SeqCst), calls);
+    }
+
+    #[test]
+    fn sub_errors_are_in_context() {
+        let schema = json!({"anyOf": [{"type": "string"}, {"items": {"minimum": 5}}]});
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        let instance = json!([1, 6, 2]);
+        let error = compiled.validate(&instance).unwrap_err().next().unwrap();
+        let context = error.context().unwrap();
+        // One entry per branch
+        assert_eq!(context.len(), 2);
+        assert_eq!(context[0].index(), 0);
+        assert_eq!(context[0].errors().len(), 1);
+        assert_eq!(context[1].index(), 1);
+        let paths: Vec<_> = context[1]
+            .errors()
+            .iter()
+            .map(|error| error.instance_path().to_string())
+            .collect();
+        assert_eq!(paths, vec!["/0", "/2"]);
+        let error = error.into_owned();
+        assert_eq!(error.context().map(<[_]>::len), Some(2));
+    }
+}
@@ -1,10 +1,13 @@
 use crate::{
-    compilation::JSONSchema,
+    compilation::{context::CompilationContext, JSONSchema},
     error::{error, no_error, ErrorIterator, ValidationError},
     keywords::CompilationResult,
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use serde_json::Value;
+use std::fmt;
 
 pub(crate) struct TrueValidator {}
 impl TrueValidator {
@@ -14,49 +17,68 @@ impl TrueValidator {
     }
 }
 impl Validate for TrueValidator {
-    fn is_valid(&self, _: &JSONSchema, _: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, _: &Value, _: &ValidationContext) -> bool {
         true
     }
 
-    fn validate<'a>(&self, _: &'a JSONSchema, _: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        _: &'a JSONSchema,
+        _: &'a Value,
+        _: &JSONPointer,
+        _: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         no_error()
     }
 }
 
-impl ToString for TrueValidator {
-    fn to_string(&self) -> String {
-        "true".to_string()
+impl fmt::Display for TrueValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("true")
     }
 }
 
-pub(crate) struct FalseValidator {}
+pub(crate) struct FalseValidator {
+    schema_path: JSONPointer,
+}
 impl FalseValidator {
     #[inline]
-    pub(crate) fn compile() -> CompilationResult {
-        Ok(Box::new(FalseValidator {}))
+    pub(crate) fn compile(schema_path: JSONPointer) -> CompilationResult {
+        Ok(Box::new(FalseValidator { schema_path }))
     }
 }
 impl Validate for FalseValidator {
-    fn is_valid(&self, _: &JSONSchema, _: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, _: &Value, _: &ValidationContext) -> bool {
         false
     }
 
-    fn validate<'a>(&self, _: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        error(ValidationError::false_schema(instance))
+    fn validate<'a>(
+        &self,
+        _: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        _: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        error(ValidationError::false_schema(
+            self.schema_path.clone(),
+            instance_path.clone(),
+            instance,
+            "",
+        ))
     }
 }
 
-impl ToString for FalseValidator {
-    fn to_string(&self) -> String {
-        "false".to_string()
+impl fmt::Display for FalseValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("false")
     }
 }
 
 #[inline]
-pub(crate) fn compile(value: bool) -> Option<CompilationResult> {
+pub(crate) fn compile(value: bool, context: &CompilationContext) -> Option<CompilationResult> {
     if value {
         Some(TrueValidator::compile())
     } else {
-        Some(FalseValidator::compile())
+        Some(FalseValidator::compile(context.schema_path.clone()))
     }
 }
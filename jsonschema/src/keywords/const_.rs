@@ -3,34 +3,49 @@ use crate::{
     compilation::{context::CompilationContext, JSONSchema},
     error::{error, no_error, ErrorIterator, ValidationError},
     keywords::CompilationResult,
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use serde_json::{Map, Number, Value};
-use std::f64::EPSILON;
+use std::{f64::EPSILON, fmt};
 
 struct ConstArrayValidator {
     value: Vec<Value>,
+    schema_path: JSONPointer,
 }
 impl ConstArrayValidator {
     #[inline]
-    pub(crate) fn compile(value: &[Value]) -> CompilationResult {
+    pub(crate) fn compile(value: &[Value], schema_path: JSONPointer) -> CompilationResult {
         Ok(Box::new(ConstArrayValidator {
             value: value.to_vec(),
+            schema_path,
         }))
     }
 }
 impl Validate for ConstArrayValidator {
     #[inline]
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        if self.is_valid(schema, instance) {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
             no_error()
         } else {
-            error(ValidationError::constant_array(instance, &self.value))
+            error(ValidationError::constant_array(
+                self.schema_path.clone(),
+                instance_path.clone(),
+                instance,
+                &self.value,
+            ))
         }
     }
 
     #[inline]
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         if let Value::Array(instance_value) = instance {
             helpers::equal_arrays(&self.value, instance_value)
         } else {
@@ -38,9 +53,10 @@ impl Validate for ConstArrayValidator {
         }
     }
 }
-impl ToString for ConstArrayValidator {
-    fn to_string(&self) -> String {
-        format!(
+impl fmt::Display for ConstArrayValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
             "const: [{}]",
             self.value
                 .iter()
@@ -53,25 +69,37 @@ impl ToString for ConstArrayValidator {
 
 struct ConstBooleanValidator {
     value: bool,
+    schema_path: JSONPointer,
 }
 impl ConstBooleanValidator {
     #[inline]
-    pub(crate) fn compile(value: bool) -> CompilationResult {
-        Ok(Box::new(ConstBooleanValidator { value }))
+    pub(crate) fn compile(value: bool, schema_path: JSONPointer) -> CompilationResult {
+        Ok(Box::new(ConstBooleanValidator { value, schema_path }))
     }
 }
 impl Validate for ConstBooleanValidator {
     #[inline]
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        if self.is_valid(schema, instance) {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
             no_error()
         } else {
-            error(ValidationError::constant_boolean(instance, self.value))
+            error(ValidationError::constant_boolean(
+                self.schema_path.clone(),
+                instance_path.clone(),
+                instance,
+                self.value,
+            ))
         }
     }
 
     #[inline]
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         if let Value::Bool(instance_value) = instance {
             &self.value == instance_value
         } else {
@@ -79,37 +107,113 @@ impl Validate for ConstBooleanValidator {
         }
     }
 }
-impl ToString for ConstBooleanValidator {
-    fn to_string(&self) -> String {
-        format!("const: {}", self.value)
+impl fmt::Display for ConstBooleanValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "const: {}", self.value)
     }
 }
 
-struct ConstNullValidator {}
+struct ConstNullValidator {
+    schema_path: JSONPointer,
+}
 impl ConstNullValidator {
     #[inline]
-    pub(crate) fn compile() -> CompilationResult {
-        Ok(Box::new(ConstNullValidator {}))
+    pub(crate) fn compile(schema_path: JSONPointer) -> CompilationResult {
+        Ok(Box::new(ConstNullValidator { schema_path }))
     }
 }
 impl Validate for ConstNullValidator {
     #[inline]
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        if self.is_valid(schema, instance) {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
             no_error()
         } else {
-            error(ValidationError::constant_null(instance))
+            error(ValidationError::constant_null(
+                self.schema_path.clone(),
+                instance_path.clone(),
+                instance,
+            ))
         }
     }
 
     #[inline]
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         instance.is_null()
     }
 }
-impl ToString for ConstNullValidator {
-    fn to_string(&self) -> String {
-        format!("const: {}", Value::Null)
+impl fmt::Display for ConstNullValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "const: {}", Value::Null)
+    }
+}
+
+struct ConstIntegerValidator {
+    original_value: Number,
+    // Wide enough for both `i64` and `u64`
+    value: i128,
+    schema_path: JSONPointer,
+}
+
+impl ConstIntegerValidator {
+    #[inline]
+    pub(crate) fn compile(
+        original_value: &Number,
+        value: i128,
+        schema_path: JSONPointer,
+    ) -> CompilationResult {
+        Ok(Box::new(ConstIntegerValidator {
+            original_value: original_value.clone(),
+            value,
+            schema_path,
+        }))
+    }
+}
+
+impl Validate for ConstIntegerValidator {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
+            no_error()
+        } else {
+            error(ValidationError::constant_number(
+                self.schema_path.clone(),
+                instance_path.clone(),
+                instance,
+                &self.original_value,
+            ))
+        }
+    }
+
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::Number(item) = instance {
+            if let Some(item) = item.as_u64() {
+                i128::from(item) == self.value
+            } else if let Some(item) = item.as_i64() {
+                i128::from(item) == self.value
+            } else {
+                // Floats with a zero fractional part, e.g. `42.0`, are equal to integers
+                helpers::equal(instance, &Value::Number(self.original_value.clone()))
+            }
+        } else {
+            false
+        }
+    }
+}
+
+impl fmt::Display for ConstIntegerValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "const: {}", self.original_value)
     }
 }
 
@@ -117,33 +221,43 @@ struct ConstNumberValidator {
     // This is saved in order to ensure that the error message is not altered by precision loss
     original_value: Number,
     value: f64,
+    schema_path: JSONPointer,
 }
 
 impl ConstNumberValidator {
     #[inline]
-    pub(crate) fn compile(original_value: &Number) -> CompilationResult {
+    pub(crate) fn compile(original_value: &Number, schema_path: JSONPointer) -> CompilationResult {
         Ok(Box::new(ConstNumberValidator {
             original_value: original_value.clone(),
             value: original_value
                 .as_f64()
                 .expect("A JSON number will always be representable as f64"),
+            schema_path,
         }))
     }
 }
 
 impl Validate for ConstNumberValidator {
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        if self.is_valid(schema, instance) {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
             no_error()
         } else {
             error(ValidationError::constant_number(
+                self.schema_path.clone(),
+                instance_path.clone(),
                 instance,
                 &self.original_value,
             ))
         }
     }
 
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         if let Value::Number(item) = instance {
             (self.value - item.as_f64().expect("Always representable as f64")).abs() < EPSILON
         } else {
@@ -152,35 +266,51 @@ impl Validate for ConstNumberValidator {
     }
 }
 
-impl ToString for ConstNumberValidator {
-    fn to_string(&self) -> String {
-        format!("const: {}", self.original_value)
+impl fmt::Display for ConstNumberValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "const: {}", self.original_value)
     }
 }
 
 pub(crate) struct ConstObjectValidator {
     value: Map<String, Value>,
+    schema_path: JSONPointer,
 }
 
 impl ConstObjectValidator {
     #[inline]
-    pub(crate) fn compile(value: &Map<String, Value>) -> CompilationResult {
+    pub(crate) fn compile(
+        value: &Map<String, Value>,
+        schema_path: JSONPointer,
+    ) -> CompilationResult {
         Ok(Box::new(ConstObjectValidator {
             value: value.clone(),
+            schema_path,
         }))
     }
 }
 
 impl Validate for ConstObjectValidator {
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        if self.is_valid(schema, instance) {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
             no_error()
         } else {
-            error(ValidationError::constant_object(instance, &self.value))
+            error(ValidationError::constant_object(
+                self.schema_path.clone(),
+                instance_path.clone(),
+                instance,
+                &self.value,
+            ))
         }
     }
 
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         if let Value::Object(item) = instance {
             helpers::equal_objects(&self.value, item)
         } else {
@@ -189,9 +319,10 @@ impl Validate for ConstObjectValidator {
     }
 }
 
-impl ToString for ConstObjectValidator {
-    fn to_string(&self) -> String {
-        format!(
+impl fmt::Display for ConstObjectValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
             "const: {{{}}}",
             self.value
                 .iter()
@@ -204,27 +335,40 @@ impl ToString for ConstObjectValidator {
 
 pub(crate) struct ConstStringValidator {
     value: String,
+    schema_path: JSONPointer,
 }
 
 impl ConstStringValidator {
     #[inline]
-    pub(crate) fn compile(value: &str) -> CompilationResult {
+    pub(crate) fn compile(value: &str, schema_path: JSONPointer) -> CompilationResult {
         Ok(Box::new(ConstStringValidator {
             value: value.to_string(),
+            schema_path,
         }))
     }
 }
 
 impl Validate for ConstStringValidator {
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        if self.is_valid(schema, instance) {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
             no_error()
         } else {
-            error(ValidationError::constant_string(instance, &self.value))
+            error(ValidationError::constant_string(
+                self.schema_path.clone(),
+                instance_path.clone(),
+                instance,
+                &self.value,
+            ))
         }
     }
 
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         if let Value::String(item) = instance {
             &self.value == item
         } else {
@@ -233,9 +377,9 @@ impl Validate for ConstStringValidator {
     }
 }
 
-impl ToString for ConstStringValidator {
-    fn to_string(&self) -> String {
-        format!("const: {}", self.value)
+impl fmt::Display for ConstStringValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "const: {}", self.value)
     }
 }
 
@@ -243,14 +387,73 @@ impl ToString for ConstStringValidator {
 pub(crate) fn compile(
     _: &Map<String, Value>,
     schema: &Value,
-    _: &CompilationContext,
+    context: &CompilationContext,
 ) -> Option<CompilationResult> {
-    match schema {
-        Value::Array(items) => Some(ConstArrayValidator::compile(items)),
-        Value::Bool(item) => Some(ConstBooleanValidator::compile(*item)),
-        Value::Null => Some(ConstNullValidator::compile()),
-        Value::Number(item) => Some(ConstNumberValidator::compile(item)),
-        Value::Object(map) => Some(ConstObjectValidator::compile(map)),
-        Value::String(string) => Some(ConstStringValidator::compile(string)),
+    Some(compile_value(schema, context.schema_path.clone()))
+}
+
+/// A validator that accepts only `value`, e.g. for `const` or a single-value `enum`.
+#[inline]
+pub(crate) fn compile_value(value: &Value, schema_path: JSONPointer) -> CompilationResult {
+    match value {
+        Value::Array(items) => ConstArrayValidator::compile(items, schema_path),
+        Value::Bool(item) => ConstBooleanValidator::compile(*item, schema_path),
+        Value::Null => ConstNullValidator::compile(schema_path),
+        Value::Number(item) => {
+            let integer = item
+                .as_u64()
+                .map(i128::from)
+                .or_else(|| item.as_i64().map(i128::from));
+            if let Some(value) = integer {
+                ConstIntegerValidator::compile(item, value, schema_path)
+            } else {
+                ConstNumberValidator::compile(item, schema_path)
+            }
+        }
+        Value::Object(map) => ConstObjectValidator::compile(map, schema_path),
+        Value::String(string) => ConstStringValidator::compile(string, schema_path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests_util;
+    use serde_json::{json, Value};
+    use test_case::test_case;
+
+    #[test_case(&json!({"const": 42}), &json!(42))]
+    #[test_case(&json!({"const": 42}), &json!(42.0); "integer as float")]
+    #[test_case(&json!({"const": -42}), &json!(-42); "negative")]
+    #[test_case(&json!({"const": u64::MAX}), &json!(u64::MAX))]
+    #[test_case(&json!({"const": i64::MIN}), &json!(i64::MIN))]
+    #[test_case(&json!({"const": 1.5}), &json!(1.5))]
+    fn const_number_valid(schema: &Value, instance: &Value) {
+        tests_util::is_valid(schema, instance)
+    }
+
+    #[test_case(&json!({"const": 42}), &json!(43))]
+    #[test_case(&json!({"const": 42}), &json!(42.5); "fractional")]
+    #[test_case(&json!({"const": 42}), &json!("42"); "string")]
+    #[test_case(&json!({"const": -42}), &json!(42); "different sign")]
+    #[test_case(&json!({"const": u64::MAX}), &json!(u64::MAX - 1))]
+    #[test_case(&json!({"const": i64::MIN}), &json!(i64::MIN + 1))]
+    #[test_case(&json!({"const": 9_007_199_254_740_993_u64}), &json!(9_007_199_254_740_992_u64))]
+    fn const_number_invalid(schema: &Value, instance: &Value) {
+        tests_util::is_not_valid(schema, instance)
+    }
+
+    #[test_case(f64::NAN)]
+    #[test_case(f64::INFINITY)]
+    #[test_case(f64::NEG_INFINITY)]
+    fn non_finite_numbers_are_not_json_numbers(value: f64) {
+        // `Number` can't hold them, therefore number validators never see NaN
+        assert!(serde_json::Number::from_f64(value).is_none());
+        let instance = Value::from(value);
+        assert!(instance.is_null());
+        tests_util::is_not_valid(&json!({ "const": 0 }), &instance);
+        tests_util::is_valid(
+            &json!({"minimum": 0, "maximum": 0, "multipleOf": 2}),
+            &instance,
+        );
     }
 }
@@ -2,12 +2,17 @@ use crate::{
     compilation::{compile_validators, context::CompilationContext, JSONSchema},
     error::{error, no_error, ErrorIterator, ValidationError},
     keywords::{format_validators, CompilationResult, Validators},
+    output::{Annotation, PartialApplication},
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use serde_json::{Map, Value};
+use std::fmt;
 
 pub(crate) struct ContainsValidator {
     validators: Validators,
+    schema_path: JSONPointer,
 }
 
 impl ContainsValidator {
@@ -15,18 +20,19 @@ impl ContainsValidator {
     pub(crate) fn compile(schema: &Value, context: &CompilationContext) -> CompilationResult {
         Ok(Box::new(ContainsValidator {
             validators: compile_validators(schema, context)?,
+            schema_path: context.schema_path.clone(),
         }))
     }
 }
 
 impl Validate for ContainsValidator {
-    fn is_valid(&self, schema: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
         if let Value::Array(items) = instance {
             for item in items {
                 if self
                     .validators
                     .iter()
-                    .all(|validator| validator.is_valid(schema, item))
+                    .all(|validator| validator.is_valid(schema, item, ctx))
                 {
                     return true;
                 }
@@ -37,27 +43,65 @@ impl Validate for ContainsValidator {
         }
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
+            no_error()
+        } else {
+            error(ValidationError::contains(
+                self.schema_path.clone(),
+                instance_path.clone(),
+                instance,
+            ))
+        }
+    }
+
+    fn apply<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> PartialApplication<'a> {
         if let Value::Array(items) = instance {
-            for item in items {
-                if self
-                    .validators
-                    .iter()
-                    .all(|validator| validator.is_valid(schema, item))
-                {
-                    return no_error();
-                }
+            let matched: Vec<Value> = items
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| {
+                    self.validators
+                        .iter()
+                        .all(|validator| validator.is_valid(schema, item, ctx))
+                })
+                .map(|(idx, _)| idx.into())
+                .collect();
+            if matched.is_empty() {
+                vec![ValidationError::contains(
+                    self.schema_path.clone(),
+                    instance_path.clone(),
+                    instance,
+                )]
+                .into()
+            } else {
+                let mut result = PartialApplication::valid_empty();
+                result
+                    .annotations
+                    .push(Annotation::new("contains", Value::Array(matched)));
+                result
             }
-            error(ValidationError::contains(instance))
         } else {
-            no_error()
+            PartialApplication::valid_empty()
         }
     }
 }
 
-impl ToString for ContainsValidator {
-    fn to_string(&self) -> String {
-        format!("contains: {}", format_validators(&self.validators))
+impl fmt::Display for ContainsValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "contains: {}", format_validators(&self.validators))
     }
 }
 
@@ -69,3 +113,53 @@ pub(crate) fn compile(
 ) -> Option<CompilationResult> {
     Some(ContainsValidator::compile(schema, context))
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{compilation::JSONSchema, tests_util};
+    use serde_json::{json, Value};
+    use test_case::test_case;
+
+    #[test_case(&json!({"contains": {"minimum": 5}}), &json!([3, 4, 5]))]
+    #[test_case(&json!({"contains": {"minimum": 5}}), &json!([6, 7]))]
+    #[test_case(&json!({"contains": {"minimum": 5}}), &json!({}))]
+    #[test_case(&json!({"contains": {"minimum": 5}}), &json!("foo"))]
+    #[test_case(&json!({"contains": true}), &json!([1]))]
+    fn valid(schema: &Value, instance: &Value) {
+        tests_util::is_valid(schema, instance)
+    }
+
+    #[test_case(&json!({"contains": {"minimum": 5}}), &json!([2, 3, 4]))]
+    #[test_case(&json!({"contains": {"minimum": 5}}), &json!([]))]
+    #[test_case(&json!({"contains": true}), &json!([]))]
+    #[test_case(&json!({"contains": false}), &json!([1]))]
+    fn invalid(schema: &Value, instance: &Value) {
+        tests_util::is_not_valid(schema, instance)
+    }
+
+    #[test_case(&json!([3, 5, 4, 6]), &json!([1, 3]))]
+    #[test_case(&json!([5]), &json!([0]))]
+    fn annotates_matched_indices(instance: &Value, expected: &Value) {
+        let schema = json!({"contains": {"minimum": 5}});
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        let output = compiled.apply(instance);
+        assert!(output.is_valid());
+        assert_eq!(output.annotations().len(), 1);
+        assert_eq!(output.annotations()[0].keyword(), "contains");
+        assert_eq!(output.annotations()[0].value(), expected);
+    }
+
+    #[test]
+    fn no_annotations_on_failure() {
+        let schema = json!({"contains": {"minimum": 5}});
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        let instance = json!([1, 2]);
+        let output = compiled.apply(&instance);
+        assert!(!output.is_valid());
+        assert!(output.annotations().is_empty());
+        assert_eq!(
+            output.errors()[0].to_string(),
+            "/contains: None of '[1,2]' are valid under the given schema"
+        );
+    }
+}
@@ -5,29 +5,38 @@ use crate::{
     content_media_type::ContentMediaTypeCheckType,
     error::{error, no_error, CompilationError, ErrorIterator, ValidationError},
     keywords::CompilationResult,
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use serde_json::{Map, Value};
+use std::fmt;
 
 /// Validator for `contentMediaType` keyword.
 pub(crate) struct ContentMediaTypeValidator {
     media_type: String,
     func: ContentMediaTypeCheckType,
+    schema_path: JSONPointer,
 }
 
 impl ContentMediaTypeValidator {
     #[inline]
-    pub(crate) fn compile(media_type: &str, func: ContentMediaTypeCheckType) -> CompilationResult {
+    pub(crate) fn compile(
+        media_type: &str,
+        func: ContentMediaTypeCheckType,
+        schema_path: JSONPointer,
+    ) -> CompilationResult {
         Ok(Box::new(ContentMediaTypeValidator {
             media_type: media_type.to_string(),
             func,
+            schema_path,
         }))
     }
 }
 
 /// Validator delegates validation to the stored function.
 impl Validate for ContentMediaTypeValidator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         if let Value::String(item) = instance {
             (self.func)(item)
         } else {
@@ -35,12 +44,20 @@ impl Validate for ContentMediaTypeValidator {
         }
     }
 
-    fn validate<'a>(&self, _: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        _: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        _: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::String(item) = instance {
             if (self.func)(item) {
                 no_error()
             } else {
                 error(ValidationError::content_media_type(
+                    self.schema_path.clone(),
+                    instance_path.clone(),
                     instance,
                     &self.media_type,
                 ))
@@ -51,9 +68,9 @@ impl Validate for ContentMediaTypeValidator {
     }
 }
 
-impl ToString for ContentMediaTypeValidator {
-    fn to_string(&self) -> String {
-        format!("contentMediaType: {}", self.media_type)
+impl fmt::Display for ContentMediaTypeValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "contentMediaType: {}", self.media_type)
     }
 }
 
@@ -61,20 +78,26 @@ impl ToString for ContentMediaTypeValidator {
 pub(crate) struct ContentEncodingValidator {
     encoding: String,
     func: ContentEncodingCheckType,
+    schema_path: JSONPointer,
 }
 
 impl ContentEncodingValidator {
     #[inline]
-    pub(crate) fn compile(encoding: &str, func: ContentEncodingCheckType) -> CompilationResult {
+    pub(crate) fn compile(
+        encoding: &str,
+        func: ContentEncodingCheckType,
+        schema_path: JSONPointer,
+    ) -> CompilationResult {
         Ok(Box::new(ContentEncodingValidator {
             encoding: encoding.to_string(),
             func,
+            schema_path,
         }))
     }
 }
 
 impl Validate for ContentEncodingValidator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         if let Value::String(item) = instance {
             (self.func)(item)
         } else {
@@ -82,12 +105,23 @@ impl Validate for ContentEncodingValidator {
         }
     }
 
-    fn validate<'a>(&self, _: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        _: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        _: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::String(item) = instance {
             if (self.func)(item) {
                 no_error()
             } else {
-                error(ValidationError::content_encoding(instance, &self.encoding))
+                error(ValidationError::content_encoding(
+                    self.schema_path.clone(),
+                    instance_path.clone(),
+                    instance,
+                    &self.encoding,
+                ))
             }
         } else {
             no_error()
@@ -95,9 +129,9 @@ impl Validate for ContentEncodingValidator {
     }
 }
 
-impl ToString for ContentEncodingValidator {
-    fn to_string(&self) -> String {
-        format!("contentEncoding: {}", self.encoding)
+impl fmt::Display for ContentEncodingValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "contentEncoding: {}", self.encoding)
     }
 }
 
@@ -107,6 +141,7 @@ pub(crate) struct ContentMediaTypeAndEncodingValidator {
     encoding: String,
     func: ContentMediaTypeCheckType,
     converter: ContentEncodingConverterType,
+    schema_path: JSONPointer,
 }
 
 impl ContentMediaTypeAndEncodingValidator {
@@ -116,19 +151,21 @@ impl ContentMediaTypeAndEncodingValidator {
         encoding: &str,
         func: ContentMediaTypeCheckType,
         converter: ContentEncodingConverterType,
+        schema_path: JSONPointer,
     ) -> CompilationResult {
         Ok(Box::new(ContentMediaTypeAndEncodingValidator {
             media_type: media_type.to_string(),
             encoding: encoding.to_string(),
             func,
             converter,
+            schema_path,
         }))
     }
 }
 
 /// Decode the input value & check media type
 impl Validate for ContentMediaTypeAndEncodingValidator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         if let Value::String(item) = instance {
             match (self.converter)(item) {
                 Ok(None) | Err(_) => false,
@@ -139,15 +176,28 @@ impl Validate for ContentMediaTypeAndEncodingValidator {
         }
     }
 
-    fn validate<'a>(&self, _: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        _: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        _: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::String(item) = instance {
             match (self.converter)(item) {
-                Ok(None) => error(ValidationError::content_encoding(instance, &self.encoding)),
+                Ok(None) => error(ValidationError::content_encoding(
+                    self.schema_path.clone(),
+                    instance_path.clone(),
+                    instance,
+                    &self.encoding,
+                )),
                 Ok(Some(converted)) => {
                     if (self.func)(&converted) {
                         no_error()
                     } else {
                         error(ValidationError::content_media_type(
+                            self.schema_path.clone(),
+                            instance_path.clone(),
                             instance,
                             &self.media_type,
                         ))
@@ -161,9 +211,10 @@ impl Validate for ContentMediaTypeAndEncodingValidator {
     }
 }
 
-impl ToString for ContentMediaTypeAndEncodingValidator {
-    fn to_string(&self) -> String {
-        format!(
+impl fmt::Display for ContentMediaTypeAndEncodingValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
             "{{contentMediaType: {}, contentEncoding: {}}}",
             self.media_type, self.encoding
         )
@@ -197,12 +248,17 @@ pub(crate) fn compile_media_type(
                             content_encoding,
                             func,
                             converter,
+                            context.schema_path.clone(),
                         ))
                     }
                     _ => Some(Err(CompilationError::SchemaError)),
                 }
             } else {
-                Some(ContentMediaTypeValidator::compile(media_type, func))
+                Some(ContentMediaTypeValidator::compile(
+                    media_type,
+                    func,
+                    context.schema_path.clone(),
+                ))
             }
         }
         _ => Some(Err(CompilationError::SchemaError)),
@@ -229,7 +285,11 @@ pub(crate) fn compile_content_encoding(
                 Some(f) => f,
                 None => return None,
             };
-            Some(ContentEncodingValidator::compile(content_encoding, func))
+            Some(ContentEncodingValidator::compile(
+                content_encoding,
+                func,
+                context.schema_path.clone(),
+            ))
         }
         _ => Some(Err(CompilationError::SchemaError)),
     }
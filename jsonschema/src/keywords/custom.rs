@@ -0,0 +1,252 @@
+//! User-defined keywords registered via `CompilationOptions::with_keyword`.
+use crate::{
+    compilation::JSONSchema,
+    error::{error, no_error, CompilationError, ErrorIterator, ValidationError},
+    keywords::CompilationResult,
+    paths::JSONPointer,
+    validation_context::ValidationContext,
+    validator::Validate,
+};
+use serde_json::{Map, Value};
+use std::{fmt, sync::Arc};
+
+/// A custom keyword compiled from its value in a schema.
+///
+/// ```rust
+/// # use jsonschema::{CompilationOptions, Keyword, ValidationContext};
+/// # use serde_json::{json, Value};
+/// struct Even;
+///
+/// impl Keyword for Even {
+///     fn validate(&self, instance: &Value, _: &ValidationContext) -> Result<(), String> {
+///         match instance.as_u64() {
+///             Some(value) if value % 2 != 0 => Err(format!("{} is not even", value)),
+///             _ => Ok(()),
+///         }
+///     }
+/// }
+///
+/// let schema = json!({"even": true});
+/// let compiled = CompilationOptions::default()
+///     .with_keyword("even", |_, _| Ok(Box::new(Even)))
+///     .compile(&schema)
+///     .expect("A valid schema");
+/// assert!(compiled.is_valid(&json!(2)));
+/// assert!(!compiled.is_valid(&json!(3)));
+/// ```
+pub trait Keyword: Send + Sync {
+    /// Validate `instance`. The error is a message describing why the instance is not valid.
+    /// It is reported at the keyword location, see `ValidationError::schema_path`.
+    /// `context` holds the data passed to `JSONSchema::validate_with_context` or other methods
+    /// with the `_with_context` suffix.
+    fn validate(&self, instance: &Value, context: &ValidationContext) -> Result<(), String>;
+
+    /// Whether `instance` is valid. Override it if the check is cheaper without the message.
+    fn is_valid(&self, instance: &Value, context: &ValidationContext) -> bool {
+        self.validate(instance, context).is_ok()
+    }
+}
+
+/// Builds a custom keyword from the parent schema and the keyword value.
+pub(crate) type KeywordFactory = Arc<
+    dyn Fn(&Map<String, Value>, &Value) -> Result<Box<dyn Keyword>, CompilationError> + Send + Sync,
+>;
+
+pub(crate) struct CustomKeywordValidator {
+    keyword: Box<dyn Keyword>,
+    // Needed only for the textual representation
+    name: String,
+    value: Value,
+    schema_path: JSONPointer,
+}
+
+impl CustomKeywordValidator {
+    #[inline]
+    pub(crate) fn compile(
+        factory: &KeywordFactory,
+        name: &str,
+        parent: &Map<String, Value>,
+        schema: &Value,
+        schema_path: JSONPointer,
+    ) -> CompilationResult {
+        Ok(Box::new(CustomKeywordValidator {
+            keyword: factory(parent, schema)?,
+            name: name.to_string(),
+            value: schema.clone(),
+            schema_path,
+        }))
+    }
+}
+
+impl Validate for CustomKeywordValidator {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
+        self.keyword.is_valid(instance, ctx)
+    }
+
+    fn validate<'a>(
+        &self,
+        _: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        match self.keyword.validate(instance, ctx) {
+            Ok(()) => no_error(),
+            Err(message) => error(ValidationError::custom(
+                self.schema_path.clone(),
+                instance_path.clone(),
+                instance,
+                message,
+            )),
+        }
+    }
+}
+
+impl fmt::Display for CustomKeywordValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.name, self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Keyword;
+    use crate::{
+        compilation::options::CompilationOptions, error::CompilationError,
+        validation_context::ValidationContext, JSONSchema,
+    };
+    use serde_json::{json, Value};
+    use std::collections::HashSet;
+
+    struct Taken(HashSet<String>);
+
+    /// A username that is not taken yet. Taken usernames are provided via the context.
+    struct Available;
+
+    impl Keyword for Available {
+        fn validate(&self, instance: &Value, context: &ValidationContext) -> Result<(), String> {
+            match (instance.as_str(), context.get::<Taken>()) {
+                (Some(name), Some(taken)) if taken.0.contains(name) => {
+                    Err(format!("'{}' is already taken", name))
+                }
+                _ => Ok(()),
+            }
+        }
+    }
+
+    fn compile(schema: &Value) -> JSONSchema<'_> {
+        CompilationOptions::default()
+            .with_keyword("available", |_, value| match value {
+                Value::Bool(true) => Ok(Box::new(Available)),
+                _ => Err(CompilationError::SchemaError),
+            })
+            .compile(schema)
+            .unwrap()
+    }
+
+    fn context() -> ValidationContext {
+        let mut context = ValidationContext::new();
+        context.insert(Taken(vec!["admin".to_string()].into_iter().collect()));
+        context
+    }
+
+    #[test]
+    fn nested_keyword_receives_context() {
+        let schema = json!({"properties": {"name": {"type": "string", "available": true}}});
+        let compiled = compile(&schema);
+        let context = context();
+        let instance = json!({"name": "admin"});
+        assert!(!compiled.is_valid_with_context(&instance, &context));
+        let errors: Vec<_> = compiled
+            .validate_with_context(&instance, &context)
+            .unwrap_err()
+            .collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].to_string(),
+            "/properties/name/available: 'admin' is already taken"
+        );
+        assert_eq!(errors[0].instance_path().to_string(), "/name");
+        assert!(compiled.is_valid_with_context(&json!({"name": "alice"}), &context));
+    }
+
+    #[test]
+    fn context_is_scoped() {
+        let schema = json!({"available": true});
+        let compiled = compile(&schema);
+        let instance = json!("admin");
+        assert!(!compiled.is_valid_with_context(&instance, &context()));
+        // Without context nothing is taken
+        assert!(compiled.is_valid(&instance));
+        assert!(compiled.validate(&instance).is_ok());
+    }
+
+    #[test]
+    fn context_in_other_entry_points() {
+        let schema = json!({"items": {"available": true}});
+        let compiled = compile(&schema);
+        let context = context();
+        let instance = json!(["admin", "alice", "admin"]);
+        assert_eq!(
+            compiled
+                .validate_limited_with_context(&instance, 1, &context)
+                .count(),
+            1
+        );
+        let error = compiled
+            .first_error_with_context(&instance, &context)
+            .unwrap_err();
+        assert_eq!(error.instance_path().to_string(), "/0");
+        let output = compiled.apply_with_context(&instance, &context);
+        assert!(!output.is_valid());
+        // Without context nothing is taken
+        assert_eq!(compiled.validate_limited(&instance, 1).count(), 0);
+        assert!(compiled.first_error(&instance).is_ok());
+        assert!(compiled.apply(&instance).is_valid());
+    }
+
+    #[test]
+    fn context_with_lazy_errors() {
+        // Errors of `items` are produced lazily, while the iterator is consumed
+        let schema = json!({"items": {"available": true}});
+        let compiled = compile(&schema);
+        let context = context();
+        let instance = json!(["admin", "admin"]);
+        let errors = compiled
+            .validate_with_context(&instance, &context)
+            .unwrap_err();
+        assert_eq!(errors.count(), 2);
+    }
+
+    #[test]
+    fn invalid_keyword_value() {
+        let schema = json!({"available": 1});
+        let error = CompilationOptions::default()
+            .with_keyword("available", |_, value| match value {
+                Value::Bool(true) => Ok(Box::new(Available)),
+                _ => Err(CompilationError::SchemaError),
+            })
+            .compile(&schema)
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Invalid value of the 'available' keyword at '/available': 1"
+        );
+    }
+
+    #[test]
+    fn overrides_built_in_keyword() {
+        struct Never;
+        impl Keyword for Never {
+            fn validate(&self, _: &Value, _: &ValidationContext) -> Result<(), String> {
+                Err("never".to_string())
+            }
+        }
+        let schema = json!({"minLength": 1});
+        let compiled = CompilationOptions::default()
+            .with_keyword("minLength", |_, _| Ok(Box::new(Never)))
+            .compile(&schema)
+            .unwrap();
+        assert!(!compiled.is_valid(&json!("foo")));
+    }
+}
@@ -1,15 +1,16 @@
 use crate::{
     compilation::{compile_validators, context::CompilationContext, JSONSchema},
-    error::{no_error, CompilationError, ErrorIterator},
-    keywords::{
-        format_key_value_validators, required::RequiredValidator, CompilationResult, Validators,
-    },
+    error::{no_error, CompilationError, ErrorIterator, ValidationError},
+    keywords::{format_validators, BoxedValidator, CompilationResult, Validators},
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use serde_json::{Map, Value};
+use std::fmt;
 
 pub(crate) struct DependenciesValidator {
-    dependencies: Vec<(String, Validators)>,
+    dependencies: Vec<(String, BoxedValidator)>,
 }
 
 impl DependenciesValidator {
@@ -18,11 +19,16 @@ impl DependenciesValidator {
         if let Value::Object(map) = schema {
             let mut dependencies = Vec::with_capacity(map.len());
             for (key, subschema) in map {
-                let s = match subschema {
-                    Value::Array(_) => vec![RequiredValidator::compile(subschema)?],
-                    _ => compile_validators(subschema, context)?,
+                let item_context = context.with_path(key.as_str());
+                let validator = match subschema {
+                    Value::Array(items) => PropertyDependencyValidator::compile(
+                        key,
+                        items,
+                        item_context.schema_path.clone(),
+                    )?,
+                    _ => SchemaDependencyValidator::compile(subschema, &item_context)?,
                 };
-                dependencies.push((key.clone(), s))
+                dependencies.push((key.clone(), validator))
             }
             Ok(Box::new(DependenciesValidator { dependencies }))
         } else {
@@ -32,34 +38,34 @@ impl DependenciesValidator {
 }
 
 impl Validate for DependenciesValidator {
-    fn is_valid(&self, schema: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
         if let Value::Object(item) = instance {
             self.dependencies
                 .iter()
                 .filter(|(property, _)| item.contains_key(property))
-                .all(move |(_, validators)| {
-                    validators
-                        .iter()
-                        .all(move |validator| validator.is_valid(schema, instance))
-                })
+                .all(move |(_, validator)| validator.is_valid(schema, instance, ctx))
         } else {
             true
         }
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::Object(item) = instance {
             let errors: Vec<_> = self
                 .dependencies
                 .iter()
                 .filter(|(property, _)| item.contains_key(property))
-                .flat_map(move |(_, validators)| {
-                    validators
-                        .iter()
-                        .flat_map(move |validator| validator.validate(schema, instance))
+                .flat_map(move |(_, validator)| {
+                    validator.validate(schema, instance, instance_path, ctx)
                 })
+                .take(ctx.error_budget())
                 .collect();
-            // TODO. custom error message for "required" case
             Box::new(errors.into_iter())
         } else {
             no_error()
@@ -67,15 +73,143 @@ impl Validate for DependenciesValidator {
     }
 }
 
-impl ToString for DependenciesValidator {
-    fn to_string(&self) -> String {
-        format!(
+impl fmt::Display for DependenciesValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
             "dependencies: {{{}}}",
-            format_key_value_validators(&self.dependencies)
+            self.dependencies
+                .iter()
+                .map(|(name, validator)| format!("{}: {}", name, validator))
+                .collect::<Vec<String>>()
+                .join(", ")
         )
     }
 }
 
+/// The array form - listed properties are required if `property` is present.
+pub(crate) struct PropertyDependencyValidator {
+    property: String,
+    required: Vec<String>,
+    schema_path: JSONPointer,
+}
+
+impl PropertyDependencyValidator {
+    #[inline]
+    pub(crate) fn compile(
+        property: &str,
+        items: &[Value],
+        schema_path: JSONPointer,
+    ) -> CompilationResult {
+        let mut required = Vec::with_capacity(items.len());
+        for item in items {
+            match item {
+                Value::String(string) => required.push(string.clone()),
+                _ => return Err(CompilationError::SchemaError),
+            }
+        }
+        Ok(Box::new(PropertyDependencyValidator {
+            property: property.to_string(),
+            required,
+            schema_path,
+        }))
+    }
+}
+
+impl Validate for PropertyDependencyValidator {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::Object(item) = instance {
+            !item.contains_key(&self.property)
+                || self
+                    .required
+                    .iter()
+                    .all(|dependency| item.contains_key(dependency))
+        } else {
+            true
+        }
+    }
+
+    fn validate<'a>(
+        &self,
+        _: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        _: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        match instance {
+            Value::Object(item) if item.contains_key(&self.property) => {
+                let errors: Vec<_> = self
+                    .required
+                    .iter()
+                    .filter(|dependency| !item.contains_key(*dependency))
+                    .map(|dependency| {
+                        ValidationError::dependency(
+                            self.schema_path.clone(),
+                            instance_path.clone(),
+                            instance,
+                            self.property.clone(),
+                            dependency.clone(),
+                        )
+                    })
+                    .collect();
+                Box::new(errors.into_iter())
+            }
+            _ => no_error(),
+        }
+    }
+}
+
+impl fmt::Display for PropertyDependencyValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{required: [{}]}}", self.required.join(", "))
+    }
+}
+
+/// The schema form - the whole object should be valid under the sub-schema if the property
+/// is present. The presence is checked by `DependenciesValidator`.
+pub(crate) struct SchemaDependencyValidator {
+    validators: Validators,
+}
+
+impl SchemaDependencyValidator {
+    #[inline]
+    pub(crate) fn compile(schema: &Value, context: &CompilationContext) -> CompilationResult {
+        Ok(Box::new(SchemaDependencyValidator {
+            validators: compile_validators(schema, context)?,
+        }))
+    }
+}
+
+impl Validate for SchemaDependencyValidator {
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
+        self.validators
+            .iter()
+            .all(|validator| validator.is_valid(schema, instance, ctx))
+    }
+
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        let errors: Vec<_> = self
+            .validators
+            .iter()
+            .flat_map(|validator| validator.validate(schema, instance, instance_path, ctx))
+            .take(ctx.error_budget())
+            .collect();
+        Box::new(errors.into_iter())
+    }
+}
+
+impl fmt::Display for SchemaDependencyValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format_validators(&self.validators))
+    }
+}
+
 #[inline]
 pub(crate) fn compile(
     _: &Map<String, Value>,
@@ -84,3 +218,64 @@ pub(crate) fn compile(
 ) -> Option<CompilationResult> {
     Some(DependenciesValidator::compile(schema, context))
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{compilation::JSONSchema, tests_util};
+    use serde_json::{json, Value};
+    use test_case::test_case;
+
+    #[test_case(&json!({"bar": 1}); "without property")]
+    #[test_case(&json!({"foo": 1, "bar": 2}); "with dependency")]
+    #[test_case(&json!("foo"); "not an object")]
+    fn property_dependency_valid(instance: &Value) {
+        tests_util::is_valid(&json!({"dependencies": {"foo": ["bar"]}}), instance)
+    }
+
+    #[test_case(&json!({"bar": 1}); "without property")]
+    #[test_case(&json!({"foo": 1, "bar": 2}); "valid sub-schema")]
+    fn schema_dependency_valid(instance: &Value) {
+        tests_util::is_valid(
+            &json!({"dependencies": {"foo": {"properties": {"bar": {"type": "integer"}}}}}),
+            instance,
+        )
+    }
+
+    #[test]
+    fn property_dependency_errors() {
+        let schema = json!({"dependencies": {"foo": ["bar", "baz"]}});
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        let instance = json!({"foo": 1});
+        let errors: Vec<_> = compiled
+            .validate(&instance)
+            .unwrap_err()
+            .map(|error| error.to_string())
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                "/dependencies/foo: 'bar' is a required property when 'foo' is present",
+                "/dependencies/foo: 'baz' is a required property when 'foo' is present",
+            ]
+        );
+    }
+
+    #[test]
+    fn schema_dependency_errors() {
+        let schema = json!({"dependencies": {"foo": {"properties": {"bar": {"type": "integer"}}}}});
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        let instance = json!({"foo": 1, "bar": "a"});
+        let errors: Vec<_> = compiled.validate(&instance).unwrap_err().collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].schema_path().to_string(),
+            "/dependencies/foo/properties/bar/type"
+        );
+        assert_eq!(errors[0].instance_path().to_string(), "/bar");
+    }
+
+    #[test]
+    fn invalid_property_dependency() {
+        assert!(JSONSchema::compile(&json!({"dependencies": {"foo": [1]}})).is_err())
+    }
+}
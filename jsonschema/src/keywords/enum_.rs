@@ -1,11 +1,14 @@
 use crate::{
     compilation::{context::CompilationContext, JSONSchema},
     error::{error, no_error, CompilationError, ErrorIterator, ValidationError},
-    keywords::{helpers, CompilationResult},
+    keywords::{const_, helpers, BoxedValidator, CompilationResult},
+    paths::JSONPointer,
     primitive_type::{PrimitiveType, PrimitiveTypesBitMap},
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use serde_json::{Map, Value};
+use std::fmt;
 
 #[derive(Debug)]
 pub(crate) struct EnumValidator {
@@ -13,11 +16,12 @@ pub(crate) struct EnumValidator {
     // Types that occur in items
     types: PrimitiveTypesBitMap,
     items: Vec<Value>,
+    schema_path: JSONPointer,
 }
 
 impl EnumValidator {
     #[inline]
-    pub(crate) fn compile(schema: &Value) -> CompilationResult {
+    pub(crate) fn compile(schema: &Value, schema_path: JSONPointer) -> CompilationResult {
         if let Value::Array(items) = schema {
             let mut types = PrimitiveTypesBitMap::new();
             for item in items.iter() {
@@ -27,6 +31,7 @@ impl EnumValidator {
                 options: schema.clone(),
                 items: items.clone(),
                 types,
+                schema_path,
             }))
         } else {
             Err(CompilationError::SchemaError)
@@ -35,15 +40,26 @@ impl EnumValidator {
 }
 
 impl Validate for EnumValidator {
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        if !self.is_valid(schema, instance) {
-            error(ValidationError::enumeration(instance, &self.options))
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if !self.is_valid(schema, instance, ctx) {
+            error(ValidationError::enumeration(
+                self.schema_path.clone(),
+                instance_path.clone(),
+                instance,
+                &self.options,
+            ))
         } else {
             no_error()
         }
     }
 
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         // If the input value type is not in the types present among the enum options, then there
         // is no reason to compare it against all items - we know that
         // there are no items with such type at all
@@ -55,9 +71,10 @@ impl Validate for EnumValidator {
     }
 }
 
-impl ToString for EnumValidator {
-    fn to_string(&self) -> String {
-        format!(
+impl fmt::Display for EnumValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
             "enum: [{}]",
             self.items
                 .iter()
@@ -68,11 +85,122 @@ impl ToString for EnumValidator {
     }
 }
 
+/// An `enum` with a single option, which is the same as `const`.
+/// Validation is delegated to the specialized `const` validator, but errors are reported as `enum`.
+pub(crate) struct SingleValueEnumValidator {
+    options: Value,
+    validator: BoxedValidator,
+    schema_path: JSONPointer,
+}
+
+impl SingleValueEnumValidator {
+    #[inline]
+    pub(crate) fn compile(
+        options: &Value,
+        value: &Value,
+        schema_path: JSONPointer,
+    ) -> CompilationResult {
+        Ok(Box::new(SingleValueEnumValidator {
+            options: options.clone(),
+            validator: const_::compile_value(value, schema_path.clone())?,
+            schema_path,
+        }))
+    }
+}
+
+impl Validate for SingleValueEnumValidator {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if !self.is_valid(schema, instance, ctx) {
+            error(ValidationError::enumeration(
+                self.schema_path.clone(),
+                instance_path.clone(),
+                instance,
+                &self.options,
+            ))
+        } else {
+            no_error()
+        }
+    }
+
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
+        self.validator.is_valid(schema, instance, ctx)
+    }
+}
+
+impl fmt::Display for SingleValueEnumValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "enum: {}", self.options)
+    }
+}
+
 #[inline]
 pub(crate) fn compile(
     _: &Map<String, Value>,
     schema: &Value,
-    _: &CompilationContext,
+    context: &CompilationContext,
 ) -> Option<CompilationResult> {
-    Some(EnumValidator::compile(schema))
+    match schema {
+        Value::Array(items) if items.len() == 1 => Some(SingleValueEnumValidator::compile(
+            schema,
+            &items[0],
+            context.schema_path.clone(),
+        )),
+        _ => Some(EnumValidator::compile(schema, context.schema_path.clone())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{compilation::JSONSchema, tests_util};
+    use serde_json::{json, Value};
+    use test_case::test_case;
+
+    #[test_case(&json!(null))]
+    #[test_case(&json!(1))]
+    #[test_case(&json!(1.0))]
+    #[test_case(&json!("a"))]
+    fn null_option_valid(instance: &Value) {
+        tests_util::is_valid(&json!({"enum": [null, 1, "a"]}), instance)
+    }
+
+    #[test_case(&json!(true))]
+    #[test_case(&json!(0))]
+    #[test_case(&json!("null"); "null string")]
+    #[test_case(&json!([null]))]
+    fn null_option_invalid(instance: &Value) {
+        tests_util::is_not_valid(&json!({"enum": [null, 1, "a"]}), instance)
+    }
+
+    #[test]
+    fn null_instance_without_null_option() {
+        tests_util::is_not_valid(&json!({"enum": [1, "a"]}), &json!(null))
+    }
+
+    #[test_case(&json!(1.0))]
+    #[test_case(&json!(1))]
+    fn single_option_valid(instance: &Value) {
+        tests_util::is_valid(&json!({"enum": [1]}), instance)
+    }
+
+    #[test]
+    fn single_option_error() {
+        let schema = json!({"enum": [{"a": 1}]});
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        let instance = json!({"a": 2});
+        let errors: Vec<_> = compiled.validate(&instance).unwrap_err().collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].to_string(),
+            r#"/enum: '{"a":2}' is not one of '[{"a":1}]'"#
+        );
+        assert!(errors[0].is_enum_error());
+        assert_eq!(errors[0].keyword(), "enum");
+        assert_eq!(errors[0].schema_path().to_string(), "/enum");
+    }
 }
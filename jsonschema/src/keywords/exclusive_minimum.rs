@@ -2,19 +2,25 @@ use crate::{
     compilation::{context::CompilationContext, JSONSchema},
     error::{error, no_error, CompilationError, ErrorIterator, ValidationError},
     keywords::CompilationResult,
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use num_cmp::NumCmp;
 use serde_json::{Map, Value};
+use std::fmt;
 
 pub(crate) struct ExclusiveMinimumU64Validator {
     limit: u64,
+    schema_path: JSONPointer,
 }
 pub(crate) struct ExclusiveMinimumI64Validator {
     limit: i64,
+    schema_path: JSONPointer,
 }
 pub(crate) struct ExclusiveMinimumF64Validator {
     limit: f64,
+    schema_path: JSONPointer,
 }
 
 macro_rules! validate {
@@ -24,18 +30,22 @@ macro_rules! validate {
                 &self,
                 schema: &'a JSONSchema,
                 instance: &'a Value,
+                instance_path: &JSONPointer,
+                ctx: &'a ValidationContext,
             ) -> ErrorIterator<'a> {
-                if self.is_valid(schema, instance) {
+                if self.is_valid(schema, instance, ctx) {
                     no_error()
                 } else {
                     error(ValidationError::exclusive_minimum(
+                        self.schema_path.clone(),
+                        instance_path.clone(),
                         instance,
-                        self.limit as f64,
+                        self.limit.into(),
                     ))
                 }
             }
 
-            fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+            fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
                 if let Value::Number(item) = instance {
                     return if let Some(item) = item.as_u64() {
                         NumCmp::num_gt(item, self.limit)
@@ -49,9 +59,9 @@ macro_rules! validate {
                 true
             }
         }
-        impl ToString for $validator {
-            fn to_string(&self) -> String {
-                format!("exclusiveMinimum: {}", self.limit)
+        impl fmt::Display for $validator {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "exclusiveMinimum: {}", self.limit)
             }
         }
     };
@@ -61,7 +71,7 @@ validate!(ExclusiveMinimumU64Validator);
 validate!(ExclusiveMinimumI64Validator);
 
 impl Validate for ExclusiveMinimumF64Validator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         if let Value::Number(item) = instance {
             return if let Some(item) = item.as_u64() {
                 NumCmp::num_gt(item, self.limit)
@@ -75,17 +85,28 @@ impl Validate for ExclusiveMinimumF64Validator {
         true
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        if self.is_valid(schema, instance) {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
             no_error()
         } else {
-            error(ValidationError::exclusive_minimum(instance, self.limit))
+            error(ValidationError::exclusive_minimum(
+                self.schema_path.clone(),
+                instance_path.clone(),
+                instance,
+                self.limit.into(),
+            ))
         }
     }
 }
-impl ToString for ExclusiveMinimumF64Validator {
-    fn to_string(&self) -> String {
-        format!("exclusiveMinimum: {}", self.limit)
+impl fmt::Display for ExclusiveMinimumF64Validator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "exclusiveMinimum: {}", self.limit)
     }
 }
 
@@ -93,16 +114,26 @@ impl ToString for ExclusiveMinimumF64Validator {
 pub(crate) fn compile(
     _: &Map<String, Value>,
     schema: &Value,
-    _: &CompilationContext,
+    context: &CompilationContext,
 ) -> Option<CompilationResult> {
     if let Value::Number(limit) = schema {
+        let schema_path = context.schema_path.clone();
         if let Some(limit) = limit.as_u64() {
-            Some(Ok(Box::new(ExclusiveMinimumU64Validator { limit })))
+            Some(Ok(Box::new(ExclusiveMinimumU64Validator {
+                limit,
+                schema_path,
+            })))
         } else if let Some(limit) = limit.as_i64() {
-            Some(Ok(Box::new(ExclusiveMinimumI64Validator { limit })))
+            Some(Ok(Box::new(ExclusiveMinimumI64Validator {
+                limit,
+                schema_path,
+            })))
         } else {
             let limit = limit.as_f64().expect("Always valid");
-            Some(Ok(Box::new(ExclusiveMinimumF64Validator { limit })))
+            Some(Ok(Box::new(ExclusiveMinimumF64Validator {
+                limit,
+                schema_path,
+            })))
         }
     } else {
         Some(Err(CompilationError::SchemaError))
@@ -0,0 +1,100 @@
+//! `base64`, `base64url` and `byte` formats.
+use crate::{compilation::JSONSchema, validation_context::ValidationContext, validator::Validate};
+use serde_json::Value;
+
+/// Whether `item` is base64-encoded. Line breaks are ignored (RFC 2045) and padding is optional.
+// `usize::is_multiple_of` requires Rust 1.87
+#[allow(clippy::manual_is_multiple_of)]
+fn is_base64(item: &str, url_safe: bool) -> bool {
+    let (extra_62, extra_63) = if url_safe { (b'-', b'_') } else { (b'+', b'/') };
+    let mut length = 0_usize;
+    let mut padding = 0_usize;
+    for byte in item.bytes().filter(|byte| *byte != b'\r' && *byte != b'\n') {
+        if byte == b'=' {
+            padding += 1;
+        } else if padding == 0
+            && (byte.is_ascii_alphanumeric() || byte == extra_62 || byte == extra_63)
+        {
+            length += 1;
+        } else {
+            // Either an unknown character or a character after padding
+            return false;
+        }
+    }
+    match padding {
+        0 => length % 4 != 1,
+        1 | 2 => (length + padding) % 4 == 0,
+        _ => false,
+    }
+}
+format_validator!(Base64Validator, "base64");
+impl Validate for Base64Validator {
+    validate!("base64");
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::String(item) = instance {
+            is_base64(item, false)
+        } else {
+            true
+        }
+    }
+}
+
+format_validator!(Base64UrlValidator, "base64url");
+impl Validate for Base64UrlValidator {
+    validate!("base64url");
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::String(item) = instance {
+            is_base64(item, true)
+        } else {
+            true
+        }
+    }
+}
+
+format_validator!(ByteValidator, "byte");
+impl Validate for ByteValidator {
+    validate!("byte");
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::String(item) = instance {
+            is_base64(item, false)
+        } else {
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests_util;
+    use serde_json::json;
+    use test_case::test_case;
+
+    #[test_case("base64", "Zm9vYg=="; "base64 padded")]
+    #[test_case("base64", "Zm9vYg"; "base64 not padded")]
+    #[test_case("base64", "Zm9v\r\nYmFy")]
+    #[test_case("base64", "+/+/"; "base64 standard alphabet")]
+    #[test_case("base64", "")]
+    #[test_case("byte", "Zm9vYmE=")]
+    #[test_case("base64url", "-_-_"; "base64url url-safe alphabet")]
+    #[test_case("base64url", "Zm9vYg"; "base64url not padded")]
+    fn base64_valid(format: &str, instance: &str) {
+        tests_util::is_valid(&json!({ "format": format }), &json!(instance))
+    }
+
+    #[test_case("base64", "Zm9vYg="; "base64 wrong padding")]
+    #[test_case("base64", "Zm9vY")]
+    #[test_case("base64", "Zm9v YmFy")]
+    #[test_case("base64", "Zm9vYg==="; "base64 too much padding")]
+    #[test_case("base64", "Zg==Zg=="; "base64 data after padding")]
+    #[test_case("base64", "-_-_"; "base64 url-safe alphabet")]
+    #[test_case("byte", "Zm9v!")]
+    #[test_case("base64url", "+/+/"; "base64url standard alphabet")]
+    fn base64_invalid(format: &str, instance: &str) {
+        tests_util::is_not_valid(&json!({ "format": format }), &json!(instance))
+    }
+
+    #[test]
+    fn base64_non_string() {
+        tests_util::is_valid(&json!({"format": "base64"}), &json!(42))
+    }
+}
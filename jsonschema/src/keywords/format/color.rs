@@ -0,0 +1,84 @@
+//! `color` format, available with the `custom-formats` feature.
+use crate::{compilation::JSONSchema, validation_context::ValidationContext, validator::Validate};
+use serde_json::Value;
+
+lazy_static::lazy_static! {
+    // Named colors from CSS Color Module Level 4
+    static ref CSS_NAMED_COLORS: ahash::AHashSet<&'static str> = [
+        "aliceblue", "antiquewhite", "aqua", "aquamarine", "azure", "beige", "bisque", "black",
+        "blanchedalmond", "blue", "blueviolet", "brown", "burlywood", "cadetblue", "chartreuse",
+        "chocolate", "coral", "cornflowerblue", "cornsilk", "crimson", "cyan", "darkblue",
+        "darkcyan", "darkgoldenrod", "darkgray", "darkgreen", "darkgrey", "darkkhaki",
+        "darkmagenta", "darkolivegreen", "darkorange", "darkorchid", "darkred", "darksalmon",
+        "darkseagreen", "darkslateblue", "darkslategray", "darkslategrey", "darkturquoise",
+        "darkviolet", "deeppink", "deepskyblue", "dimgray", "dimgrey", "dodgerblue",
+        "firebrick", "floralwhite", "forestgreen", "fuchsia", "gainsboro", "ghostwhite", "gold",
+        "goldenrod", "gray", "green", "greenyellow", "grey", "honeydew", "hotpink", "indianred",
+        "indigo", "ivory", "khaki", "lavender", "lavenderblush", "lawngreen", "lemonchiffon",
+        "lightblue", "lightcoral", "lightcyan", "lightgoldenrodyellow", "lightgray",
+        "lightgreen", "lightgrey", "lightpink", "lightsalmon", "lightseagreen", "lightskyblue",
+        "lightslategray", "lightslategrey", "lightsteelblue", "lightyellow", "lime",
+        "limegreen", "linen", "magenta", "maroon", "mediumaquamarine", "mediumblue",
+        "mediumorchid", "mediumpurple", "mediumseagreen", "mediumslateblue",
+        "mediumspringgreen", "mediumturquoise", "mediumvioletred", "midnightblue", "mintcream",
+        "mistyrose", "moccasin", "navajowhite", "navy", "oldlace", "olive", "olivedrab",
+        "orange", "orangered", "orchid", "palegoldenrod", "palegreen", "paleturquoise",
+        "palevioletred", "papayawhip", "peachpuff", "peru", "pink", "plum", "powderblue",
+        "purple", "rebeccapurple", "red", "rosybrown", "royalblue", "saddlebrown", "salmon",
+        "sandybrown", "seagreen", "seashell", "sienna", "silver", "skyblue", "slateblue",
+        "slategray", "slategrey", "snow", "springgreen", "steelblue", "tan", "teal", "thistle",
+        "tomato", "turquoise", "violet", "wheat", "white", "whitesmoke", "yellow",
+        "yellowgreen",
+    ]
+    .iter()
+    .copied()
+    .collect();
+}
+
+/// CSS hex colors (`#RGB`, `#RGBA`, `#RRGGBB` or `#RRGGBBAA`) or named colors, e.g. `teal`.
+/// Function notations like `rgb()` are not supported.
+fn is_color(item: &str) -> bool {
+    if let Some(hex) = item.strip_prefix('#') {
+        matches!(hex.len(), 3 | 4 | 6 | 8) && hex.bytes().all(|byte| byte.is_ascii_hexdigit())
+    } else {
+        CSS_NAMED_COLORS.contains(item.to_ascii_lowercase().as_str())
+    }
+}
+format_validator!(ColorValidator, "color");
+impl Validate for ColorValidator {
+    validate!("color");
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::String(item) = instance {
+            is_color(item)
+        } else {
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests_util;
+    use serde_json::json;
+    use test_case::test_case;
+
+    #[test_case("#fff")]
+    #[test_case("#FFFA")]
+    #[test_case("#00ff7f")]
+    #[test_case("#00ff7f80")]
+    #[test_case("rebeccapurple")]
+    #[test_case("Teal")]
+    fn color_valid(instance: &str) {
+        tests_util::is_valid(&json!({"format": "color"}), &json!(instance))
+    }
+
+    #[test_case("#ff")]
+    #[test_case("#fffff")]
+    #[test_case("#ggg")]
+    #[test_case("fff")]
+    #[test_case("rgb(0, 0, 0)")]
+    #[test_case("notacolor")]
+    fn color_invalid(instance: &str) {
+        tests_util::is_not_valid(&json!({"format": "color"}), &json!(instance))
+    }
+}
@@ -0,0 +1,105 @@
+//! `date`, `date-time`, `time` and `partial-time` formats.
+use crate::{compilation::JSONSchema, validation_context::ValidationContext, validator::Validate};
+use chrono::{DateTime, NaiveDate};
+use regex::Regex;
+use serde_json::Value;
+
+lazy_static::lazy_static! {
+    static ref DATE_RE: Regex =
+        Regex::new(r"^[0-9]{4}-[0-9]{2}-[0-9]{2}\z").expect("Is a valid regex");
+    static ref PARTIAL_TIME_RE: Regex =
+        Regex::new(r"^(([01][0-9]|2[0-3]):[0-5][0-9]:[0-5][0-9]|23:59:60)(\.[0-9]+)?\z").expect("Is a valid regex");
+    static ref TIME_RE: Regex =
+        Regex::new(
+        r"^([01][0-9]|2[0-3]):([0-5][0-9]):([0-5][0-9])(\.[0-9]{6})?(([Zz])|([+|\-]([01][0-9]|2[0-3]):[0-5][0-9]))\z",
+    ).expect("Is a valid regex");
+}
+
+format_validator!(DateValidator, "date");
+impl Validate for DateValidator {
+    validate!("date");
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::String(item) = instance {
+            if NaiveDate::parse_from_str(item, "%Y-%m-%d").is_ok() {
+                // Padding with zeroes is ignored by the underlying parser. The most efficient
+                // way to check it will be to use a custom parser that won't ignore zeroes,
+                // but this regex will do the trick and costs ~20% extra time in this validator.
+                DATE_RE.is_match(item.as_str())
+            } else {
+                false
+            }
+        } else {
+            true
+        }
+    }
+}
+
+format_validator!(DateTimeValidator, "date-time");
+impl Validate for DateTimeValidator {
+    validate!("date-time");
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::String(item) = instance {
+            DateTime::parse_from_rfc3339(item).is_ok()
+        } else {
+            true
+        }
+    }
+}
+
+format_validator!(TimeValidator, "time");
+impl Validate for TimeValidator {
+    validate!("time");
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::String(item) = instance {
+            TIME_RE.is_match(item)
+        } else {
+            true
+        }
+    }
+}
+
+// `partial-time` from RFC 3339 is `time` without the zone: "12:30:00" or "12:30:00.25" are
+// valid, but "12:30:00Z" or "12:30:00+02:00" are not. Without an offset the time is in UTC,
+// so a leap second is only allowed at "23:59:60".
+format_validator!(PartialTimeValidator, "partial-time");
+impl Validate for PartialTimeValidator {
+    validate!("partial-time");
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::String(item) = instance {
+            PARTIAL_TIME_RE.is_match(item)
+        } else {
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests_util;
+    use serde_json::json;
+    use test_case::test_case;
+
+    #[test_case("12:30:00")]
+    #[test_case("23:59:60"; "leap second")]
+    #[test_case("23:59:60.5"; "fractional leap second")]
+    #[test_case("00:00:00.5")]
+    #[test_case("12:30:00.123456789")]
+    fn partial_time_valid(instance: &str) {
+        tests_util::is_valid(&json!({"format": "partial-time"}), &json!(instance))
+    }
+
+    #[test_case("12:30:00Z")]
+    #[test_case("12:30:00+02:00")]
+    #[test_case("12:30:00.5-05:00")]
+    #[test_case("24:00:00")]
+    #[test_case("12:30")]
+    #[test_case("12:30:00.")]
+    #[test_case("12:30:60"; "leap second at another time")]
+    #[test_case("00:00:60"; "leap second at midnight")]
+    #[test_case("23:58:60"; "leap second at another minute")]
+    #[test_case("22:59:60"; "leap second at another hour")]
+    #[test_case("23:59:61")]
+    fn partial_time_invalid(instance: &str) {
+        tests_util::is_not_valid(&json!({"format": "partial-time"}), &json!(instance))
+    }
+}
@@ -0,0 +1,27 @@
+//! `email` and `idn-email` formats.
+use crate::{compilation::JSONSchema, validation_context::ValidationContext, validator::Validate};
+use serde_json::Value;
+
+format_validator!(EmailValidator, "email");
+impl Validate for EmailValidator {
+    validate!("email");
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::String(item) = instance {
+            item.contains('@')
+        } else {
+            true
+        }
+    }
+}
+
+format_validator!(IDNEmailValidator, "idn-email");
+impl Validate for IDNEmailValidator {
+    validate!("idn-email");
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::String(item) = instance {
+            item.contains('@')
+        } else {
+            true
+        }
+    }
+}
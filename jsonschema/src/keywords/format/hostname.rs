@@ -0,0 +1,41 @@
+//! `hostname` and `idn-hostname` formats.
+use crate::{compilation::JSONSchema, validation_context::ValidationContext, validator::Validate};
+use serde_json::Value;
+
+format_validator!(HostnameValidator, "hostname");
+impl Validate for HostnameValidator {
+    validate!("hostname");
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::String(item) = instance {
+            !(item.ends_with('-')
+                || item.starts_with('-')
+                || item.is_empty()
+                || item.chars().count() > 255
+                || item
+                    .chars()
+                    .any(|c| !(c.is_alphanumeric() || c == '-' || c == '.'))
+                || item.split('.').any(|part| part.chars().count() > 63))
+        } else {
+            true
+        }
+    }
+}
+
+format_validator!(IDNHostnameValidator, "idn-hostname");
+impl Validate for IDNHostnameValidator {
+    validate!("idn-hostname");
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::String(item) = instance {
+            !(item.ends_with('-')
+                || item.starts_with('-')
+                || item.is_empty()
+                || item.chars().count() > 255
+                || item
+                    .chars()
+                    .any(|c| !(c.is_alphanumeric() || c == '-' || c == '.'))
+                || item.split('.').any(|part| part.chars().count() > 63))
+        } else {
+            true
+        }
+    }
+}
@@ -0,0 +1,34 @@
+//! `ipv4` and `ipv6` formats.
+use crate::{compilation::JSONSchema, validation_context::ValidationContext, validator::Validate};
+use serde_json::Value;
+use std::{net::IpAddr, str::FromStr};
+
+format_validator!(IpV4Validator, "ipv4");
+impl Validate for IpV4Validator {
+    validate!("ipv4");
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::String(item) = instance {
+            match IpAddr::from_str(item.as_str()) {
+                Ok(i) => i.is_ipv4(),
+                Err(_) => false,
+            }
+        } else {
+            true
+        }
+    }
+}
+
+format_validator!(IpV6Validator, "ipv6");
+impl Validate for IpV6Validator {
+    validate!("ipv6");
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::String(item) = instance {
+            match IpAddr::from_str(item.as_str()) {
+                Ok(i) => i.is_ipv6(),
+                Err(_) => false,
+            }
+        } else {
+            true
+        }
+    }
+}
@@ -0,0 +1,34 @@
+//! `json-pointer` and `relative-json-pointer` formats.
+use crate::{compilation::JSONSchema, validation_context::ValidationContext, validator::Validate};
+use regex::Regex;
+use serde_json::Value;
+
+lazy_static::lazy_static! {
+    static ref JSON_POINTER_RE: Regex = Regex::new(r"^(/(([^/~])|(~[01]))*)*\z").expect("Is a valid regex");
+    static ref RELATIVE_JSON_POINTER_RE: Regex =
+        Regex::new(r"^(?:0|[1-9][0-9]*)(?:#|(?:/(?:[^~/]|~0|~1)*)*)\z").expect("Is a valid regex");
+}
+
+format_validator!(JSONPointerValidator, "json-pointer");
+impl Validate for JSONPointerValidator {
+    validate!("json-pointer");
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::String(item) = instance {
+            JSON_POINTER_RE.is_match(item)
+        } else {
+            true
+        }
+    }
+}
+
+format_validator!(RelativeJSONPointerValidator, "relative-json-pointer");
+impl Validate for RelativeJSONPointerValidator {
+    validate!("relative-json-pointer");
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::String(item) = instance {
+            RELATIVE_JSON_POINTER_RE.is_match(item)
+        } else {
+            true
+        }
+    }
+}
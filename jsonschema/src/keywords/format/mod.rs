@@ -0,0 +1,343 @@
+//! Validator for `format` keyword.
+//!
+//! Each built-in format lives in its own module and is registered in `BUILT_IN_FORMATS`.
+use crate::{
+    compilation::{context::CompilationContext, JSONSchema},
+    error::{error, no_error, CompilationError, ErrorIterator, ValidationError},
+    format_registry::FormatCheckType,
+    keywords::CompilationResult,
+    paths::JSONPointer,
+    validation_context::ValidationContext,
+    validator::Validate,
+    Draft,
+};
+use ahash::AHashMap;
+use serde_json::{Map, Value};
+use std::fmt;
+
+macro_rules! format_validator {
+    ($validator:ident, $format_name:tt) => {
+        pub(crate) struct $validator {
+            schema_path: $crate::paths::JSONPointer,
+        }
+        impl $validator {
+            pub(crate) fn compile(
+                schema_path: $crate::paths::JSONPointer,
+            ) -> $crate::keywords::CompilationResult {
+                Ok(Box::new($validator { schema_path }))
+            }
+        }
+
+        impl std::fmt::Display for $validator {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(concat!("format: ", $format_name))
+            }
+        }
+    };
+}
+
+macro_rules! validate {
+    ($format:expr) => {
+        fn validate<'a>(
+            &self,
+            schema: &'a $crate::compilation::JSONSchema,
+            instance: &'a serde_json::Value,
+            instance_path: &$crate::paths::JSONPointer,
+            ctx: &'a $crate::validation_context::ValidationContext,
+        ) -> $crate::error::ErrorIterator<'a> {
+            if let serde_json::Value::String(_item) = instance {
+                if !self.is_valid(schema, instance, ctx) {
+                    return $crate::error::error($crate::error::ValidationError::format(
+                        self.schema_path.clone(),
+                        instance_path.clone(),
+                        instance,
+                        $format,
+                    ));
+                }
+            }
+            $crate::error::no_error()
+        }
+    };
+}
+
+mod base64;
+#[cfg(feature = "custom-formats")]
+mod color;
+mod date_time;
+mod email;
+mod hostname;
+mod ip;
+mod json_pointer;
+#[cfg(feature = "custom-formats")]
+mod numeric;
+mod regex_;
+#[cfg(feature = "custom-formats")]
+mod semver;
+mod uri;
+
+/// Compiles a validator for a built-in format.
+pub(crate) type FormatFactory = fn(JSONPointer) -> CompilationResult;
+
+/// A built-in format and the first draft that defines it.
+pub(crate) struct BuiltInFormat {
+    since: Draft,
+    compile: FormatFactory,
+}
+
+impl BuiltInFormat {
+    fn new(since: Draft, compile: FormatFactory) -> Self {
+        BuiltInFormat { since, compile }
+    }
+
+    /// Whether the format is checked in `draft`. Formats defined in later drafts are ignored.
+    fn is_defined_in(&self, draft: Draft) -> bool {
+        match self.since {
+            Draft::Draft4 => true,
+            Draft::Draft6 => draft != Draft::Draft4,
+            Draft::Draft7 | Draft::Draft201909 => {
+                matches!(draft, Draft::Draft7 | Draft::Draft201909)
+            }
+        }
+    }
+}
+
+lazy_static::lazy_static! {
+    pub(crate) static ref BUILT_IN_FORMATS: AHashMap<&'static str, BuiltInFormat> = {
+        let mut formats = AHashMap::with_capacity(29);
+        formats.insert("base64", BuiltInFormat::new(Draft::Draft4, base64::Base64Validator::compile));
+        formats.insert("base64url", BuiltInFormat::new(Draft::Draft4, base64::Base64UrlValidator::compile));
+        formats.insert("byte", BuiltInFormat::new(Draft::Draft4, base64::ByteValidator::compile));
+        #[cfg(feature = "custom-formats")]
+        formats.insert("color", BuiltInFormat::new(Draft::Draft4, color::ColorValidator::compile));
+        formats.insert("date", BuiltInFormat::new(Draft::Draft4, date_time::DateValidator::compile));
+        formats.insert("date-time", BuiltInFormat::new(Draft::Draft4, date_time::DateTimeValidator::compile));
+        #[cfg(feature = "custom-formats")]
+        formats.insert("double", BuiltInFormat::new(Draft::Draft4, numeric::DoubleValidator::compile));
+        formats.insert("email", BuiltInFormat::new(Draft::Draft4, email::EmailValidator::compile));
+        #[cfg(feature = "custom-formats")]
+        formats.insert("float", BuiltInFormat::new(Draft::Draft4, numeric::FloatValidator::compile));
+        formats.insert("hostname", BuiltInFormat::new(Draft::Draft4, hostname::HostnameValidator::compile));
+        formats.insert("idn-email", BuiltInFormat::new(Draft::Draft4, email::IDNEmailValidator::compile));
+        formats.insert("idn-hostname", BuiltInFormat::new(Draft::Draft7, hostname::IDNHostnameValidator::compile));
+        #[cfg(feature = "custom-formats")]
+        formats.insert("int32", BuiltInFormat::new(Draft::Draft4, numeric::Int32Validator::compile));
+        #[cfg(feature = "custom-formats")]
+        formats.insert("int64", BuiltInFormat::new(Draft::Draft4, numeric::Int64Validator::compile));
+        formats.insert("ipv4", BuiltInFormat::new(Draft::Draft4, ip::IpV4Validator::compile));
+        formats.insert("ipv6", BuiltInFormat::new(Draft::Draft4, ip::IpV6Validator::compile));
+        formats.insert("iri", BuiltInFormat::new(Draft::Draft7, uri::IRIValidator::compile));
+        formats.insert("iri-reference", BuiltInFormat::new(Draft::Draft7, uri::IRIReferenceValidator::compile));
+        formats.insert("json-pointer", BuiltInFormat::new(Draft::Draft6, json_pointer::JSONPointerValidator::compile));
+        formats.insert("partial-time", BuiltInFormat::new(Draft::Draft4, date_time::PartialTimeValidator::compile));
+        formats.insert("regex", BuiltInFormat::new(Draft::Draft4, regex_::RegexValidator::compile));
+        formats.insert("relative-json-pointer", BuiltInFormat::new(Draft::Draft7, json_pointer::RelativeJSONPointerValidator::compile));
+        #[cfg(feature = "custom-formats")]
+        formats.insert("semver", BuiltInFormat::new(Draft::Draft4, semver::SemverValidator::compile));
+        formats.insert("time", BuiltInFormat::new(Draft::Draft4, date_time::TimeValidator::compile));
+        formats.insert("uri", BuiltInFormat::new(Draft::Draft4, uri::URIValidator::compile));
+        formats.insert("uri-reference", BuiltInFormat::new(Draft::Draft6, uri::URIReferenceValidator::compile));
+        formats.insert("uri-template", BuiltInFormat::new(Draft::Draft6, uri::URITemplateValidator::compile));
+        formats
+    };
+}
+
+/// A format check provided by the user.
+struct CustomFormatValidator {
+    format: &'static str,
+    check: FormatCheckType,
+    schema_path: JSONPointer,
+}
+impl CustomFormatValidator {
+    pub(crate) fn compile(
+        format: &'static str,
+        check: FormatCheckType,
+        schema_path: JSONPointer,
+    ) -> CompilationResult {
+        Ok(Box::new(CustomFormatValidator {
+            format,
+            check,
+            schema_path,
+        }))
+    }
+}
+impl fmt::Display for CustomFormatValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "format: {}", self.format)
+    }
+}
+impl Validate for CustomFormatValidator {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
+            no_error()
+        } else {
+            error(ValidationError::format(
+                self.schema_path.clone(),
+                instance_path.clone(),
+                instance,
+                self.format,
+            ))
+        }
+    }
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::String(item) = instance {
+            (self.check)(item)
+        } else {
+            true
+        }
+    }
+}
+
+#[inline]
+pub(crate) fn compile(
+    _: &Map<String, Value>,
+    schema: &Value,
+    context: &CompilationContext,
+) -> Option<CompilationResult> {
+    if let Value::String(format) = schema {
+        if context.config.is_format_disabled(format) {
+            return None;
+        }
+        if let Some((format, check)) = context.config.format_check(format) {
+            return Some(CustomFormatValidator::compile(
+                format,
+                check,
+                context.schema_path.clone(),
+            ));
+        }
+        match BUILT_IN_FORMATS.get(format.as_str()) {
+            Some(built_in) if built_in.is_defined_in(context.config.draft()) => {
+                Some((built_in.compile)(context.schema_path.clone()))
+            }
+            _ => None,
+        }
+    } else {
+        Some(Err(CompilationError::SchemaError))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        compilation::{options::CompilationOptions, JSONSchema},
+        format_registry::FormatRegistry,
+        schemas::Draft,
+    };
+    use serde_json::json;
+    use test_case::test_case;
+
+    #[test]
+    fn ignored_format() {
+        let schema = json!({"format": "custom", "type": "string"});
+        let instance = json!("foo");
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        assert!(compiled.is_valid(&instance))
+    }
+
+    fn only_foo(item: &str) -> bool {
+        item == "foo"
+    }
+
+    fn only_bar(item: &str) -> bool {
+        item == "bar"
+    }
+
+    #[test]
+    fn custom_format() {
+        let schema = json!({"format": "custom"});
+        let compiled = CompilationOptions::default()
+            .with_format("custom", only_foo)
+            .compile(&schema)
+            .unwrap();
+        assert!(compiled.is_valid(&json!("foo")));
+        assert!(compiled.is_valid(&json!(42)));
+        let instance = json!("bar");
+        let errors: Vec<_> = compiled.validate(&instance).unwrap_err().collect();
+        assert_eq!(
+            errors[0].to_string(),
+            r#"/format: '"bar"' is not a 'custom'"#
+        );
+    }
+
+    #[test]
+    fn custom_format_overrides_built_in() {
+        let schema = json!({"format": "email"});
+        let compiled = CompilationOptions::default()
+            .with_format("email", only_foo)
+            .compile(&schema)
+            .unwrap();
+        assert!(compiled.is_valid(&json!("foo")));
+        assert!(!compiled.is_valid(&json!("foo@example.com")));
+    }
+
+    #[test_case("email", "foo"; "built-in")]
+    #[test_case("custom", "bar"; "custom")]
+    fn disabled_format(format: &str, instance: &str) {
+        let schema = json!({"properties": {"a": {"format": format}, "b": {"format": "ipv4"}}});
+        let compiled = CompilationOptions::default()
+            .with_format("custom", only_foo)
+            .disable_format(format)
+            .compile(&schema)
+            .unwrap();
+        assert!(compiled.is_valid(&json!({ "a": instance })));
+        // Other formats are still validated
+        assert!(!compiled.is_valid(&json!({"b": "foo"})));
+    }
+
+    #[test]
+    fn thread_local_format_takes_precedence() {
+        let schema = json!({"format": "custom"});
+        let mut options = CompilationOptions::default();
+        options.with_format("custom", only_foo);
+        let registry = FormatRegistry::current();
+        assert!(registry.insert("custom", only_bar).is_none());
+        let compiled = options.compile(&schema).unwrap();
+        assert!(compiled.is_valid(&json!("bar")));
+        assert!(!compiled.is_valid(&json!("foo")));
+        // Not visible from other threads
+        std::thread::spawn(|| assert!(FormatRegistry::current().get("custom").is_none()))
+            .join()
+            .unwrap();
+        registry.clear();
+        let compiled = options.compile(&schema).unwrap();
+        assert!(compiled.is_valid(&json!("foo")));
+    }
+
+    #[test]
+    fn thread_local_format_is_captured_by_lazy_compilation() {
+        let schema = json!({"properties": {"a": {"format": "custom"}}});
+        let registry = FormatRegistry::current();
+        registry.insert("custom", only_bar);
+        let compiled = CompilationOptions::default()
+            .with_lazy_compilation()
+            .compile_owned(schema)
+            .unwrap();
+        registry.clear();
+        // The sub-schema is compiled on another thread, which has no checks installed
+        std::thread::spawn(move || {
+            assert!(compiled.is_valid(&json!({"a": "bar"})));
+            assert!(!compiled.is_valid(&json!({"a": "foo"})));
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test_case("iri", Draft::Draft6, false)]
+    #[test_case("iri", Draft::Draft7, true)]
+    #[test_case("json-pointer", Draft::Draft4, false)]
+    #[test_case("json-pointer", Draft::Draft6, true)]
+    #[test_case("email", Draft::Draft4, true)]
+    fn built_in_format_drafts(format: &str, draft: Draft, is_checked: bool) {
+        let schema = json!({ "format": format });
+        let compiled = CompilationOptions::default()
+            .with_draft(draft)
+            .compile(&schema)
+            .unwrap();
+        assert_eq!(compiled.is_valid(&json!("\\not valid")), !is_checked)
+    }
+}
@@ -0,0 +1,109 @@
+//! `OpenAPI` numeric formats - `int32`, `int64`, `float` and `double`, available with the
+//! `custom-formats` feature. Unlike other formats, they apply to numbers, not strings.
+use crate::{compilation::JSONSchema, validation_context::ValidationContext, validator::Validate};
+use serde_json::{Number, Value};
+
+macro_rules! number_format {
+    ($validator:ident, $format_name:tt, $check:ident) => {
+        format_validator!($validator, $format_name);
+        impl Validate for $validator {
+            fn validate<'a>(
+                &self,
+                schema: &'a JSONSchema,
+                instance: &'a Value,
+                instance_path: &crate::paths::JSONPointer,
+                ctx: &'a ValidationContext,
+            ) -> crate::error::ErrorIterator<'a> {
+                if self.is_valid(schema, instance, ctx) {
+                    crate::error::no_error()
+                } else {
+                    crate::error::error(crate::error::ValidationError::format(
+                        self.schema_path.clone(),
+                        instance_path.clone(),
+                        instance,
+                        $format_name,
+                    ))
+                }
+            }
+            fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+                if let Value::Number(item) = instance {
+                    $check(item)
+                } else {
+                    true
+                }
+            }
+        }
+    };
+}
+
+/// Whether the number is within `[min, max]`. Only the range is checked, whether the number is an
+/// integer is up to the `type` keyword.
+fn is_within(item: &Number, min: i64, max: i64) -> bool {
+    if let Some(item) = item.as_i64() {
+        min <= item && item <= max
+    } else if item.is_u64() {
+        // Bigger than `i64::MAX`
+        false
+    } else {
+        let item = item.as_f64().expect("Always valid");
+        // `max + 1` is exactly representable for both `i32::MAX` and `i64::MAX`
+        min as f64 <= item && item < max as f64 + 1.0
+    }
+}
+
+/// Signed 32-bit integer, `[-2^31, 2^31 - 1]`.
+fn is_int32(item: &Number) -> bool {
+    is_within(item, i64::from(i32::MIN), i64::from(i32::MAX))
+}
+
+/// Signed 64-bit integer, `[-2^63, 2^63 - 1]`.
+fn is_int64(item: &Number) -> bool {
+    is_within(item, i64::MIN, i64::MAX)
+}
+
+/// Within the range of a single precision float.
+fn is_float(item: &Number) -> bool {
+    let item = item.as_f64().expect("Always valid");
+    item.abs() <= f64::from(f32::MAX)
+}
+
+/// Any JSON number is a valid double.
+fn is_double(_: &Number) -> bool {
+    true
+}
+
+number_format!(Int32Validator, "int32", is_int32);
+number_format!(Int64Validator, "int64", is_int64);
+number_format!(FloatValidator, "float", is_float);
+number_format!(DoubleValidator, "double", is_double);
+
+#[cfg(test)]
+mod tests {
+    use crate::tests_util;
+    use serde_json::{json, Value};
+    use test_case::test_case;
+
+    #[test_case("int32", &json!(2_147_483_647))]
+    #[test_case("int32", &json!(-2_147_483_648))]
+    #[test_case("int32", &json!(1.0); "int32 float")]
+    #[test_case("int64", &json!(9_223_372_036_854_775_807_i64))]
+    #[test_case("int64", &json!(-9_223_372_036_854_775_808_i64))]
+    #[test_case("float", &json!(3.4e38))]
+    #[test_case("float", &json!(-3.4e38); "negative float")]
+    #[test_case("double", &json!(1.7e308))]
+    #[test_case("int32", &json!("2147483648"); "not a number")]
+    fn valid(format: &str, instance: &Value) {
+        tests_util::is_valid(&json!({ "format": format }), instance)
+    }
+
+    #[test_case("int32", &json!(2_147_483_648_i64))]
+    #[test_case("int32", &json!(-2_147_483_649_i64))]
+    #[test_case("int32", &json!(2_147_483_648.0); "int32 float")]
+    #[test_case("int64", &json!(9_223_372_036_854_775_808_u64))]
+    #[test_case("int64", &json!(9.3e18))]
+    #[test_case("float", &json!(3.5e38))]
+    #[test_case("float", &json!(-3.5e38); "negative float")]
+    fn invalid(format: &str, instance: &Value) {
+        tests_util::is_not_valid(&json!({ "format": format }), instance)
+    }
+}
@@ -0,0 +1,16 @@
+//! `regex` format.
+use crate::{compilation::JSONSchema, validation_context::ValidationContext, validator::Validate};
+use regex::Regex;
+use serde_json::Value;
+
+format_validator!(RegexValidator, "regex");
+impl Validate for RegexValidator {
+    validate!("regex");
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::String(item) = instance {
+            Regex::new(item).is_ok()
+        } else {
+            true
+        }
+    }
+}
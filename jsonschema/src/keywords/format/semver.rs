@@ -0,0 +1,52 @@
+//! `semver` format, available with the `custom-formats` feature.
+use crate::{compilation::JSONSchema, validation_context::ValidationContext, validator::Validate};
+use regex::Regex;
+use serde_json::Value;
+
+lazy_static::lazy_static! {
+    // The regex suggested by Semantic Versioning 2.0.0 with ASCII digits only
+    static ref SEMVER_RE: Regex = Regex::new(
+        r"^(0|[1-9][0-9]*)\.(0|[1-9][0-9]*)\.(0|[1-9][0-9]*)(?:-((?:0|[1-9][0-9]*|[0-9]*[a-zA-Z-][0-9a-zA-Z-]*)(?:\.(?:0|[1-9][0-9]*|[0-9]*[a-zA-Z-][0-9a-zA-Z-]*))*))?(?:\+([0-9a-zA-Z-]+(?:\.[0-9a-zA-Z-]+)*))?\z",
+    )
+    .expect("Is a valid regex");
+}
+
+format_validator!(SemverValidator, "semver");
+impl Validate for SemverValidator {
+    validate!("semver");
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::String(item) = instance {
+            SEMVER_RE.is_match(item)
+        } else {
+            true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests_util;
+    use serde_json::json;
+    use test_case::test_case;
+
+    #[test_case("1.2.3")]
+    #[test_case("0.0.0")]
+    #[test_case("1.2.3-alpha.1+build.001")]
+    #[test_case("1.0.0-0.3.7")]
+    #[test_case("1.0.0-x-y-z.--")]
+    #[test_case("1.0.0+20130313144700")]
+    fn semver_valid(instance: &str) {
+        tests_util::is_valid(&json!({"format": "semver"}), &json!(instance))
+    }
+
+    #[test_case("1.2")]
+    #[test_case("01.2.3")]
+    #[test_case("1.2.3-01")]
+    #[test_case("1.2.3-alpha..1")]
+    #[test_case("1.2.3+build+1")]
+    #[test_case("v1.2.3")]
+    #[test_case("1.2.3\n")]
+    fn semver_invalid(instance: &str) {
+        tests_util::is_not_valid(&json!({"format": "semver"}), &json!(instance))
+    }
+}
@@ -0,0 +1,77 @@
+//! `uri`, `uri-reference`, `uri-template`, `iri` and `iri-reference` formats.
+use crate::{compilation::JSONSchema, validation_context::ValidationContext, validator::Validate};
+use regex::Regex;
+use serde_json::Value;
+use std::str::FromStr;
+use url::Url;
+
+lazy_static::lazy_static! {
+    static ref IRI_REFERENCE_RE: Regex =
+        Regex::new(r"^(\w+:(/?/?))?[^#\\\s]*(#[^\\\s]*)?\z").expect("Is a valid regex");
+    static ref URI_REFERENCE_RE: Regex =
+        Regex::new(r"^(\w+:(/?/?))?[^#\\\s]*(#[^\\\s]*)?\z").expect("Is a valid regex");
+    static ref URI_TEMPLATE_RE: Regex = Regex::new(
+        r#"^(?:(?:[^\x00-\x20"'<>%\\^`{|}]|%[0-9a-f]{2})|\{[+#./;?&=,!@|]?(?:[a-z0-9_]|%[0-9a-f]{2})+(?::[1-9][0-9]{0,3}|\*)?(?:,(?:[a-z0-9_]|%[0-9a-f]{2})+(?::[1-9][0-9]{0,3}|\*)?)*})*\z"#
+    )
+    .expect("Is a valid regex");
+}
+
+format_validator!(URIValidator, "uri");
+impl Validate for URIValidator {
+    validate!("uri");
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::String(item) = instance {
+            Url::from_str(item).is_ok()
+        } else {
+            true
+        }
+    }
+}
+
+format_validator!(URIReferenceValidator, "uri-reference");
+impl Validate for URIReferenceValidator {
+    validate!("uri-reference");
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::String(item) = instance {
+            URI_REFERENCE_RE.is_match(item)
+        } else {
+            true
+        }
+    }
+}
+
+format_validator!(URITemplateValidator, "uri-template");
+impl Validate for URITemplateValidator {
+    validate!("uri-template");
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::String(item) = instance {
+            URI_TEMPLATE_RE.is_match(item)
+        } else {
+            true
+        }
+    }
+}
+
+format_validator!(IRIValidator, "iri");
+impl Validate for IRIValidator {
+    validate!("iri");
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::String(item) = instance {
+            Url::from_str(item).is_ok()
+        } else {
+            true
+        }
+    }
+}
+
+format_validator!(IRIReferenceValidator, "iri-reference");
+impl Validate for IRIReferenceValidator {
+    validate!("iri-reference");
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::String(item) = instance {
+            IRI_REFERENCE_RE.is_match(item)
+        } else {
+            true
+        }
+    }
+}
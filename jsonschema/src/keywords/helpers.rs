@@ -1,5 +1,16 @@
+//! JSON value equality as defined by JSON Schema, e.g. for `const`, `enum` or `uniqueItems`.
+//! Useful for custom keywords that compare instances.
+use crate::{
+    compilation::context::CompilationContext, content_encoding::ContentEncodingConverterType,
+    error::CompilationError, schemas::Draft,
+};
+use ahash::AHasher;
 use num_cmp::NumCmp;
-use serde_json::{Map, Value};
+use serde_json::{Map, Number, Value};
+use std::{
+    convert::TryFrom,
+    hash::{Hash, Hasher},
+};
 
 macro_rules! num_cmp {
     ($left:expr, $right:expr) => {
@@ -13,8 +24,18 @@ macro_rules! num_cmp {
     };
 }
 
+/// Whether two JSON values are equal. Unlike `==`, numbers are compared by their mathematical
+/// value, therefore `1` and `1.0` are equal, including within arrays and objects.
+///
+/// ```rust
+/// # use jsonschema::helpers::equal;
+/// # use serde_json::json;
+/// assert!(equal(&json!({"a": [1, 2]}), &json!({"a": [1.0, 2.0]})));
+/// assert!(!equal(&json!(1), &json!("1")));
+/// ```
+#[must_use]
 #[inline]
-pub(crate) fn equal(left: &Value, right: &Value) -> bool {
+pub fn equal(left: &Value, right: &Value) -> bool {
     match (left, right) {
         (Value::Number(left), Value::Number(right)) => {
             if let Some(a) = left.as_u64() {
@@ -32,24 +53,151 @@ pub(crate) fn equal(left: &Value, right: &Value) -> bool {
     }
 }
 
+/// Whether two arrays have equal items in the same order. See `equal`.
+#[must_use]
 #[inline]
-pub(crate) fn equal_arrays(left: &[Value], right: &[Value]) -> bool {
+pub fn equal_arrays(left: &[Value], right: &[Value]) -> bool {
     left.len() == right.len() && left.iter().zip(right.iter()).all(|(a, b)| equal(a, b))
 }
 
+/// Whether two objects have the same keys with equal values. See `equal`.
+/// The order of keys does not matter, even if `serde_json/preserve_order` is enabled.
+#[must_use]
 #[inline]
-pub(crate) fn equal_objects(left: &Map<String, Value>, right: &Map<String, Value>) -> bool {
+pub fn equal_objects(left: &Map<String, Value>, right: &Map<String, Value>) -> bool {
     left.len() == right.len()
         && left
             .iter()
-            .zip(right)
-            .all(|((ka, va), (kb, vb))| ka == kb && equal(va, vb))
+            .all(|(key, value)| matches!(right.get(key), Some(other) if equal(value, other)))
+}
+
+/// A value that could be stored in hash sets. Values that are equal according to `equal` have
+/// the same hash: integer-valued floats are hashed as integers and the order of object keys
+/// does not affect the hash.
+// Based on implementation proposed by Sven Marnach:
+// https://stackoverflow.com/questions/60882381/what-is-the-fastest-correct-way-to-detect-that-there-are-no-duplicates-in-a-json
+pub(crate) struct HashedValue<'a>(pub(crate) &'a Value);
+
+impl PartialEq for HashedValue<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        equal(self.0, other.0)
+    }
+}
+
+impl Eq for HashedValue<'_> {}
+
+impl Hash for HashedValue<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self.0 {
+            Value::Null => state.write_u32(3_221_225_473), // chosen randomly
+            Value::Bool(ref item) => item.hash(state),
+            Value::Number(ref item) => {
+                if let Some(number) = item.as_u64() {
+                    i128::from(number).hash(state);
+                } else if let Some(number) = item.as_i64() {
+                    i128::from(number).hash(state);
+                } else {
+                    let number = item.as_f64().expect("Always valid");
+                    // Larger floats can not be equal to any `u64` or `i64` value
+                    if number.fract() == 0. && number.abs() < 18_446_744_073_709_551_616. {
+                        #[allow(clippy::cast_possible_truncation)]
+                        (number as i128).hash(state);
+                    } else {
+                        number.to_bits().hash(state)
+                    }
+                }
+            }
+            Value::String(ref item) => item.hash(state),
+            Value::Array(ref items) => {
+                for item in items {
+                    HashedValue(item).hash(state);
+                }
+            }
+            Value::Object(ref items) => {
+                let mut hash = 0;
+                for (key, value) in items {
+                    // We have no way of building a new hasher of type `H`, so we
+                    // hardcode using the default hasher of a hash map.
+                    let mut item_hasher = AHasher::default();
+                    key.hash(&mut item_hasher);
+                    HashedValue(value).hash(&mut item_hasher);
+                    hash ^= item_hasher.finish();
+                }
+                state.write_u64(hash);
+            }
+        }
+    }
+}
+
+/// Parse a limit of keywords like `maxItems` or `minLength`.
+/// It is a non-negative integer that should fit into `usize`, since it is compared with lengths
+/// of arrays, objects and strings.
+#[inline]
+pub(crate) fn limit(schema: &Value) -> Result<u64, CompilationError> {
+    match schema.as_u64() {
+        Some(limit) if usize::try_from(limit).is_ok() => Ok(limit),
+        _ => Err(CompilationError::SchemaError),
+    }
+}
+
+/// Whether the number is an integer for the `type` keyword since Draft 6.
+/// Integer-valued floats (e.g. `1.0`) are integers. Big floats like `1e300` are integers too,
+/// as they have no fractional part, even though they don't fit into `i64` / `u64`.
+///
+/// Draft 4 defines integers as numbers without a fraction or exponent part, therefore `1.0` is
+/// not an integer there, see `is_integer_literal`.
+#[inline]
+pub(crate) fn is_integer_valued(num: &Number) -> bool {
+    is_integer_literal(num) || num.as_f64().expect("Always valid").fract() == 0.
+}
+
+/// Whether the number is an integer for the `type` keyword in Draft 4, i.e. it is written
+/// without a fraction or exponent part.
+#[inline]
+pub(crate) fn is_integer_literal(num: &Number) -> bool {
+    num.is_u64() || num.is_i64()
+}
+
+/// Converter for the `contentEncoding` keyword next to `minLength` or `maxLength`.
+/// If it is present and supported, the length limits apply to the decoded content.
+#[inline]
+pub(crate) fn content_encoding_converter(
+    parent: &Map<String, Value>,
+    context: &CompilationContext,
+) -> Option<ContentEncodingConverterType> {
+    if context.config.draft() == Draft::Draft4
+        || !context.config.is_keyword_enabled("contentEncoding")
+    {
+        return None;
+    }
+    match parent.get("contentEncoding") {
+        Some(Value::String(encoding)) => context.config.content_encoding_convert(encoding),
+        _ => None,
+    }
+}
+
+/// Length of a string in characters, or the length of its decoded content in bytes.
+/// `None` if the content can not be decoded, which is reported by `contentEncoding` itself.
+#[inline]
+pub(crate) fn string_length(
+    item: &str,
+    converter: Option<ContentEncodingConverterType>,
+) -> Option<u64> {
+    match converter {
+        None => Some(item.chars().count() as u64),
+        Some(convert) => match convert(item) {
+            Ok(Some(decoded)) => Some(decoded.len() as u64),
+            _ => None,
+        },
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::equal;
-    use serde_json::{json, Value};
+    use super::{equal, equal_objects, is_integer_literal, is_integer_valued, limit, HashedValue};
+    use ahash::AHasher;
+    use serde_json::{json, Map, Value};
+    use std::hash::{Hash, Hasher};
     use test_case::test_case;
 
     #[test_case(&json!(1), &json!(1.0))]
@@ -67,4 +215,59 @@ mod tests {
     fn are_not_equal(left: &Value, right: &Value) {
         assert!(!equal(left, right))
     }
+
+    #[test]
+    fn objects_with_different_key_order() {
+        let mut left = Map::new();
+        left.insert("a".to_string(), json!(1));
+        left.insert("b".to_string(), json!({"c": 2, "d": 3}));
+        let mut right = Map::new();
+        right.insert("b".to_string(), json!({"d": 3.0, "c": 2}));
+        right.insert("a".to_string(), json!(1.0));
+        assert!(equal_objects(&left, &right));
+        assert!(equal(&Value::Object(left), &Value::Object(right)));
+    }
+
+    fn hash(value: &Value) -> u64 {
+        let mut hasher = AHasher::default();
+        HashedValue(value).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test_case(&json!(1), &json!(1.0))]
+    #[test_case(&json!(-3), &json!(-3.0))]
+    #[test_case(&json!(0), &json!(-0.0))]
+    #[test_case(&json!(u64::MAX), &json!(u64::MAX); "max integer")]
+    #[test_case(&json!([1, {"a": 2}]), &json!([1.0, {"a": 2.0}]))]
+    fn equal_hashes(left: &Value, right: &Value) {
+        assert!(HashedValue(left) == HashedValue(right));
+        assert_eq!(hash(left), hash(right))
+    }
+
+    #[test_case(&json!(0), Some(0))]
+    #[test_case(&json!(5), Some(5))]
+    #[test_case(&json!(-1), None)]
+    #[test_case(&json!(1.5), None)]
+    #[test_case(&json!("5"), None)]
+    fn limits(schema: &Value, expected: Option<u64>) {
+        assert_eq!(limit(schema).ok(), expected)
+    }
+
+    #[test_case(&json!(1), true, true)]
+    #[test_case(&json!(-1), true, true; "negative")]
+    #[test_case(&json!(u64::MAX), true, true)]
+    #[test_case(&json!(1.0), true, false)]
+    #[test_case(&json!(1.5), false, false)]
+    #[test_case(&json!(1e300), true, false)]
+    fn integers(value: &Value, valued: bool, literal: bool) {
+        let num = value.as_number().unwrap();
+        assert_eq!(is_integer_valued(num), valued);
+        assert_eq!(is_integer_literal(num), literal);
+    }
+
+    #[cfg(target_pointer_width = "32")]
+    #[test]
+    fn limit_exceeds_usize() {
+        assert!(limit(&json!(1_u64 << 32)).is_err())
+    }
 }
@@ -1,14 +1,127 @@
 use crate::{
     compilation::{compile_validators, context::CompilationContext, JSONSchema},
-    error::{no_error, ErrorIterator},
-    keywords::{format_validators, CompilationResult, Validators},
+    error::{error, no_error, CompilationError, ErrorIterator, SubSchemaErrors, ValidationError},
+    keywords::{apply_validators, format_validators, CompilationResult, Validators},
+    output::{Annotation, PartialApplication},
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use serde_json::{Map, Value};
+use std::fmt;
+
+/// The `then` or `else` sub-schema.
+struct Branch {
+    keyword: &'static str,
+    validators: Validators,
+    schema_path: JSONPointer,
+}
+
+impl Branch {
+    fn compile(
+        schema: &Value,
+        keyword: &'static str,
+        context: &CompilationContext,
+    ) -> Result<Branch, CompilationError> {
+        let context = context.with_sibling(keyword);
+        Ok(Branch {
+            keyword,
+            validators: compile_validators(schema, &context)?,
+            schema_path: context.schema_path,
+        })
+    }
+
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
+        self.validators
+            .iter()
+            .all(|validator| validator.is_valid(schema, instance, ctx))
+    }
+
+    /// A single error with errors of the branch as its context.
+    fn error<'a>(
+        &self,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        errors: Vec<ValidationError<'a>>,
+    ) -> ValidationError<'a> {
+        let schema_path = self.schema_path.clone();
+        let instance_path = instance_path.clone();
+        let error = if self.keyword == "then" {
+            ValidationError::if_then(schema_path, instance_path, instance)
+        } else {
+            ValidationError::if_else(schema_path, instance_path, instance)
+        };
+        error.with_context(vec![SubSchemaErrors::new(0, errors)])
+    }
+
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        let errors: Vec<_> = self
+            .validators
+            .iter()
+            .flat_map(|validator| validator.validate(schema, instance, instance_path, ctx))
+            .take(ctx.error_budget())
+            .collect();
+        if errors.is_empty() {
+            no_error()
+        } else {
+            error(self.error(instance, instance_path, errors))
+        }
+    }
+
+    fn apply<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> PartialApplication<'a> {
+        let mut result = apply_validators(&self.validators, schema, instance, instance_path, ctx);
+        if !result.is_valid() {
+            let errors = std::mem::take(&mut result.errors);
+            result.errors = vec![self.error(instance, instance_path, errors)];
+        }
+        result
+    }
+}
+
+/// Apply the `if` sub-schema and then the branch selected by its result.
+/// The result of `if` is annotated, and annotations of the applied branch are propagated.
+fn apply_conditional<'a>(
+    condition: &Validators,
+    then_branch: Option<&Branch>,
+    else_branch: Option<&Branch>,
+    schema: &'a JSONSchema,
+    instance: &'a Value,
+    instance_path: &JSONPointer,
+    ctx: &'a ValidationContext,
+) -> PartialApplication<'a> {
+    let condition_result = apply_validators(condition, schema, instance, instance_path, ctx);
+    let is_matched = condition_result.is_valid();
+    let mut result = PartialApplication::valid_empty();
+    result
+        .annotations
+        .push(Annotation::new("if", Value::Bool(is_matched)));
+    let branch = if is_matched {
+        result.annotations.extend(condition_result.annotations);
+        then_branch
+    } else {
+        else_branch
+    };
+    if let Some(branch) = branch {
+        result.merge(branch.apply(schema, instance, instance_path, ctx));
+    }
+    result
+}
 
 pub(crate) struct IfThenValidator {
     schema: Validators,
-    then_schema: Validators,
+    then_branch: Branch,
 }
 
 impl IfThenValidator {
@@ -20,57 +133,76 @@ impl IfThenValidator {
     ) -> CompilationResult {
         Ok(Box::new(IfThenValidator {
             schema: compile_validators(schema, context)?,
-            then_schema: compile_validators(then_schema, context)?,
+            then_branch: Branch::compile(then_schema, "then", context)?,
         }))
     }
 }
 
 impl Validate for IfThenValidator {
-    fn is_valid(&self, schema: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
         if self
             .schema
             .iter()
-            .all(|validator| validator.is_valid(schema, instance))
+            .all(|validator| validator.is_valid(schema, instance, ctx))
         {
-            self.then_schema
-                .iter()
-                .all(move |validator| validator.is_valid(schema, instance))
+            self.then_branch.is_valid(schema, instance, ctx)
         } else {
             true
         }
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if self
             .schema
             .iter()
-            .all(|validator| validator.is_valid(schema, instance))
+            .all(|validator| validator.is_valid(schema, instance, ctx))
         {
-            let errors: Vec<_> = self
-                .then_schema
-                .iter()
-                .flat_map(move |validator| validator.validate(schema, instance))
-                .collect();
-            Box::new(errors.into_iter())
+            self.then_branch
+                .validate(schema, instance, instance_path, ctx)
         } else {
             no_error()
         }
     }
+
+    fn apply<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> PartialApplication<'a> {
+        apply_conditional(
+            &self.schema,
+            Some(&self.then_branch),
+            None,
+            schema,
+            instance,
+            instance_path,
+            ctx,
+        )
+    }
 }
 
-impl ToString for IfThenValidator {
-    fn to_string(&self) -> String {
-        format!(
+impl fmt::Display for IfThenValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
             "if: {}, then: {}",
             format_validators(&self.schema),
-            format_validators(&self.then_schema)
+            format_validators(&self.then_branch.validators)
         )
     }
 }
 
 pub(crate) struct IfElseValidator {
     schema: Validators,
-    else_schema: Validators,
+    else_branch: Branch,
 }
 
 impl IfElseValidator {
@@ -82,58 +214,77 @@ impl IfElseValidator {
     ) -> CompilationResult {
         Ok(Box::new(IfElseValidator {
             schema: compile_validators(schema, context)?,
-            else_schema: compile_validators(else_schema, context)?,
+            else_branch: Branch::compile(else_schema, "else", context)?,
         }))
     }
 }
 
 impl Validate for IfElseValidator {
-    fn is_valid(&self, schema: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
         if self
             .schema
             .iter()
-            .any(|validator| !validator.is_valid(schema, instance))
+            .any(|validator| !validator.is_valid(schema, instance, ctx))
         {
-            self.else_schema
-                .iter()
-                .all(move |validator| validator.is_valid(schema, instance))
+            self.else_branch.is_valid(schema, instance, ctx)
         } else {
             true
         }
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if self
             .schema
             .iter()
-            .any(|validator| !validator.is_valid(schema, instance))
+            .any(|validator| !validator.is_valid(schema, instance, ctx))
         {
-            let errors: Vec<_> = self
-                .else_schema
-                .iter()
-                .flat_map(move |validator| validator.validate(schema, instance))
-                .collect();
-            Box::new(errors.into_iter())
+            self.else_branch
+                .validate(schema, instance, instance_path, ctx)
         } else {
             no_error()
         }
     }
+
+    fn apply<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> PartialApplication<'a> {
+        apply_conditional(
+            &self.schema,
+            None,
+            Some(&self.else_branch),
+            schema,
+            instance,
+            instance_path,
+            ctx,
+        )
+    }
 }
 
-impl ToString for IfElseValidator {
-    fn to_string(&self) -> String {
-        format!(
+impl fmt::Display for IfElseValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
             "if: {}, else: {}",
             format_validators(&self.schema),
-            format_validators(&self.else_schema)
+            format_validators(&self.else_branch.validators)
         )
     }
 }
 
 pub(crate) struct IfThenElseValidator {
     schema: Validators,
-    then_schema: Validators,
-    else_schema: Validators,
+    then_branch: Branch,
+    else_branch: Branch,
 }
 
 impl IfThenElseValidator {
@@ -146,59 +297,72 @@ impl IfThenElseValidator {
     ) -> CompilationResult {
         Ok(Box::new(IfThenElseValidator {
             schema: compile_validators(schema, context)?,
-            then_schema: compile_validators(then_schema, context)?,
-            else_schema: compile_validators(else_schema, context)?,
+            then_branch: Branch::compile(then_schema, "then", context)?,
+            else_branch: Branch::compile(else_schema, "else", context)?,
         }))
     }
 }
 
 impl Validate for IfThenElseValidator {
-    fn is_valid(&self, schema: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
         if self
             .schema
             .iter()
-            .all(|validator| validator.is_valid(schema, instance))
+            .all(|validator| validator.is_valid(schema, instance, ctx))
         {
-            self.then_schema
-                .iter()
-                .all(move |validator| validator.is_valid(schema, instance))
+            self.then_branch.is_valid(schema, instance, ctx)
         } else {
-            self.else_schema
-                .iter()
-                .all(move |validator| validator.is_valid(schema, instance))
+            self.else_branch.is_valid(schema, instance, ctx)
         }
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if self
             .schema
             .iter()
-            .all(|validator| validator.is_valid(schema, instance))
+            .all(|validator| validator.is_valid(schema, instance, ctx))
         {
-            let errors: Vec<_> = self
-                .then_schema
-                .iter()
-                .flat_map(move |validator| validator.validate(schema, instance))
-                .collect();
-            Box::new(errors.into_iter())
+            self.then_branch
+                .validate(schema, instance, instance_path, ctx)
         } else {
-            let errors: Vec<_> = self
-                .else_schema
-                .iter()
-                .flat_map(move |validator| validator.validate(schema, instance))
-                .collect();
-            Box::new(errors.into_iter())
+            self.else_branch
+                .validate(schema, instance, instance_path, ctx)
         }
     }
+
+    fn apply<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> PartialApplication<'a> {
+        apply_conditional(
+            &self.schema,
+            Some(&self.then_branch),
+            Some(&self.else_branch),
+            schema,
+            instance,
+            instance_path,
+            ctx,
+        )
+    }
 }
 
-impl ToString for IfThenElseValidator {
-    fn to_string(&self) -> String {
-        format!(
+impl fmt::Display for IfThenElseValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
             "if: {}, then: {}, else: {}",
             format_validators(&self.schema),
-            format_validators(&self.then_schema),
-            format_validators(&self.else_schema)
+            format_validators(&self.then_branch.validators),
+            format_validators(&self.else_branch.validators)
         )
     }
 }
@@ -223,3 +387,118 @@ pub(crate) fn compile(
         (None, None) => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{tests_util, JSONSchema, ValidationError};
+    use serde_json::{json, Value};
+    use test_case::test_case;
+
+    #[test_case(&json!({"if": {"type": "integer"}, "then": {"minimum": 0}}), &json!(1); "then passes")]
+    #[test_case(&json!({"if": {"type": "integer"}, "then": {"minimum": 0}}), &json!(-1.5); "if fails without else")]
+    #[test_case(&json!({"if": {"type": "integer"}, "else": {"type": "number"}}), &json!(-1.5); "else passes")]
+    #[test_case(&json!({"if": {"type": "integer"}, "else": {"minimum": 0}}), &json!(-1); "if passes without then")]
+    #[test_case(&json!({"if": {"type": "integer"}, "then": {"minimum": 0}, "else": {"type": "string"}}), &json!("a"); "else branch")]
+    #[test_case(&json!({"if": {"type": "integer"}}), &json!("a"); "if alone")]
+    #[test_case(&json!({"then": {"type": "integer"}, "else": {"type": "integer"}}), &json!("a"); "without if")]
+    fn valid(schema: &Value, instance: &Value) {
+        tests_util::is_valid(schema, instance)
+    }
+
+    #[test_case(&json!({"if": {"type": "integer"}, "then": {"minimum": 0}}), &json!(-1); "then fails")]
+    #[test_case(&json!({"if": {"type": "integer"}, "else": {"type": "string"}}), &json!(-1.5); "else fails")]
+    #[test_case(&json!({"if": {"type": "integer"}, "then": {"minimum": 0}, "else": {"type": "string"}}), &json!(-1); "then branch fails")]
+    #[test_case(&json!({"if": {"type": "integer"}, "then": {"minimum": 0}, "else": {"type": "string"}}), &json!(1.5); "else branch fails")]
+    fn invalid(schema: &Value, instance: &Value) {
+        tests_util::is_not_valid(schema, instance)
+    }
+
+    #[test]
+    fn errors_come_from_branches() {
+        let schema =
+            json!({"if": {"type": "integer"}, "then": {"minimum": 0}, "else": {"type": "string"}});
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        let instance = json!(-1);
+        let errors: Vec<_> = compiled.validate(&instance).unwrap_err().collect();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].is_then_error());
+        assert_eq!(errors[0].schema_path().to_string(), "/then");
+        assert_eq!(branch_errors(&errors[0]), vec!["/then/minimum"]);
+        let instance = json!(1.5);
+        let errors: Vec<_> = compiled.validate(&instance).unwrap_err().collect();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].is_else_error());
+        assert_eq!(errors[0].schema_path().to_string(), "/else");
+        assert_eq!(branch_errors(&errors[0]), vec!["/else/type"]);
+        assert_eq!(
+            errors[0].to_string(),
+            "/else: '1.5' is not valid under the 'if' schema, nor under the 'else' schema"
+        );
+    }
+
+    /// Schema paths of errors in the context of `error`
+    fn branch_errors(error: &ValidationError) -> Vec<String> {
+        let context = error.context().unwrap();
+        assert_eq!(context.len(), 1);
+        assert_eq!(context[0].index(), 0);
+        context[0]
+            .errors()
+            .iter()
+            .map(|error| error.schema_path().to_string())
+            .collect()
+    }
+
+    fn annotations(schema: &Value, instance: &Value) -> Value {
+        let compiled = JSONSchema::compile(schema).unwrap();
+        let output = compiled.apply(instance);
+        serde_json::to_value(&output).unwrap()["annotations"].clone()
+    }
+
+    #[test_case(&json!({"if": {"type": "integer"}, "then": {"minimum": 0}}), &json!(1), &json!([true]))]
+    #[test_case(&json!({"if": {"type": "integer"}, "then": {"minimum": 0}}), &json!("a"), &json!([false]))]
+    #[test_case(&json!({"if": {"type": "integer"}, "else": {"type": "string"}}), &json!(1), &json!([true]))]
+    #[test_case(&json!({"if": {"type": "integer"}, "else": {"type": "string"}}), &json!("a"), &json!([false]))]
+    #[test_case(&json!({"if": {"type": "integer"}, "then": {"minimum": 0}, "else": {"type": "string"}}), &json!("a"), &json!([false]))]
+    fn condition_is_annotated(schema: &Value, instance: &Value, expected: &Value) {
+        let expected: Vec<_> = expected
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|value| json!({"keyword": "if", "annotation": value}))
+            .collect();
+        assert_eq!(annotations(schema, instance), json!(expected));
+    }
+
+    #[test]
+    fn branch_annotations_are_propagated() {
+        let schema = json!({
+            "if": {"type": "integer"},
+            "then": {"if": {"minimum": 5}, "then": true},
+            "else": {"if": {"maxLength": 2}, "else": true}
+        });
+        assert_eq!(
+            annotations(&schema, &json!(7)),
+            json!([
+                {"keyword": "if", "annotation": true},
+                {"keyword": "if", "annotation": true}
+            ])
+        );
+        assert_eq!(
+            annotations(&schema, &json!("abc")),
+            json!([
+                {"keyword": "if", "annotation": false},
+                {"keyword": "if", "annotation": false}
+            ])
+        );
+    }
+
+    #[test]
+    fn invalid_branch_drops_annotations() {
+        let schema = json!({"if": {"type": "integer"}, "then": {"minimum": 5}});
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        let instance = json!(1);
+        let output = compiled.apply(&instance);
+        assert!(!output.is_valid());
+        assert!(output.annotations().is_empty());
+    }
+}
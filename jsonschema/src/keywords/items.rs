@@ -1,30 +1,50 @@
 use crate::{
     compilation::{compile_validators, context::CompilationContext, JSONSchema},
-    error::{no_error, ErrorIterator},
+    error::{error, no_error, ErrorIterator, ValidationError},
     keywords::{
         boolean::TrueValidator, format_validators, format_vec_of_validators, CompilationResult,
         Validators,
     },
+    output::{Annotation, PartialApplication},
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use serde_json::{Map, Value};
+use std::fmt;
 
-pub(crate) struct ItemsArrayValidator {
+/// Annotate a valid result with the largest evaluated index, if any item was evaluated.
+#[inline]
+fn annotate_last_index(
+    mut result: PartialApplication<'_>,
+    evaluated: usize,
+) -> PartialApplication<'_> {
+    if result.is_valid() && evaluated > 0 {
+        result
+            .annotations
+            .push(Annotation::new("items", (evaluated - 1).into()));
+    }
+    result
+}
+
+/// The tuple form - one schema per position, `prefixItems` in Draft 2020-12.
+/// Items beyond the listed schemas are checked by `additionalItems`.
+pub(crate) struct TupleItemsValidator {
     items: Vec<Validators>,
 }
-impl ItemsArrayValidator {
+impl TupleItemsValidator {
     #[inline]
     pub(crate) fn compile(schemas: &[Value], context: &CompilationContext) -> CompilationResult {
         let mut items = Vec::with_capacity(schemas.len());
-        for item in schemas {
-            let validators = compile_validators(item, context)?;
+        for (idx, item) in schemas.iter().enumerate() {
+            let validators = compile_validators(item, &context.with_path(idx))?;
             items.push(validators)
         }
-        Ok(Box::new(ItemsArrayValidator { items }))
+        Ok(Box::new(TupleItemsValidator { items }))
     }
 }
-impl Validate for ItemsArrayValidator {
-    fn is_valid(&self, schema: &JSONSchema, instance: &Value) -> bool {
+impl Validate for TupleItemsValidator {
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
         if let Value::Array(items) = instance {
             items
                 .iter()
@@ -32,81 +52,181 @@ impl Validate for ItemsArrayValidator {
                 .all(move |(item, validators)| {
                     validators
                         .iter()
-                        .all(move |validator| validator.is_valid(schema, item))
+                        .all(move |validator| validator.is_valid(schema, item, ctx))
                 })
         } else {
             true
         }
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::Array(items) = instance {
             let errors: Vec<_> = items
                 .iter()
                 .zip(self.items.iter())
-                .flat_map(move |(item, validators)| {
-                    validators
-                        .iter()
-                        .flat_map(move |validator| validator.validate(schema, item))
+                .enumerate()
+                .flat_map(move |(idx, (item, validators))| {
+                    let instance_path = instance_path.extend_with(idx);
+                    validators.iter().flat_map(move |validator| {
+                        validator.validate(schema, item, &instance_path, ctx)
+                    })
                 })
+                .take(ctx.error_budget())
                 .collect();
             Box::new(errors.into_iter())
         } else {
             no_error()
         }
     }
+
+    fn apply<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> PartialApplication<'a> {
+        let result = self
+            .validate(schema, instance, instance_path, ctx)
+            .collect::<Vec<_>>()
+            .into();
+        if let Value::Array(items) = instance {
+            annotate_last_index(result, items.len().min(self.items.len()))
+        } else {
+            result
+        }
+    }
 }
 
-impl ToString for ItemsArrayValidator {
-    fn to_string(&self) -> String {
-        format!("items: [{}]", format_vec_of_validators(&self.items))
+impl fmt::Display for TupleItemsValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "items: [{}]", format_vec_of_validators(&self.items))
     }
 }
 
-pub(crate) struct ItemsObjectValidator {
+/// The list form - a single schema applied to every item.
+/// `additionalItems` is ignored in this case.
+pub(crate) struct ListItemsValidator {
     validators: Validators,
 }
-impl ItemsObjectValidator {
+impl ListItemsValidator {
     #[inline]
     pub(crate) fn compile(schema: &Value, context: &CompilationContext) -> CompilationResult {
         let validators = compile_validators(schema, context)?;
-        Ok(Box::new(ItemsObjectValidator { validators }))
+        Ok(Box::new(ListItemsValidator { validators }))
     }
 }
-impl Validate for ItemsObjectValidator {
-    fn is_valid(&self, schema: &JSONSchema, instance: &Value) -> bool {
+impl Validate for ListItemsValidator {
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
         if let Value::Array(items) = instance {
             self.validators.iter().all(move |validator| {
                 items
                     .iter()
-                    .all(move |item| validator.is_valid(schema, item))
+                    .all(move |item| validator.is_valid(schema, item, ctx))
             })
         } else {
             true
         }
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::Array(items) = instance {
             let errors: Vec<_> = self
                 .validators
                 .iter()
                 .flat_map(move |validator| {
-                    items
-                        .iter()
-                        .flat_map(move |item| validator.validate(schema, item))
+                    items.iter().enumerate().flat_map(move |(idx, item)| {
+                        validator.validate(schema, item, &instance_path.extend_with(idx), ctx)
+                    })
                 })
+                .take(ctx.error_budget())
                 .collect();
             Box::new(errors.into_iter())
         } else {
             no_error()
         }
     }
+
+    fn apply<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> PartialApplication<'a> {
+        let result = self
+            .validate(schema, instance, instance_path, ctx)
+            .collect::<Vec<_>>()
+            .into();
+        if let Value::Array(items) = instance {
+            annotate_last_index(result, items.len())
+        } else {
+            result
+        }
+    }
+}
+
+impl fmt::Display for ListItemsValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "items: {}", format_validators(&self.validators))
+    }
+}
+
+/// `items: false` - only empty arrays are valid.
+pub(crate) struct ItemsFalseValidator {
+    schema_path: JSONPointer,
+}
+impl ItemsFalseValidator {
+    #[inline]
+    pub(crate) fn compile(schema_path: JSONPointer) -> CompilationResult {
+        Ok(Box::new(ItemsFalseValidator { schema_path }))
+    }
+}
+impl Validate for ItemsFalseValidator {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        if let Value::Array(items) = instance {
+            items.is_empty()
+        } else {
+            true
+        }
+    }
+
+    fn validate<'a>(
+        &self,
+        _: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        _: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if let Value::Array(items) = instance {
+            if let Some(item) = items.first() {
+                return error(ValidationError::false_schema(
+                    self.schema_path.clone(),
+                    instance_path.clone(),
+                    item,
+                    "items",
+                ));
+            }
+        }
+        no_error()
+    }
 }
 
-impl ToString for ItemsObjectValidator {
-    fn to_string(&self) -> String {
-        format!("items: {}", format_validators(&self.validators))
+impl fmt::Display for ItemsFalseValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("items: false")
     }
 }
 
@@ -117,15 +237,76 @@ pub(crate) fn compile(
     context: &CompilationContext,
 ) -> Option<CompilationResult> {
     match schema {
-        Value::Array(items) => Some(ItemsArrayValidator::compile(items, context)),
-        Value::Object(_) => Some(ItemsObjectValidator::compile(schema, context)),
+        Value::Array(items) => Some(TupleItemsValidator::compile(items, context)),
+        Value::Object(_) => Some(ListItemsValidator::compile(schema, context)),
         Value::Bool(value) => {
             if *value {
                 Some(TrueValidator::compile())
             } else {
-                Some(ItemsObjectValidator::compile(schema, context))
+                Some(ItemsFalseValidator::compile(context.schema_path.clone()))
             }
         }
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{compilation::JSONSchema, tests_util};
+    use serde_json::{json, Value};
+    use test_case::test_case;
+
+    #[test_case(&json!({"items": false}), &json!([]))]
+    #[test_case(&json!({"items": false}), &json!({}) ; "not an array")]
+    #[test_case(&json!({"items": false}), &json!("foo"))]
+    #[test_case(&json!({"items": true}), &json!([1, "a"]))]
+    fn valid(schema: &Value, instance: &Value) {
+        tests_util::is_valid(schema, instance)
+    }
+
+    #[test_case(&json!({"items": false}), &json!([1]))]
+    #[test_case(&json!({"items": false}), &json!([null, 2]))]
+    fn invalid(schema: &Value, instance: &Value) {
+        tests_util::is_not_valid(schema, instance)
+    }
+
+    #[test]
+    fn items_false_single_error() {
+        tests_util::expect_errors(
+            &json!({"items": false}),
+            &json!([1, 2, 3]),
+            &["False schema does not allow '1'"],
+        )
+    }
+
+    #[test_case(&json!({"items": {"type": "integer"}}), &json!([1, 2, 3]), Some(2))]
+    #[test_case(&json!({"items": {"type": "integer"}}), &json!([]), None)]
+    #[test_case(&json!({"items": [{}, {}]}), &json!([1, 2, 3]), Some(1))]
+    #[test_case(&json!({"items": [{}, {}]}), &json!([1]), Some(0))]
+    #[test_case(&json!({"items": [{}, {}]}), &json!([]), None ; "empty tuple")]
+    fn annotates_last_evaluated_index(schema: &Value, instance: &Value, expected: Option<u64>) {
+        let compiled = JSONSchema::compile(schema).unwrap();
+        let output = compiled.apply(instance);
+        assert!(output.is_valid());
+        let annotations: Vec<_> = output
+            .annotations()
+            .iter()
+            .map(|annotation| (annotation.keyword(), annotation.value().clone()))
+            .collect();
+        let expected: Vec<_> = expected
+            .into_iter()
+            .map(|idx| ("items", json!(idx)))
+            .collect();
+        assert_eq!(annotations, expected);
+    }
+
+    #[test]
+    fn no_annotations_on_failure() {
+        let schema = json!({"items": {"type": "integer"}});
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        let instance = json!([1, "a"]);
+        let output = compiled.apply(&instance);
+        assert!(!output.is_valid());
+        assert!(output.annotations().is_empty());
+    }
+}
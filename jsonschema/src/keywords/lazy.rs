@@ -0,0 +1,152 @@
+use crate::{
+    compilation::{compile_validators_eagerly, context::CompilationContext, JSONSchema},
+    error::{error, CompilationError, ErrorIterator, ValidationError},
+    keywords::{apply_validators, BoxedValidator, Validators},
+    output::PartialApplication,
+    paths::JSONPointer,
+    validation_context::ValidationContext,
+    validator::Validate,
+};
+use once_cell::sync::OnceCell;
+use serde_json::Value;
+use std::{borrow::Cow, fmt};
+use url::Url;
+
+/// A sub-schema that is compiled on its first use.
+/// Enabled by `CompilationOptions::with_lazy_compilation`.
+pub(crate) struct LazyValidator {
+    schema: Value,
+    scope: Url,
+    schema_path: JSONPointer,
+    /// Validators of the sub-schema, or the reason why it failed to compile.
+    /// Initialized on the first use, as validation has only a shared reference to `self`.
+    validators: OnceCell<Result<Validators, CompilationError>>,
+}
+
+impl LazyValidator {
+    #[inline]
+    pub(crate) fn compile(schema: &Value, context: &CompilationContext) -> BoxedValidator {
+        Box::new(LazyValidator {
+            schema: schema.clone(),
+            scope: context.scope.as_ref().clone(),
+            schema_path: context.schema_path.clone(),
+            validators: OnceCell::new(),
+        })
+    }
+
+    /// Validators of the sub-schema. They are built once, even if the compilation fails.
+    #[inline]
+    fn validators<'a>(&self, schema: &'a JSONSchema) -> Result<&Validators, ValidationError<'a>> {
+        self.validators
+            .get_or_init(|| {
+                let context = CompilationContext {
+                    scope: Cow::Borrowed(&self.scope),
+                    config: Cow::Borrowed(&schema.context.config),
+                    schema_path: self.schema_path.clone(),
+                };
+                compile_validators_eagerly(&self.schema, &context)
+            })
+            .as_ref()
+            .map_err(|_| ValidationError::schema())
+    }
+}
+
+impl Validate for LazyValidator {
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
+        match self.validators(schema) {
+            Ok(validators) => validators
+                .iter()
+                .all(|validator| validator.is_valid(schema, instance, ctx)),
+            Err(_) => false,
+        }
+    }
+
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        match self.validators(schema) {
+            Ok(validators) => Box::new(
+                validators
+                    .iter()
+                    .flat_map(move |validator| {
+                        validator.validate(schema, instance, instance_path, ctx)
+                    })
+                    .take(ctx.error_budget())
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            ),
+            Err(err) => error(err),
+        }
+    }
+
+    fn apply<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> PartialApplication<'a> {
+        match self.validators(schema) {
+            Ok(validators) => apply_validators(validators, schema, instance, instance_path, ctx),
+            Err(err) => vec![err].into(),
+        }
+    }
+}
+
+impl fmt::Display for LazyValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.schema)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CompilationOptions, JSONSchema};
+    use serde_json::json;
+
+    fn compile_lazy(schema: &serde_json::Value) -> JSONSchema<'_> {
+        CompilationOptions::default()
+            .with_lazy_compilation()
+            .compile(schema)
+            .unwrap()
+    }
+
+    #[test]
+    fn sub_schemas_are_compiled_on_first_use() {
+        let schema = json!({
+            "properties": {"foo": {"type": "string"}},
+            "items": {"$ref": "#/definitions/positive"},
+            "definitions": {"positive": {"minimum": 0}}
+        });
+        let compiled = compile_lazy(&schema);
+        assert!(compiled.is_valid(&json!({"foo": "bar"})));
+        assert!(!compiled.is_valid(&json!({"foo": 1})));
+        assert!(compiled.is_valid(&json!([1, 2])));
+        let instance = json!([1, -2]);
+        let errors: Vec<_> = compiled.validate(&instance).unwrap_err().collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].to_string(),
+            "/items/$ref/minimum: -2 is less than the minimum of 0"
+        );
+        assert_eq!(errors[0].instance_path().to_string(), "/1");
+    }
+
+    #[test]
+    fn invalid_sub_schemas_are_reported_on_use() {
+        let schema = json!({"properties": {"foo": {"type": 42}}});
+        // The sub-schema is not compiled yet
+        let compiled = compile_lazy(&schema);
+        assert!(compiled.is_valid(&json!({"bar": 1})));
+        assert!(!compiled.is_valid(&json!({"foo": 1})));
+        assert!(compiled.validate(&json!({"foo": 1})).is_err());
+        // The failure is cached and reported on every use
+        assert!(!compiled.is_valid(&json!({"foo": 1})));
+        // Without lazy compilation the schema is rejected upfront
+        assert!(JSONSchema::compile(&schema).is_err());
+    }
+}
@@ -1,20 +1,23 @@
 use crate::{
     compilation::{context::CompilationContext, JSONSchema},
     error::{error, no_error, CompilationError, ErrorIterator, ValidationError},
-    keywords::{type_, CompilationResult},
+    keywords::{helpers, type_, CompilationResult},
+    paths::JSONPointer,
     primitive_type::{PrimitiveType, PrimitiveTypesBitMap},
+    validation_context::ValidationContext,
     validator::Validate,
 };
-use serde_json::{Map, Number, Value};
-use std::convert::TryFrom;
+use serde_json::{Map, Value};
+use std::{convert::TryFrom, fmt};
 
 pub(crate) struct MultipleTypesValidator {
     types: PrimitiveTypesBitMap,
+    schema_path: JSONPointer,
 }
 
 impl MultipleTypesValidator {
     #[inline]
-    pub(crate) fn compile(items: &[Value]) -> CompilationResult {
+    pub(crate) fn compile(items: &[Value], schema_path: JSONPointer) -> CompilationResult {
         let mut types = PrimitiveTypesBitMap::new();
         for item in items {
             match item {
@@ -28,36 +31,49 @@ impl MultipleTypesValidator {
                 _ => return Err(CompilationError::SchemaError),
             }
         }
-        Ok(Box::new(MultipleTypesValidator { types }))
+        Ok(Box::new(MultipleTypesValidator { types, schema_path }))
     }
 }
 
 impl Validate for MultipleTypesValidator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         match instance {
             Value::Array(_) => self.types.contains_type(PrimitiveType::Array),
             Value::Bool(_) => self.types.contains_type(PrimitiveType::Boolean),
             Value::Null => self.types.contains_type(PrimitiveType::Null),
             Value::Number(num) => {
                 self.types.contains_type(PrimitiveType::Number)
-                    || (self.types.contains_type(PrimitiveType::Integer) && is_integer(num))
+                    || (self.types.contains_type(PrimitiveType::Integer)
+                        && helpers::is_integer_literal(num))
             }
             Value::Object(_) => self.types.contains_type(PrimitiveType::Object),
             Value::String(_) => self.types.contains_type(PrimitiveType::String),
         }
     }
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        if self.is_valid(schema, instance) {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
             no_error()
         } else {
-            error(ValidationError::multiple_type_error(instance, self.types))
+            error(ValidationError::multiple_type_error(
+                self.schema_path.clone(),
+                instance_path.clone(),
+                instance,
+                self.types,
+            ))
         }
     }
 }
 
-impl ToString for MultipleTypesValidator {
-    fn to_string(&self) -> String {
-        format!(
+impl fmt::Display for MultipleTypesValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
             "type: [{}]",
             self.types
                 .into_iter()
@@ -68,29 +84,39 @@ impl ToString for MultipleTypesValidator {
     }
 }
 
-pub(crate) struct IntegerTypeValidator {}
+pub(crate) struct IntegerTypeValidator {
+    schema_path: JSONPointer,
+}
 
 impl IntegerTypeValidator {
     #[inline]
-    pub(crate) fn compile() -> CompilationResult {
-        Ok(Box::new(IntegerTypeValidator {}))
+    pub(crate) fn compile(schema_path: JSONPointer) -> CompilationResult {
+        Ok(Box::new(IntegerTypeValidator { schema_path }))
     }
 }
 
 impl Validate for IntegerTypeValidator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         if let Value::Number(num) = instance {
-            is_integer(num)
+            helpers::is_integer_literal(num)
         } else {
             false
         }
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        if self.is_valid(schema, instance) {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
             no_error()
         } else {
             error(ValidationError::single_type_error(
+                self.schema_path.clone(),
+                instance_path.clone(),
                 instance,
                 PrimitiveType::Integer,
             ))
@@ -98,48 +124,71 @@ impl Validate for IntegerTypeValidator {
     }
 }
 
-impl ToString for IntegerTypeValidator {
-    fn to_string(&self) -> String {
-        "type: integer".to_string()
+impl fmt::Display for IntegerTypeValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("type: integer")
     }
 }
 
-fn is_integer(num: &Number) -> bool {
-    num.is_u64() || num.is_i64()
-}
-
 #[inline]
 pub(crate) fn compile(
     _: &Map<String, Value>,
     schema: &Value,
-    _: &CompilationContext,
+    context: &CompilationContext,
 ) -> Option<CompilationResult> {
+    if context.config.has_strict_keywords() && type_::has_duplicate_types(schema) {
+        return Some(Err(CompilationError::SchemaError));
+    }
+    if context.config.coerces_types() {
+        return Some(type_::CoercingTypesValidator::compile(
+            schema,
+            helpers::is_integer_literal,
+            context.schema_path.clone(),
+        ));
+    }
     match schema {
-        Value::String(item) => compile_single_type(item.as_str()),
+        Value::String(item) => compile_single_type(item.as_str(), context),
         Value::Array(items) => {
             if items.len() == 1 {
                 if let Some(Value::String(item)) = items.iter().next() {
-                    compile_single_type(item.as_str())
+                    compile_single_type(item.as_str(), context)
                 } else {
                     Some(Err(CompilationError::SchemaError))
                 }
             } else {
-                Some(MultipleTypesValidator::compile(items))
+                Some(MultipleTypesValidator::compile(
+                    items,
+                    context.schema_path.clone(),
+                ))
             }
         }
         _ => Some(Err(CompilationError::SchemaError)),
     }
 }
 
-fn compile_single_type(item: &str) -> Option<CompilationResult> {
+fn compile_single_type(item: &str, context: &CompilationContext) -> Option<CompilationResult> {
     match PrimitiveType::try_from(item) {
-        Ok(PrimitiveType::Array) => Some(type_::ArrayTypeValidator::compile()),
-        Ok(PrimitiveType::Boolean) => Some(type_::BooleanTypeValidator::compile()),
-        Ok(PrimitiveType::Integer) => Some(IntegerTypeValidator::compile()),
-        Ok(PrimitiveType::Null) => Some(type_::NullTypeValidator::compile()),
-        Ok(PrimitiveType::Number) => Some(type_::NumberTypeValidator::compile()),
-        Ok(PrimitiveType::Object) => Some(type_::ObjectTypeValidator::compile()),
-        Ok(PrimitiveType::String) => Some(type_::StringTypeValidator::compile()),
+        Ok(PrimitiveType::Array) => Some(type_::ArrayTypeValidator::compile(
+            context.schema_path.clone(),
+        )),
+        Ok(PrimitiveType::Boolean) => Some(type_::BooleanTypeValidator::compile(
+            context.schema_path.clone(),
+        )),
+        Ok(PrimitiveType::Integer) => {
+            Some(IntegerTypeValidator::compile(context.schema_path.clone()))
+        }
+        Ok(PrimitiveType::Null) => Some(type_::NullTypeValidator::compile(
+            context.schema_path.clone(),
+        )),
+        Ok(PrimitiveType::Number) => Some(type_::NumberTypeValidator::compile(
+            context.schema_path.clone(),
+        )),
+        Ok(PrimitiveType::Object) => Some(type_::ObjectTypeValidator::compile(
+            context.schema_path.clone(),
+        )),
+        Ok(PrimitiveType::String) => Some(type_::StringTypeValidator::compile(
+            context.schema_path.clone(),
+        )),
         Err(()) => Some(Err(CompilationError::SchemaError)),
     }
 }
@@ -1,28 +1,29 @@
 use crate::{
     compilation::{context::CompilationContext, JSONSchema},
-    error::{error, no_error, CompilationError, ErrorIterator, ValidationError},
-    keywords::CompilationResult,
+    error::{error, no_error, ErrorIterator, ValidationError},
+    keywords::{helpers, CompilationResult},
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use serde_json::{Map, Value};
+use std::fmt;
 
 pub(crate) struct MaxItemsValidator {
     limit: u64,
+    schema_path: JSONPointer,
 }
 
 impl MaxItemsValidator {
     #[inline]
-    pub(crate) fn compile(schema: &Value) -> CompilationResult {
-        if let Some(limit) = schema.as_u64() {
-            Ok(Box::new(MaxItemsValidator { limit }))
-        } else {
-            Err(CompilationError::SchemaError)
-        }
+    pub(crate) fn compile(schema: &Value, schema_path: JSONPointer) -> CompilationResult {
+        let limit = helpers::limit(schema)?;
+        Ok(Box::new(MaxItemsValidator { limit, schema_path }))
     }
 }
 
 impl Validate for MaxItemsValidator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         if let Value::Array(items) = instance {
             if (items.len() as u64) > self.limit {
                 return false;
@@ -31,19 +32,32 @@ impl Validate for MaxItemsValidator {
         true
     }
 
-    fn validate<'a>(&self, _: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        _: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        _: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::Array(items) = instance {
-            if (items.len() as u64) > self.limit {
-                return error(ValidationError::max_items(instance, self.limit));
+            let actual = items.len() as u64;
+            if actual > self.limit {
+                return error(ValidationError::max_items(
+                    self.schema_path.clone(),
+                    instance_path.clone(),
+                    instance,
+                    self.limit,
+                    actual,
+                ));
             }
         }
         no_error()
     }
 }
 
-impl ToString for MaxItemsValidator {
-    fn to_string(&self) -> String {
-        format!("maxItems: {}", self.limit)
+impl fmt::Display for MaxItemsValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "maxItems: {}", self.limit)
     }
 }
 
@@ -51,7 +65,10 @@ impl ToString for MaxItemsValidator {
 pub(crate) fn compile(
     _: &Map<String, Value>,
     schema: &Value,
-    _: &CompilationContext,
+    context: &CompilationContext,
 ) -> Option<CompilationResult> {
-    Some(MaxItemsValidator::compile(schema))
+    Some(MaxItemsValidator::compile(
+        schema,
+        context.schema_path.clone(),
+    ))
 }
@@ -1,28 +1,29 @@
 use crate::{
     compilation::{context::CompilationContext, JSONSchema},
-    error::{error, no_error, CompilationError, ErrorIterator, ValidationError},
-    keywords::CompilationResult,
+    error::{error, no_error, ErrorIterator, ValidationError},
+    keywords::{helpers, CompilationResult},
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use serde_json::{Map, Value};
+use std::fmt;
 
 pub(crate) struct MaxPropertiesValidator {
     limit: u64,
+    schema_path: JSONPointer,
 }
 
 impl MaxPropertiesValidator {
     #[inline]
-    pub(crate) fn compile(schema: &Value) -> CompilationResult {
-        if let Some(limit) = schema.as_u64() {
-            Ok(Box::new(MaxPropertiesValidator { limit }))
-        } else {
-            Err(CompilationError::SchemaError)
-        }
+    pub(crate) fn compile(schema: &Value, schema_path: JSONPointer) -> CompilationResult {
+        let limit = helpers::limit(schema)?;
+        Ok(Box::new(MaxPropertiesValidator { limit, schema_path }))
     }
 }
 
 impl Validate for MaxPropertiesValidator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         if let Value::Object(item) = instance {
             if (item.len() as u64) > self.limit {
                 return false;
@@ -31,19 +32,32 @@ impl Validate for MaxPropertiesValidator {
         true
     }
 
-    fn validate<'a>(&self, _: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        _: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        _: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::Object(item) = instance {
-            if (item.len() as u64) > self.limit {
-                return error(ValidationError::max_properties(instance, self.limit));
+            let actual = item.len() as u64;
+            if actual > self.limit {
+                return error(ValidationError::max_properties(
+                    self.schema_path.clone(),
+                    instance_path.clone(),
+                    instance,
+                    self.limit,
+                    actual,
+                ));
             }
         }
         no_error()
     }
 }
 
-impl ToString for MaxPropertiesValidator {
-    fn to_string(&self) -> String {
-        format!("maxProperties: {}", self.limit)
+impl fmt::Display for MaxPropertiesValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "maxProperties: {}", self.limit)
     }
 }
 
@@ -51,7 +65,10 @@ impl ToString for MaxPropertiesValidator {
 pub(crate) fn compile(
     _: &Map<String, Value>,
     schema: &Value,
-    _: &CompilationContext,
+    context: &CompilationContext,
 ) -> Option<CompilationResult> {
-    Some(MaxPropertiesValidator::compile(schema))
+    Some(MaxPropertiesValidator::compile(
+        schema,
+        context.schema_path.clone(),
+    ))
 }
@@ -2,19 +2,25 @@ use crate::{
     compilation::{context::CompilationContext, JSONSchema},
     error::{error, no_error, CompilationError, ErrorIterator, ValidationError},
     keywords::CompilationResult,
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use num_cmp::NumCmp;
 use serde_json::{Map, Value};
+use std::fmt;
 
 pub(crate) struct MaximumU64Validator {
     limit: u64,
+    schema_path: JSONPointer,
 }
 pub(crate) struct MaximumI64Validator {
     limit: i64,
+    schema_path: JSONPointer,
 }
 pub(crate) struct MaximumF64Validator {
     limit: f64,
+    schema_path: JSONPointer,
 }
 
 macro_rules! validate {
@@ -24,15 +30,22 @@ macro_rules! validate {
                 &self,
                 schema: &'a JSONSchema,
                 instance: &'a Value,
+                instance_path: &JSONPointer,
+                ctx: &'a ValidationContext,
             ) -> ErrorIterator<'a> {
-                if self.is_valid(schema, instance) {
+                if self.is_valid(schema, instance, ctx) {
                     no_error()
                 } else {
-                    error(ValidationError::maximum(instance, self.limit as f64)) // do not cast
+                    error(ValidationError::maximum(
+                        self.schema_path.clone(),
+                        instance_path.clone(),
+                        instance,
+                        self.limit.into(),
+                    ))
                 }
             }
 
-            fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+            fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
                 if let Value::Number(item) = instance {
                     return if let Some(item) = item.as_u64() {
                         !NumCmp::num_gt(item, self.limit)
@@ -46,9 +59,9 @@ macro_rules! validate {
                 true
             }
         }
-        impl ToString for $validator {
-            fn to_string(&self) -> String {
-                format!("maximum: {}", self.limit)
+        impl fmt::Display for $validator {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "maximum: {}", self.limit)
             }
         }
     };
@@ -58,7 +71,7 @@ validate!(MaximumU64Validator);
 validate!(MaximumI64Validator);
 
 impl Validate for MaximumF64Validator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         if let Value::Number(item) = instance {
             return if let Some(item) = item.as_u64() {
                 !NumCmp::num_gt(item, self.limit)
@@ -72,17 +85,28 @@ impl Validate for MaximumF64Validator {
         true
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        if self.is_valid(schema, instance) {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
             no_error()
         } else {
-            error(ValidationError::maximum(instance, self.limit))
+            error(ValidationError::maximum(
+                self.schema_path.clone(),
+                instance_path.clone(),
+                instance,
+                self.limit.into(),
+            ))
         }
     }
 }
-impl ToString for MaximumF64Validator {
-    fn to_string(&self) -> String {
-        format!("maximum: {}", self.limit)
+impl fmt::Display for MaximumF64Validator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "maximum: {}", self.limit)
     }
 }
 
@@ -90,16 +114,17 @@ impl ToString for MaximumF64Validator {
 pub(crate) fn compile(
     _: &Map<String, Value>,
     schema: &Value,
-    _: &CompilationContext,
+    context: &CompilationContext,
 ) -> Option<CompilationResult> {
     if let Value::Number(limit) = schema {
+        let schema_path = context.schema_path.clone();
         if let Some(limit) = limit.as_u64() {
-            Some(Ok(Box::new(MaximumU64Validator { limit })))
+            Some(Ok(Box::new(MaximumU64Validator { limit, schema_path })))
         } else if let Some(limit) = limit.as_i64() {
-            Some(Ok(Box::new(MaximumI64Validator { limit })))
+            Some(Ok(Box::new(MaximumI64Validator { limit, schema_path })))
         } else {
             let limit = limit.as_f64().expect("Always valid");
-            Some(Ok(Box::new(MaximumF64Validator { limit })))
+            Some(Ok(Box::new(MaximumF64Validator { limit, schema_path })))
         }
     } else {
         Some(Err(CompilationError::SchemaError))
@@ -114,7 +139,24 @@ mod tests {
 
     #[test_case(&json!({"maximum": 1u64 << 54}), &json!((1u64 << 54) + 1))]
     #[test_case(&json!({"maximum": 1i64 << 54}), &json!((1i64 << 54) + 1))]
+    #[test_case(&json!({"maximum": u64::MAX - 1}), &json!(u64::MAX))]
+    #[test_case(&json!({"maximum": i64::MIN}), &json!(i64::MIN + 1))]
     fn is_not_valid(schema: &Value, instance: &Value) {
         tests_util::is_not_valid(schema, instance)
     }
+
+    #[test_case(&json!({"maximum": u64::MAX - 1}), &json!(u64::MAX - 1))]
+    #[test_case(&json!({"maximum": i64::MIN + 1}), &json!(i64::MIN))]
+    fn is_valid(schema: &Value, instance: &Value) {
+        tests_util::is_valid(schema, instance)
+    }
+
+    #[test]
+    fn exact_limit_in_message() {
+        tests_util::expect_errors(
+            &json!({"maximum": (1u64 << 54) + 1}),
+            &json!((1u64 << 54) + 2),
+            &["18014398509481986 is greater than the maximum of 18014398509481985"],
+        )
+    }
 }
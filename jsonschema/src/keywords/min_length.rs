@@ -1,57 +1,125 @@
 use crate::{
     compilation::{context::CompilationContext, JSONSchema},
-    error::{error, no_error, CompilationError, ErrorIterator, ValidationError},
-    keywords::CompilationResult,
+    content_encoding::ContentEncodingConverterType,
+    error::{error, no_error, ErrorIterator, ValidationError},
+    keywords::{helpers, CompilationResult},
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use serde_json::{Map, Value};
+use std::fmt;
 
 pub(crate) struct MinLengthValidator {
     limit: u64,
+    /// Decoder of a sibling `contentEncoding`, the limit applies to the decoded content then.
+    converter: Option<ContentEncodingConverterType>,
+    schema_path: JSONPointer,
 }
 
 impl MinLengthValidator {
     #[inline]
-    pub(crate) fn compile(schema: &Value) -> CompilationResult {
-        if let Some(limit) = schema.as_u64() {
-            Ok(Box::new(MinLengthValidator { limit }))
-        } else {
-            Err(CompilationError::SchemaError)
-        }
+    pub(crate) fn compile(
+        schema: &Value,
+        converter: Option<ContentEncodingConverterType>,
+        schema_path: JSONPointer,
+    ) -> CompilationResult {
+        let limit = helpers::limit(schema)?;
+        Ok(Box::new(MinLengthValidator {
+            limit,
+            converter,
+            schema_path,
+        }))
     }
 }
 
 impl Validate for MinLengthValidator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         if let Value::String(item) = instance {
-            if (item.chars().count() as u64) < self.limit {
-                return false;
+            if let Some(actual) = helpers::string_length(item, self.converter) {
+                return actual >= self.limit;
             }
         }
         true
     }
 
-    fn validate<'a>(&self, _: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        _: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        _: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::String(item) = instance {
-            if (item.chars().count() as u64) < self.limit {
-                return error(ValidationError::min_length(instance, self.limit));
+            let actual = helpers::string_length(item, self.converter);
+            if let Some(actual) = actual.filter(|actual| *actual < self.limit) {
+                return error(ValidationError::min_length(
+                    self.schema_path.clone(),
+                    instance_path.clone(),
+                    instance,
+                    self.limit,
+                    actual,
+                    self.converter.is_some(),
+                ));
             }
         }
         no_error()
     }
 }
 
-impl ToString for MinLengthValidator {
-    fn to_string(&self) -> String {
-        format!("minLength: {}", self.limit)
+impl fmt::Display for MinLengthValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "minLength: {}", self.limit)
     }
 }
 
 #[inline]
 pub(crate) fn compile(
-    _: &Map<String, Value>,
+    parent: &Map<String, Value>,
     schema: &Value,
-    _: &CompilationContext,
+    context: &CompilationContext,
 ) -> Option<CompilationResult> {
-    Some(MinLengthValidator::compile(schema))
+    Some(MinLengthValidator::compile(
+        schema,
+        helpers::content_encoding_converter(parent, context),
+        context.schema_path.clone(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests_util;
+    use serde_json::{json, Value};
+    use test_case::test_case;
+
+    // "aGVsbG8=" is "hello" in base64
+    #[test_case(&json!({"minLength": 5, "contentEncoding": "base64"}), &json!("aGVsbG8="))]
+    #[test_case(&json!({"minLength": 8}), &json!("aGVsbG8="))]
+    fn valid(schema: &Value, instance: &Value) {
+        tests_util::is_valid(schema, instance)
+    }
+
+    #[test_case(&json!({"minLength": 6, "contentEncoding": "base64"}), &json!("aGVsbG8="))]
+    #[test_case(&json!({"minLength": 6, "contentEncoding": "base64", "contentMediaType": "text/plain"}), &json!("aGVsbG8="))]
+    #[test_case(&json!({"minLength": 3, "contentEncoding": "unknown"}), &json!("ab"); "unknown encoding")]
+    fn invalid(schema: &Value, instance: &Value) {
+        tests_util::is_not_valid(schema, instance)
+    }
+
+    #[test_case(&json!({"minLength": 6, "contentEncoding": "base64"}), &json!("aGVsbG8="), r#"'"aGVsbG8="' is shorter than 6 bytes (actual: 5)"#)]
+    #[test_case(&json!({"minLength": 9}), &json!("aGVsbG8="), r#"'"aGVsbG8="' is shorter than 9 characters (actual: 8)"#)]
+    fn error_message(schema: &Value, instance: &Value, expected: &str) {
+        tests_util::expect_errors(schema, instance, &[expected])
+    }
+
+    #[test]
+    fn not_decodable_content() {
+        // Reported by `contentEncoding` only
+        let schema = json!({"minLength": 20, "contentEncoding": "base64"});
+        let compiled = crate::JSONSchema::compile(&schema).unwrap();
+        let instance = json!("not base64");
+        let errors: Vec<_> = compiled.validate(&instance).unwrap_err().collect();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].is_content_encoding_error())
+    }
 }
@@ -1,28 +1,29 @@
 use crate::{
     compilation::{context::CompilationContext, JSONSchema},
-    error::{error, no_error, CompilationError, ErrorIterator, ValidationError},
-    keywords::CompilationResult,
+    error::{error, no_error, ErrorIterator, ValidationError},
+    keywords::{helpers, CompilationResult},
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use serde_json::{Map, Value};
+use std::fmt;
 
 pub(crate) struct MinPropertiesValidator {
     limit: u64,
+    schema_path: JSONPointer,
 }
 
 impl MinPropertiesValidator {
     #[inline]
-    pub(crate) fn compile(schema: &Value) -> CompilationResult {
-        if let Some(limit) = schema.as_u64() {
-            Ok(Box::new(MinPropertiesValidator { limit }))
-        } else {
-            Err(CompilationError::SchemaError)
-        }
+    pub(crate) fn compile(schema: &Value, schema_path: JSONPointer) -> CompilationResult {
+        let limit = helpers::limit(schema)?;
+        Ok(Box::new(MinPropertiesValidator { limit, schema_path }))
     }
 }
 
 impl Validate for MinPropertiesValidator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         if let Value::Object(item) = instance {
             if (item.len() as u64) < self.limit {
                 return false;
@@ -31,19 +32,32 @@ impl Validate for MinPropertiesValidator {
         true
     }
 
-    fn validate<'a>(&self, _: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        _: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        _: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::Object(item) = instance {
-            if (item.len() as u64) < self.limit {
-                return error(ValidationError::min_properties(instance, self.limit));
+            let actual = item.len() as u64;
+            if actual < self.limit {
+                return error(ValidationError::min_properties(
+                    self.schema_path.clone(),
+                    instance_path.clone(),
+                    instance,
+                    self.limit,
+                    actual,
+                ));
             }
         }
         no_error()
     }
 }
 
-impl ToString for MinPropertiesValidator {
-    fn to_string(&self) -> String {
-        format!("minProperties: {}", self.limit)
+impl fmt::Display for MinPropertiesValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "minProperties: {}", self.limit)
     }
 }
 
@@ -51,7 +65,31 @@ impl ToString for MinPropertiesValidator {
 pub(crate) fn compile(
     _: &Map<String, Value>,
     schema: &Value,
-    _: &CompilationContext,
+    context: &CompilationContext,
 ) -> Option<CompilationResult> {
-    Some(MinPropertiesValidator::compile(schema))
+    Some(MinPropertiesValidator::compile(
+        schema,
+        context.schema_path.clone(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests_util;
+    use serde_json::{json, Value};
+    use test_case::test_case;
+
+    #[test_case(&json!({"a": 1, "b": 2}))]
+    #[test_case(&json!({"a": 1, "b": 2, "c": 3, "d": 4, "e": 5}))]
+    #[test_case(&json!([1]); "not an object")]
+    fn within_range(instance: &Value) {
+        tests_util::is_valid(&json!({"minProperties": 2, "maxProperties": 5}), instance)
+    }
+
+    #[test_case(&json!({}))]
+    #[test_case(&json!({"a": 1}))]
+    #[test_case(&json!({"a": 1, "b": 2, "c": 3, "d": 4, "e": 5, "f": 6}))]
+    fn out_of_range(instance: &Value) {
+        tests_util::is_not_valid(&json!({"minProperties": 2, "maxProperties": 5}), instance)
+    }
 }
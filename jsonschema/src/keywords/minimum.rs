@@ -2,19 +2,25 @@ use crate::{
     compilation::{context::CompilationContext, JSONSchema},
     error::{error, no_error, CompilationError, ErrorIterator, ValidationError},
     keywords::CompilationResult,
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use num_cmp::NumCmp;
 use serde_json::{Map, Value};
+use std::fmt;
 
 pub(crate) struct MinimumU64Validator {
     limit: u64,
+    schema_path: JSONPointer,
 }
 pub(crate) struct MinimumI64Validator {
     limit: i64,
+    schema_path: JSONPointer,
 }
 pub(crate) struct MinimumF64Validator {
     limit: f64,
+    schema_path: JSONPointer,
 }
 
 macro_rules! validate {
@@ -24,15 +30,22 @@ macro_rules! validate {
                 &self,
                 schema: &'a JSONSchema,
                 instance: &'a Value,
+                instance_path: &JSONPointer,
+                ctx: &'a ValidationContext,
             ) -> ErrorIterator<'a> {
-                if self.is_valid(schema, instance) {
+                if self.is_valid(schema, instance, ctx) {
                     no_error()
                 } else {
-                    error(ValidationError::minimum(instance, self.limit as f64)) // do not cast
+                    error(ValidationError::minimum(
+                        self.schema_path.clone(),
+                        instance_path.clone(),
+                        instance,
+                        self.limit.into(),
+                    ))
                 }
             }
 
-            fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+            fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
                 if let Value::Number(item) = instance {
                     return if let Some(item) = item.as_u64() {
                         !NumCmp::num_lt(item, self.limit)
@@ -46,9 +59,9 @@ macro_rules! validate {
                 true
             }
         }
-        impl ToString for $validator {
-            fn to_string(&self) -> String {
-                format!("minimum: {}", self.limit)
+        impl fmt::Display for $validator {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "minimum: {}", self.limit)
             }
         }
     };
@@ -58,7 +71,7 @@ validate!(MinimumU64Validator);
 validate!(MinimumI64Validator);
 
 impl Validate for MinimumF64Validator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         if let Value::Number(item) = instance {
             return if let Some(item) = item.as_u64() {
                 !NumCmp::num_lt(item, self.limit)
@@ -72,17 +85,28 @@ impl Validate for MinimumF64Validator {
         true
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        if self.is_valid(schema, instance) {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
             no_error()
         } else {
-            error(ValidationError::minimum(instance, self.limit))
+            error(ValidationError::minimum(
+                self.schema_path.clone(),
+                instance_path.clone(),
+                instance,
+                self.limit.into(),
+            ))
         }
     }
 }
-impl ToString for MinimumF64Validator {
-    fn to_string(&self) -> String {
-        format!("minimum: {}", self.limit)
+impl fmt::Display for MinimumF64Validator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "minimum: {}", self.limit)
     }
 }
 
@@ -90,16 +114,17 @@ impl ToString for MinimumF64Validator {
 pub(crate) fn compile(
     _: &Map<String, Value>,
     schema: &Value,
-    _: &CompilationContext,
+    context: &CompilationContext,
 ) -> Option<CompilationResult> {
     if let Value::Number(limit) = schema {
+        let schema_path = context.schema_path.clone();
         if let Some(limit) = limit.as_u64() {
-            Some(Ok(Box::new(MinimumU64Validator { limit })))
+            Some(Ok(Box::new(MinimumU64Validator { limit, schema_path })))
         } else if let Some(limit) = limit.as_i64() {
-            Some(Ok(Box::new(MinimumI64Validator { limit })))
+            Some(Ok(Box::new(MinimumI64Validator { limit, schema_path })))
         } else {
             let limit = limit.as_f64().expect("Always valid");
-            Some(Ok(Box::new(MinimumF64Validator { limit })))
+            Some(Ok(Box::new(MinimumF64Validator { limit, schema_path })))
         }
     } else {
         Some(Err(CompilationError::SchemaError))
@@ -114,7 +139,24 @@ mod tests {
 
     #[test_case(&json!({"minimum": 1u64 << 54}), &json!((1u64 << 54) - 1))]
     #[test_case(&json!({"minimum": 1i64 << 54}), &json!((1i64 << 54) - 1))]
+    #[test_case(&json!({"minimum": u64::MAX}), &json!(u64::MAX - 1))]
+    #[test_case(&json!({"minimum": i64::MIN + 1}), &json!(i64::MIN))]
     fn is_not_valid(schema: &Value, instance: &Value) {
         tests_util::is_not_valid(schema, instance)
     }
+
+    #[test_case(&json!({"minimum": u64::MAX - 1}), &json!(u64::MAX - 1))]
+    #[test_case(&json!({"minimum": i64::MIN}), &json!(i64::MIN + 1))]
+    fn is_valid(schema: &Value, instance: &Value) {
+        tests_util::is_valid(schema, instance)
+    }
+
+    #[test]
+    fn exact_limit_in_message() {
+        tests_util::expect_errors(
+            &json!({"minimum": (1u64 << 54) + 1}),
+            &json!(1u64 << 54),
+            &["18014398509481984 is less than the minimum of 18014398509481985"],
+        )
+    }
 }
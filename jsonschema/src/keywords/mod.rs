@@ -6,14 +6,16 @@ pub(crate) mod boolean;
 pub(crate) mod const_;
 pub(crate) mod contains;
 pub(crate) mod content;
+pub(crate) mod custom;
 pub(crate) mod dependencies;
 pub(crate) mod enum_;
 pub(crate) mod exclusive_maximum;
 pub(crate) mod exclusive_minimum;
 pub(crate) mod format;
-pub(crate) mod helpers;
+pub mod helpers;
 pub(crate) mod if_;
 pub(crate) mod items;
+pub(crate) mod lazy;
 pub(crate) mod legacy;
 pub(crate) mod max_items;
 pub(crate) mod max_length;
@@ -25,6 +27,7 @@ pub(crate) mod min_properties;
 pub(crate) mod minimum;
 pub(crate) mod multiple_of;
 pub(crate) mod not;
+pub(crate) mod nullable;
 pub(crate) mod one_of;
 pub(crate) mod pattern;
 pub(crate) mod pattern_properties;
@@ -34,12 +37,32 @@ pub(crate) mod ref_;
 pub(crate) mod required;
 pub(crate) mod type_;
 pub(crate) mod unique_items;
-use crate::{error, validator::Validate};
+use crate::{
+    compilation::JSONSchema, error, output::PartialApplication, paths::JSONPointer,
+    validation_context::ValidationContext, validator::ValidateDisplay,
+};
+use serde_json::Value;
 
 pub(crate) type CompilationResult = Result<BoxedValidator, error::CompilationError>;
-pub(crate) type BoxedValidator = Box<dyn Validate + Send + Sync>;
+pub(crate) type BoxedValidator = Box<dyn ValidateDisplay + Send + Sync>;
 pub(crate) type Validators = Vec<BoxedValidator>;
 
+/// Apply all `validators` to `instance` and combine their results.
+#[inline]
+pub(crate) fn apply_validators<'a>(
+    validators: &[BoxedValidator],
+    schema: &'a JSONSchema,
+    instance: &'a Value,
+    instance_path: &JSONPointer,
+    ctx: &'a ValidationContext,
+) -> PartialApplication<'a> {
+    let mut result = PartialApplication::valid_empty();
+    for validator in validators {
+        result.merge(validator.apply(schema, instance, instance_path, ctx));
+    }
+    result
+}
+
 fn format_validators(validators: &[BoxedValidator]) -> String {
     match validators.len() {
         0 => "{}".to_string(),
@@ -82,7 +105,10 @@ fn format_key_value_validators(validators: &[(String, Validators)]) -> String {
 #[cfg(test)]
 mod tests {
     use crate::compilation::JSONSchema;
+    use num_cmp::NumCmp;
+    use proptest::prelude::*;
     use serde_json::{json, Value};
+    use std::cmp::Ordering;
     use test_case::test_case;
 
     #[test_case(&json!({"additionalItems": false, "items": [{"type": "string"}]}), "additionalItems: false")]
@@ -105,6 +131,7 @@ mod tests {
     #[test_case(&json!({"contentEncoding": "base64"}), "contentEncoding: base64")]
     #[test_case(&json!({"contentEncoding": "base64", "contentMediaType": "application/json"}), "{contentMediaType: application/json, contentEncoding: base64}")]
     #[test_case(&json!({"dependencies": {"bar": ["foo"]}}), "dependencies: {bar: {required: [foo]}}")]
+    #[test_case(&json!({"dependencies": {"bar": {"type": "integer"}}}), "dependencies: {bar: {type: integer}}")]
     #[test_case(&json!({"enum": [1]}), "enum: [1]")]
     #[test_case(&json!({"exclusiveMaximum": 1}), "exclusiveMaximum: 1")]
     #[test_case(&json!({"exclusiveMinimum": 1}), "exclusiveMinimum: 1")]
@@ -129,6 +156,7 @@ mod tests {
     #[test_case(&json!({"if": {"exclusiveMaximum": 0}, "else": {"minimum": -10}}), "if: {exclusiveMaximum: 0}, else: {minimum: -10}")]
     #[test_case(&json!({"if": {"exclusiveMaximum": 0}, "then": {"minimum": -10}, "else": {"multipleOf": 2}}), "if: {exclusiveMaximum: 0}, then: {minimum: -10}, else: {multipleOf: 2}")]
     #[test_case(&json!({"items": [{"type": "string"}]}), "items: [{type: string}]")]
+    #[test_case(&json!({"items": false}), "items: false")]
     #[test_case(&json!({"items": {"type": "integer"}}), "items: {type: integer}")]
     #[test_case(&json!({"items": {"type": "integer", "minimum": 4}}), "items: {minimum: 4, type: integer}")]
     #[test_case(&json!({"maxItems": 1}), "maxItems: 1")]
@@ -158,6 +186,7 @@ mod tests {
     #[test_case(&json!({"type": "number"}), "type: number")]
     #[test_case(&json!({"type": "integer"}), "type: integer")]
     #[test_case(&json!({"type": "integer", "$schema": "http://json-schema.org/draft-04/schema#"}), "type: integer")]
+    #[test_case(&json!({"type": ["integer"]}), "type: integer"; "single item array")]
     #[test_case(&json!({"type": ["integer", "null"]}), "type: [integer, null]")]
     #[test_case(&json!({"type": ["integer", "null"], "$schema": "http://json-schema.org/draft-04/schema#"}), "type: [integer, null]")]
     #[test_case(&json!({"uniqueItems": true}), "uniqueItems: true")]
@@ -178,17 +207,19 @@ mod tests {
     #[test_case(&json!({"exclusiveMinimum": 1.1}), &json!(1.1), r#"1.1 is less than or equal to the minimum of 1.1"#)]
     #[test_case(&json!({"format": "ipv4"}), &json!("2001:0db8:85a3:0000:0000:8a2e:0370:7334"), r#"'"2001:0db8:85a3:0000:0000:8a2e:0370:7334"' is not a 'ipv4'"#)]
     #[test_case(&json!({"maximum": 3.0}), &json!(3.5), r#"3.5 is greater than the maximum of 3"#)]
-    #[test_case(&json!({"maxItems": 2}), &json!([1, 2, 3]), r#"[1,2,3] has more than 2 items"#)]
-    #[test_case(&json!({"maxLength": 2}), &json!("foo"), r#"'"foo"' is longer than 2 characters"#)]
-    #[test_case(&json!({"maxProperties": 2}), &json!({"foo": 1, "bar": 2, "baz": 3}), r#"{"bar":2,"baz":3,"foo":1} has more than 2 properties"#)]
+    #[test_case(&json!({"maxItems": 2}), &json!([1, 2, 3]), r#"[1,2,3] has more than 2 items (actual: 3)"#)]
+    #[test_case(&json!({"maxLength": 2}), &json!("foo"), r#"'"foo"' is longer than 2 characters (actual: 3)"#)]
+    #[test_case(&json!({"maxProperties": 2}), &json!({"foo": 1, "bar": 2, "baz": 3}), r#"{"bar":2,"baz":3,"foo":1} has more than 2 properties (actual: 3)"#)]
     #[test_case(&json!({"minimum": 1.1}), &json!(0.6), r#"0.6 is less than the minimum of 1.1"#)]
-    #[test_case(&json!({"minItems": 1}), &json!([]), r#"[] has less than 1 item"#)]
-    #[test_case(&json!({"minLength": 2}), &json!("f"), r#"'"f"' is shorter than 2 characters"#)]
-    #[test_case(&json!({"minProperties": 1}), &json!({}), r#"{} has less than 1 property"#)]
+    #[test_case(&json!({"minItems": 1}), &json!([]), r#"[] has less than 1 item (actual: 0)"#)]
+    #[test_case(&json!({"minLength": 2}), &json!("f"), r#"'"f"' is shorter than 2 characters (actual: 1)"#)]
+    #[test_case(&json!({"minProperties": 1}), &json!({}), r#"{} has less than 1 property (actual: 0)"#)]
     #[test_case(&json!({"multipleOf": 2}), &json!(7), r#"7 is not a multiple of 2"#)]
-    #[test_case(&json!({"not": {"type": "integer"}}), &json!(1), r#"{"type":"integer"} is not allowed for 1"#)]
+    #[test_case(&json!({"not": {"type": "integer"}}), &json!(1), r#"1 must NOT match: (type: integer)"#)]
+    #[test_case(&json!({"not": {"type": "string", "minLength": 5}}), &json!("abcdef"), r#""abcdef" must NOT match: (minLength: 5 AND type: string)"#)]
+    #[test_case(&json!({"not": {}}), &json!(null), r#"null must NOT match: ({})"#)]
     #[test_case(&json!({"oneOf": [{"type": "integer"}, {"minimum": 2}]}), &json!(1.1), r#"'1.1' is not valid under any of the given schemas"#)]
-    #[test_case(&json!({"oneOf": [{"type": "integer"}, {"minimum": 2}]}), &json!(3), r#"'3' is valid under more than one of the given schemas"#)]
+    #[test_case(&json!({"oneOf": [{"type": "integer"}, {"minimum": 2}]}), &json!(3), r#"'3' is valid under more than one of the given schemas (matched: 0, 1)"#)]
     #[test_case(&json!({"pattern": "^a*$"}), &json!("abc"), r#"'"abc"' does not match '^a*$'"#)]
     #[test_case(&json!({"properties": {"foo": {}, "bar": {}}, "required": ["foo"]}), &json!({"bar": 1}), r#"'foo' is a required property"#)]
     #[test_case(&json!({"type": "integer"}), &json!(1.1), r#"'1.1' is not of type 'integer'"#)]
@@ -203,9 +234,37 @@ mod tests {
                 schema, instance
             ))
             .collect();
+        assert_eq!(errors[0].message().to_string(), expected);
+    }
+
+    #[test_case(&json!({"properties": {"foo": {"minLength": 3}}}), &json!({"foo": "a"}), r#"/properties/foo/minLength: '"a"' is shorter than 3 characters (actual: 1)"#)]
+    #[test_case(&json!({"allOf": [{"type": "string"}, {"maximum": 2}]}), &json!(3), r#"/allOf: '3' is not valid under all of the given schemas"#)]
+    #[test_case(&json!({"items": [{}, {"type": "string"}]}), &json!([1, 2]), r#"/items/1/type: '2' is not of type 'string'"#)]
+    #[test_case(&json!({"properties": {"foo": {}}, "additionalProperties": false}), &json!({"bar": 1}), r#"/additionalProperties: False schema does not allow '"bar"'"#)]
+    #[test_case(&json!({"patternProperties": {"a/b": {"type": "string"}}}), &json!({"a/b": 1}), r#"/patternProperties/a~1b/type: '1' is not of type 'string'"#)]
+    #[test_case(&json!({"definitions": {"foo": {"type": "string"}}, "$ref": "#/definitions/foo"}), &json!(1), r#"/$ref/type: '1' is not of type 'string'"#)]
+    #[test_case(&json!({"if": {"type": "integer"}, "then": {"minimum": 5}}), &json!(1), r#"/then: '1' is valid under the 'if' schema, but not under the 'then' schema"#)]
+    fn error_schema_path(schema: &Value, instance: &Value, expected: &str) {
+        let compiled = JSONSchema::compile(schema).unwrap();
+        let errors: Vec<_> = compiled.validate(instance).unwrap_err().collect();
         assert_eq!(errors[0].to_string(), expected);
     }
 
+    #[test_case(&json!({"minLength": 3}), &json!("a"), "")]
+    #[test_case(&json!({"properties": {"foo": {"properties": {"bar": {"type": "string"}}}}}), &json!({"foo": {"bar": 1}}), "/foo/bar")]
+    #[test_case(&json!({"items": {"type": "string"}}), &json!(["a", 1]), "/1")]
+    #[test_case(&json!({"items": [{}, {"type": "string"}]}), &json!([1, 2]), "/1")]
+    #[test_case(&json!({"items": [{}], "additionalItems": {"type": "string"}}), &json!([1, 2]), "/1")]
+    #[test_case(&json!({"patternProperties": {"^f": {"type": "string"}}}), &json!({"f/o": 1}), "/f~1o")]
+    #[test_case(&json!({"additionalProperties": {"type": "string"}}), &json!({"foo": 1}), "/foo")]
+    #[test_case(&json!({"additionalProperties": false}), &json!({"foo": 1}), "")]
+    #[test_case(&json!({"items": {"allOf": [{"minimum": 5}]}}), &json!([1]), "/0")]
+    fn error_instance_path(schema: &Value, instance: &Value, expected: &str) {
+        let compiled = JSONSchema::compile(schema).unwrap();
+        let errors: Vec<_> = compiled.validate(instance).unwrap_err().collect();
+        assert_eq!(errors[0].instance_path().to_string(), expected);
+    }
+
     // Extra cases not covered by JSON test suite
     #[test_case(&json!({"additionalProperties": {"type": "string"}}))]
     #[test_case(&json!({"additionalProperties": {"type": "string"}, "properties": {"foo": {}}}))]
@@ -279,4 +338,34 @@ mod tests {
         let compiled = JSONSchema::compile(schema).unwrap();
         assert!(compiled.is_valid(instance))
     }
+
+    /// Check all bound keywords against the expected ordering of `instance` relative to `limit`.
+    fn check_bounds(limit: &Value, instance: &Value, ordering: Ordering) {
+        for (keyword, expected) in &[
+            ("minimum", ordering != Ordering::Less),
+            ("maximum", ordering != Ordering::Greater),
+            ("exclusiveMinimum", ordering == Ordering::Greater),
+            ("exclusiveMaximum", ordering == Ordering::Less),
+        ] {
+            let schema = json!({ *keyword: limit });
+            let compiled = JSONSchema::compile(&schema).unwrap();
+            assert_eq!(compiled.is_valid(instance), *expected, "{}", schema);
+            assert_eq!(compiled.validate(instance).is_ok(), *expected, "{}", schema);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn float_bounds(limit in prop::num::f64::NORMAL | prop::num::f64::SUBNORMAL | prop::num::f64::ZERO, value in prop::num::f64::NORMAL | prop::num::f64::SUBNORMAL | prop::num::f64::ZERO) {
+            let ordering = value.partial_cmp(&limit).unwrap();
+            check_bounds(&json!(limit), &json!(value), ordering);
+        }
+
+        #[test]
+        fn mixed_bounds(limit in prop::num::f64::NORMAL | prop::num::f64::ZERO, value in any::<i64>()) {
+            // Casting `value` to `f64` may round it, therefore the ordering is computed exactly
+            let ordering = value.num_cmp(limit).unwrap();
+            check_bounds(&json!(limit), &json!(value), ordering);
+        }
+    }
 }
@@ -2,116 +2,264 @@ use crate::{
     compilation::{context::CompilationContext, JSONSchema},
     error::{error, no_error, CompilationError, ErrorIterator, ValidationError},
     keywords::CompilationResult,
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
-use serde_json::{Map, Value};
-use std::f64::EPSILON;
+use serde_json::{Map, Number, Value};
+use std::{f64::EPSILON, fmt};
+
+/// Whether the quotient is close enough to an integer.
+/// Division of floats is not exact (e.g. `0.3 / 0.1 == 2.9999999999999996`), therefore the error
+/// is compared with `EPSILON` scaled to the quotient's magnitude.
+#[inline]
+fn is_float_multiple_of(item: f64, multiple_of: f64) -> bool {
+    let quotient = item / multiple_of;
+    if quotient.is_infinite() {
+        // Overflow, e.g. `1e308 / 0.5`. Rounding would give NaN, but such a big quotient is an
+        // integer as long as `multiple_of` divides one
+        return (1. / multiple_of).fract() == 0.;
+    }
+    (quotient - quotient.round()).abs() < EPSILON * quotient.abs().max(1.)
+}
 
 pub(crate) struct MultipleOfFloatValidator {
     multiple_of: f64,
+    schema_path: JSONPointer,
 }
 
 impl MultipleOfFloatValidator {
     #[inline]
-    pub(crate) fn compile(multiple_of: f64) -> CompilationResult {
-        Ok(Box::new(MultipleOfFloatValidator { multiple_of }))
+    pub(crate) fn compile(multiple_of: f64, schema_path: JSONPointer) -> CompilationResult {
+        Ok(Box::new(MultipleOfFloatValidator {
+            multiple_of,
+            schema_path,
+        }))
     }
 }
 
 impl Validate for MultipleOfFloatValidator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         if let Value::Number(item) = instance {
             let item = item.as_f64().expect("Always valid");
-            let remainder = (item / self.multiple_of) % 1.;
-            if !(remainder < EPSILON && remainder < (1. - EPSILON)) {
+            if !is_float_multiple_of(item, self.multiple_of) {
                 return false;
             }
         }
         true
     }
 
-    fn validate<'a>(&self, _: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        _: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        _: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::Number(item) = instance {
             let item = item.as_f64().expect("Always valid");
-            let remainder = (item / self.multiple_of) % 1.;
-            if !(remainder < EPSILON && remainder < (1. - EPSILON)) {
-                return error(ValidationError::multiple_of(instance, self.multiple_of));
+            if !is_float_multiple_of(item, self.multiple_of) {
+                return error(ValidationError::multiple_of(
+                    self.schema_path.clone(),
+                    instance_path.clone(),
+                    instance,
+                    self.multiple_of,
+                ));
             }
         }
         no_error()
     }
 }
 
-impl ToString for MultipleOfFloatValidator {
-    fn to_string(&self) -> String {
-        format!("multipleOf: {}", self.multiple_of)
+impl fmt::Display for MultipleOfFloatValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "multipleOf: {}", self.multiple_of)
     }
 }
 
 pub(crate) struct MultipleOfIntegerValidator {
     multiple_of: f64,
+    // The exact value, if `multipleOf` is written as an integer literal
+    exact: Option<u64>,
+    schema_path: JSONPointer,
 }
 
 impl MultipleOfIntegerValidator {
     #[inline]
-    pub(crate) fn compile(multiple_of: f64) -> CompilationResult {
-        Ok(Box::new(MultipleOfIntegerValidator { multiple_of }))
+    pub(crate) fn compile(
+        multiple_of: f64,
+        exact: Option<u64>,
+        schema_path: JSONPointer,
+    ) -> CompilationResult {
+        Ok(Box::new(MultipleOfIntegerValidator {
+            multiple_of,
+            exact,
+            schema_path,
+        }))
+    }
+
+    fn is_multiple(&self, item: &Number) -> bool {
+        if let Some(exact) = self.exact {
+            // Integers beyond 2^53 can't be represented by f64 exactly
+            if let Some(item) = item.as_u64() {
+                return item % exact == 0;
+            }
+            if let Some(item) = item.as_i64() {
+                return i128::from(item) % i128::from(exact) == 0;
+            }
+        }
+        let item = item.as_f64().expect("Always valid");
+        if item.fract() == 0. {
+            (item % self.multiple_of) == 0.
+        } else {
+            is_float_multiple_of(item, self.multiple_of)
+        }
     }
 }
 
 impl Validate for MultipleOfIntegerValidator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         if let Value::Number(item) = instance {
-            let item = item.as_f64().expect("Always valid");
-            let is_multiple = if item.fract() == 0. {
-                (item % self.multiple_of) == 0.
-            } else {
-                let remainder = (item / self.multiple_of) % 1.;
-                remainder < EPSILON && remainder < (1. - EPSILON)
-            };
-            if !is_multiple {
-                return false;
-            }
+            self.is_multiple(item)
+        } else {
+            true
         }
-        true
     }
 
-    fn validate<'a>(&self, _: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        _: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        _: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::Number(item) = instance {
-            let item = item.as_f64().expect("Always valid");
-            let is_multiple = if item.fract() == 0. {
-                (item % self.multiple_of) == 0.
-            } else {
-                let remainder = (item / self.multiple_of) % 1.;
-                remainder < EPSILON && remainder < (1. - EPSILON)
-            };
-            if !is_multiple {
-                return error(ValidationError::multiple_of(instance, self.multiple_of));
+            if !self.is_multiple(item) {
+                return error(ValidationError::multiple_of(
+                    self.schema_path.clone(),
+                    instance_path.clone(),
+                    instance,
+                    self.multiple_of,
+                ));
             }
         }
         no_error()
     }
 }
 
-impl ToString for MultipleOfIntegerValidator {
-    fn to_string(&self) -> String {
-        format!("multipleOf: {}", self.multiple_of)
+impl fmt::Display for MultipleOfIntegerValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "multipleOf: {}", self.multiple_of)
     }
 }
 #[inline]
 pub(crate) fn compile(
     _: &Map<String, Value>,
     schema: &Value,
-    _: &CompilationContext,
+    context: &CompilationContext,
 ) -> Option<CompilationResult> {
-    if let Value::Number(multiple_of) = schema {
-        let multiple_of = multiple_of.as_f64().expect("Always valid");
+    if let Value::Number(number) = schema {
+        let multiple_of = number.as_f64().expect("Always valid");
+        // Must be strictly greater than zero, otherwise the remainder is not defined
+        if multiple_of <= 0. {
+            return Some(Err(CompilationError::SchemaError));
+        }
         if multiple_of.fract() == 0. {
-            Some(MultipleOfIntegerValidator::compile(multiple_of))
+            Some(MultipleOfIntegerValidator::compile(
+                multiple_of,
+                number.as_u64(),
+                context.schema_path.clone(),
+            ))
         } else {
-            Some(MultipleOfFloatValidator::compile(multiple_of))
+            Some(MultipleOfFloatValidator::compile(
+                multiple_of,
+                context.schema_path.clone(),
+            ))
         }
     } else {
         Some(Err(CompilationError::SchemaError))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{tests_util, JSONSchema};
+    use proptest::prelude::*;
+    use serde_json::{json, Value};
+    use test_case::test_case;
+
+    #[test_case(&json!({"multipleOf": 0.1}), &json!(0.3))]
+    #[test_case(&json!({"multipleOf": 0.1}), &json!(0.6))]
+    #[test_case(&json!({"multipleOf": 0.1}), &json!(0.9))]
+    #[test_case(&json!({"multipleOf": 0.1}), &json!(-0.3); "negative")]
+    #[test_case(&json!({"multipleOf": 0.0001}), &json!(0.0075))]
+    #[test_case(&json!({"multipleOf": 1.5}), &json!(4.5))]
+    #[test_case(&json!({"multipleOf": 0.5}), &json!(1e308); "overflow")]
+    #[test_case(&json!({"multipleOf": 0.5}), &json!(-1e308); "negative overflow")]
+    fn multiple_of_is_valid(schema: &Value, instance: &Value) {
+        tests_util::is_valid(schema, instance)
+    }
+
+    #[test_case(&json!({"multipleOf": 0.1}), &json!(0.35))]
+    #[test_case(&json!({"multipleOf": 0.1}), &json!(-0.35); "negative")]
+    #[test_case(&json!({"multipleOf": 0.0001}), &json!(0.00751))]
+    #[test_case(&json!({"multipleOf": 2}), &json!(4.5))]
+    #[test_case(&json!({"multipleOf": 0.123456789}), &json!(1e308))]
+    fn multiple_of_is_not_valid(schema: &Value, instance: &Value) {
+        tests_util::is_not_valid(schema, instance)
+    }
+
+    #[test_case(&json!(0))]
+    #[test_case(&json!(0.0))]
+    #[test_case(&json!(-2))]
+    #[test_case(&json!(-0.5))]
+    fn non_positive_is_schema_error(multiple_of: &Value) {
+        let schema = json!({ "multipleOf": multiple_of });
+        assert!(JSONSchema::compile(&schema).is_err())
+    }
+
+    #[test_case(&json!(i64::MAX), 7, true)]
+    #[test_case(&json!(i64::MAX), 2, false)]
+    #[test_case(&json!(i64::MAX - 1), 2, true)]
+    #[test_case(&json!(i64::MIN), 2, true)]
+    #[test_case(&json!(i64::MIN), 3, false)]
+    #[test_case(&json!(i64::MIN + 1), 7, true)]
+    #[test_case(&json!(u64::MAX), 5, true)]
+    #[test_case(&json!(u64::MAX), 2, false)]
+    #[test_case(&json!(9_007_199_254_740_993_u64), 3, true; "beyond f64 precision")]
+    #[test_case(&json!(9_007_199_254_740_993_u64), 2, false; "odd beyond f64 precision")]
+    #[test_case(&json!(4.0), 2, true)]
+    fn exact_integer_multiples(instance: &Value, multiple_of: u64, expected: bool) {
+        let schema = json!({ "multipleOf": multiple_of });
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        assert_eq!(compiled.is_valid(instance), expected);
+        assert_eq!(compiled.validate(instance).is_ok(), expected);
+    }
+
+    proptest! {
+        #[test]
+        fn integer_multiples_are_valid(multiplier in -1_000_000i64..1_000_000, divisor in 0.001f64..1000.) {
+            let schema = json!({"multipleOf": divisor});
+            let compiled = JSONSchema::compile(&schema).unwrap();
+            prop_assert!(compiled.is_valid(&json!(multiplier as f64 * divisor)));
+        }
+
+        #[test]
+        fn half_multiples_are_not_valid(multiplier in -1_000_000i64..1_000_000, divisor in 0.001f64..1000.) {
+            let schema = json!({"multipleOf": divisor});
+            let compiled = JSONSchema::compile(&schema).unwrap();
+            let instance = json!((multiplier as f64 + 0.5) * divisor);
+            prop_assert!(!compiled.is_valid(&instance));
+            prop_assert!(compiled.validate(&instance).is_err());
+        }
+
+        #[test]
+        fn any_float_does_not_panic(value in any::<f64>(), divisor in prop::num::f64::POSITIVE | prop::num::f64::NORMAL | prop::num::f64::SUBNORMAL) {
+            // Non-finite values are converted to `null` by `serde_json`
+            let schema = json!({"multipleOf": divisor});
+            let compiled = JSONSchema::compile(&schema).unwrap();
+            let instance = json!(value);
+            prop_assert_eq!(compiled.is_valid(&instance), compiled.validate(&instance).is_ok());
+        }
+    }
+}
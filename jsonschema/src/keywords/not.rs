@@ -2,46 +2,72 @@ use crate::{
     compilation::{compile_validators, context::CompilationContext, JSONSchema},
     error::{error, no_error, ErrorIterator, ValidationError},
     keywords::{format_validators, CompilationResult, Validators},
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use serde_json::{Map, Value};
+use std::fmt;
 
 pub(crate) struct NotValidator {
-    // needed only for error representation
-    original: Value,
     validators: Validators,
+    // Needed only for error messages, e.g. `type: string AND minLength: 5`
+    description: String,
+    schema_path: JSONPointer,
 }
 
 impl NotValidator {
     #[inline]
     pub(crate) fn compile(schema: &Value, context: &CompilationContext) -> CompilationResult {
+        let validators = compile_validators(schema, context)?;
+        let description = if validators.is_empty() {
+            "{}".to_string()
+        } else {
+            validators
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(" AND ")
+        };
         Ok(Box::new(NotValidator {
-            original: schema.clone(),
-            validators: compile_validators(schema, context)?,
+            validators,
+            description,
+            schema_path: context.schema_path.clone(),
         }))
     }
 }
 
 impl Validate for NotValidator {
-    fn is_valid(&self, schema: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
         !self
             .validators
             .iter()
-            .all(|validator| validator.is_valid(schema, instance))
+            .all(|validator| validator.is_valid(schema, instance, ctx))
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        if self.is_valid(schema, instance) {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
             no_error()
         } else {
-            error(ValidationError::not(instance, self.original.clone()))
+            error(ValidationError::not(
+                self.schema_path.clone(),
+                instance_path.clone(),
+                instance,
+                self.description.clone(),
+            ))
         }
     }
 }
 
-impl ToString for NotValidator {
-    fn to_string(&self) -> String {
-        format!("not: {}", format_validators(&self.validators))
+impl fmt::Display for NotValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "not: {}", format_validators(&self.validators))
     }
 }
 
@@ -0,0 +1,102 @@
+use crate::{
+    compilation::JSONSchema,
+    error::{no_error, ErrorIterator},
+    keywords::{format_validators, BoxedValidator, Validators},
+    paths::JSONPointer,
+    validation_context::ValidationContext,
+    validator::Validate,
+};
+use serde_json::Value;
+use std::fmt;
+
+/// Open API 3.0 `nullable: true` support.
+/// `null` is always accepted, any other value is validated by the wrapped validators.
+/// It is equivalent to `{"anyOf": [<schema>, {"type": "null"}]}`.
+pub(crate) struct NullableValidator {
+    validators: Validators,
+}
+
+impl NullableValidator {
+    #[inline]
+    pub(crate) fn compile(validators: Validators) -> BoxedValidator {
+        Box::new(NullableValidator { validators })
+    }
+}
+
+impl Validate for NullableValidator {
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
+        if let Value::Null = instance {
+            true
+        } else {
+            self.validators
+                .iter()
+                .all(|validator| validator.is_valid(schema, instance, ctx))
+        }
+    }
+
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if let Value::Null = instance {
+            no_error()
+        } else {
+            let errors: Vec<_> = self
+                .validators
+                .iter()
+                .flat_map(move |validator| validator.validate(schema, instance, instance_path, ctx))
+                .take(ctx.error_budget())
+                .collect();
+            Box::new(errors.into_iter())
+        }
+    }
+}
+
+impl fmt::Display for NullableValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "nullable: {}", format_validators(&self.validators))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::CompilationOptions;
+    use serde_json::{json, Value};
+    use test_case::test_case;
+
+    #[test_case(&json!({"type": "string", "nullable": true}), &json!(null))]
+    #[test_case(&json!({"type": "string", "nullable": true}), &json!("foo"))]
+    #[test_case(&json!({"type": "integer", "nullable": true, "minimum": 5}), &json!(null))]
+    #[test_case(&json!({"properties": {"a": {"type": "string", "nullable": true}}}), &json!({"a": null}))]
+    // Not supported in Open API 3.0 and therefore ignored
+    #[test_case(&json!({"type": "array", "additionalItems": false, "items": [{}]}), &json!([1, 2]))]
+    #[test_case(&json!({"dependencies": {"a": ["b"]}}), &json!({"a": 1}))]
+    #[test_case(&json!({"patternProperties": {"^f": {"type": "string"}}}), &json!({"foo": 1}))]
+    // Annotation only
+    #[test_case(&json!({"discriminator": {"propertyName": "kind"}}), &json!({}))]
+    fn openapi_30_valid(schema: &Value, instance: &Value) {
+        let compiled = CompilationOptions::openapi_30().compile(schema).unwrap();
+        assert!(compiled.is_valid(instance));
+        assert!(compiled.validate(instance).is_ok());
+    }
+
+    #[test_case(&json!({"type": "string", "nullable": true}), &json!(1))]
+    #[test_case(&json!({"type": "string", "nullable": false}), &json!(null))]
+    #[test_case(&json!({"type": "string"}), &json!(null))]
+    #[test_case(&json!({"maximum": 5, "exclusiveMaximum": true}), &json!(5))]
+    fn openapi_30_invalid(schema: &Value, instance: &Value) {
+        let compiled = CompilationOptions::openapi_30().compile(schema).unwrap();
+        assert!(!compiled.is_valid(instance));
+        assert!(compiled.validate(instance).is_err());
+    }
+
+    #[test]
+    fn nullable_is_ignored_without_openapi_30() {
+        let schema = json!({"type": "string", "nullable": true});
+        let compiled = CompilationOptions::default().compile(&schema).unwrap();
+        assert!(!compiled.is_valid(&json!(null)));
+    }
+}
@@ -1,13 +1,17 @@
 use crate::{
     compilation::{compile_validators, context::CompilationContext, JSONSchema},
-    error::{error, no_error, CompilationError, ErrorIterator, ValidationError},
+    error::{error, no_error, CompilationError, ErrorIterator, SubSchemaErrors, ValidationError},
     keywords::{format_vec_of_validators, CompilationResult, Validators},
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use serde_json::{Map, Value};
+use std::fmt;
 
 pub(crate) struct OneOfValidator {
     schemas: Vec<Validators>,
+    schema_path: JSONPointer,
 }
 
 impl OneOfValidator {
@@ -15,21 +19,29 @@ impl OneOfValidator {
     pub(crate) fn compile(schema: &Value, context: &CompilationContext) -> CompilationResult {
         if let Value::Array(items) = schema {
             let mut schemas = Vec::with_capacity(items.len());
-            for item in items {
-                schemas.push(compile_validators(item, context)?)
+            for (idx, item) in items.iter().enumerate() {
+                schemas.push(compile_validators(item, &context.with_path(idx))?)
             }
-            Ok(Box::new(OneOfValidator { schemas }))
+            Ok(Box::new(OneOfValidator {
+                schemas,
+                schema_path: context.schema_path.clone(),
+            }))
         } else {
             Err(CompilationError::SchemaError)
         }
     }
 
-    fn get_first_valid(&self, schema: &JSONSchema, instance: &Value) -> Option<usize> {
+    fn get_first_valid(
+        &self,
+        schema: &JSONSchema,
+        instance: &Value,
+        ctx: &ValidationContext,
+    ) -> Option<usize> {
         let mut first_valid_idx = None;
         for (idx, validators) in self.schemas.iter().enumerate() {
             if validators
                 .iter()
-                .all(|validator| validator.is_valid(schema, instance))
+                .all(|validator| validator.is_valid(schema, instance, ctx))
             {
                 first_valid_idx = Some(idx);
                 break;
@@ -39,47 +51,108 @@ impl OneOfValidator {
     }
 
     #[allow(clippy::integer_arithmetic)]
-    fn are_others_valid(&self, schema: &JSONSchema, instance: &Value, idx: usize) -> bool {
+    fn are_others_valid(
+        &self,
+        schema: &JSONSchema,
+        instance: &Value,
+        idx: usize,
+        ctx: &ValidationContext,
+    ) -> bool {
         // `idx + 1` will not overflow, because the maximum possible value there is `usize::MAX - 1`
         // For example we have `usize::MAX` schemas and only the last one is valid, then
         // in `get_first_valid` we enumerate from `0`, and on the last index will be `usize::MAX - 1`
         for validators in self.schemas.iter().skip(idx + 1) {
             if validators
                 .iter()
-                .all(|validator| validator.is_valid(schema, instance))
+                .all(|validator| validator.is_valid(schema, instance, ctx))
             {
                 return true;
             }
         }
         false
     }
+
+    /// Indices of all sub-schemas that are valid for the instance, starting from `first_valid_idx`.
+    fn get_all_valid(
+        &self,
+        schema: &JSONSchema,
+        instance: &Value,
+        first_valid_idx: usize,
+        ctx: &ValidationContext,
+    ) -> Vec<usize> {
+        self.schemas
+            .iter()
+            .enumerate()
+            .skip(first_valid_idx)
+            .filter(|(_, validators)| {
+                validators
+                    .iter()
+                    .all(|validator| validator.is_valid(schema, instance, ctx))
+            })
+            .map(|(idx, _)| idx)
+            .collect()
+    }
 }
 
 impl Validate for OneOfValidator {
-    fn is_valid(&self, schema: &JSONSchema, instance: &Value) -> bool {
-        let first_valid_idx = self.get_first_valid(schema, instance);
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
+        let first_valid_idx = self.get_first_valid(schema, instance, ctx);
         if let Some(idx) = first_valid_idx {
-            !self.are_others_valid(schema, instance, idx)
+            !self.are_others_valid(schema, instance, idx, ctx)
         } else {
             false
         }
     }
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        let first_valid_idx = self.get_first_valid(schema, instance);
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        let first_valid_idx = self.get_first_valid(schema, instance, ctx);
         if let Some(idx) = first_valid_idx {
-            if self.are_others_valid(schema, instance, idx) {
-                return error(ValidationError::one_of_multiple_valid(instance));
+            if self.are_others_valid(schema, instance, idx, ctx) {
+                let matched = self.get_all_valid(schema, instance, idx, ctx);
+                return error(ValidationError::one_of_multiple_valid(
+                    self.schema_path.clone(),
+                    instance_path.clone(),
+                    instance,
+                    matched,
+                ));
             }
             no_error()
         } else {
-            error(ValidationError::one_of_not_valid(instance))
+            let context = self
+                .schemas
+                .iter()
+                .enumerate()
+                .map(|(idx, validators)| {
+                    let errors = validators
+                        .iter()
+                        .flat_map(|validator| {
+                            validator.validate(schema, instance, instance_path, ctx)
+                        })
+                        .take(ctx.error_budget())
+                        .collect();
+                    SubSchemaErrors::new(idx, errors)
+                })
+                .collect();
+            error(
+                ValidationError::one_of_not_valid(
+                    self.schema_path.clone(),
+                    instance_path.clone(),
+                    instance,
+                )
+                .with_context(context),
+            )
         }
     }
 }
 
-impl ToString for OneOfValidator {
-    fn to_string(&self) -> String {
-        format!("oneOf: [{}]", format_vec_of_validators(&self.schemas))
+impl fmt::Display for OneOfValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "oneOf: [{}]", format_vec_of_validators(&self.schemas))
     }
 }
 
@@ -91,3 +164,52 @@ pub(crate) fn compile(
 ) -> Option<CompilationResult> {
     Some(OneOfValidator::compile(schema, context))
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{tests_util, JSONSchema};
+    use serde_json::json;
+
+    #[test]
+    fn multiple_matches_are_reported() {
+        tests_util::expect_errors(
+            &json!({"oneOf": [{"type": "integer"}, {"type": "string"}, {"minimum": 2}]}),
+            &json!(3),
+            &["'3' is valid under more than one of the given schemas (matched: 0, 2)"],
+        )
+    }
+
+    #[test]
+    fn sub_errors_are_in_context() {
+        let schema = json!({"oneOf": [{"type": "string"}, {"minimum": 5}]});
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        let instance = json!(3);
+        let error = compiled.first_error(&instance).unwrap_err();
+        let context: Vec<_> = error
+            .context()
+            .unwrap()
+            .iter()
+            .map(|errors| {
+                let messages: Vec<_> = errors.errors().iter().map(ToString::to_string).collect();
+                (errors.index(), messages)
+            })
+            .collect();
+        assert_eq!(
+            context,
+            vec![
+                (
+                    0,
+                    vec!["/oneOf/0/type: '3' is not of type 'string'".to_string()]
+                ),
+                (
+                    1,
+                    vec!["/oneOf/1/minimum: 3 is less than the minimum of 5".to_string()]
+                )
+            ]
+        );
+        // Multiple matches are not caused by sub-schema errors
+        let instance = json!("foo");
+        let error = compiled.first_error(&instance).unwrap_err();
+        assert!(error.context().is_none());
+    }
+}
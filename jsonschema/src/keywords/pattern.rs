@@ -2,12 +2,14 @@ use crate::{
     compilation::{context::CompilationContext, JSONSchema},
     error::{error, no_error, CompilationError, ErrorIterator, ValidationError},
     keywords::CompilationResult,
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use regex::{Captures, Regex};
 use serde_json::{Map, Value};
 
-use std::ops::Index;
+use std::{fmt, ops::Index};
 
 lazy_static::lazy_static! {
     static ref CONTROL_GROUPS_RE: Regex = Regex::new(r"\\c[A-Za-z]").expect("Is a valid regex");
@@ -16,17 +18,25 @@ lazy_static::lazy_static! {
 pub(crate) struct PatternValidator {
     original: String,
     pattern: Regex,
+    schema_path: JSONPointer,
 }
 
 impl PatternValidator {
     #[inline]
-    pub(crate) fn compile(pattern: &Value) -> CompilationResult {
+    pub(crate) fn compile(
+        pattern: &Value,
+        ecma_regex_compat: bool,
+        schema_path: JSONPointer,
+    ) -> CompilationResult {
         match pattern {
             Value::String(item) => {
-                let pattern = convert_regex(item)?;
+                check_ecma_compat(item, ecma_regex_compat, &schema_path)?;
+                let pattern = convert_regex(item)
+                    .map_err(|error| CompilationError::from_regex(item, &schema_path, &error))?;
                 Ok(Box::new(PatternValidator {
                     original: item.clone(),
                     pattern,
+                    schema_path,
                 }))
             }
             _ => Err(CompilationError::SchemaError),
@@ -35,16 +45,27 @@ impl PatternValidator {
 }
 
 impl Validate for PatternValidator {
-    fn validate<'a>(&self, _: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        _: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        _: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::String(item) = instance {
             if !self.pattern.is_match(item) {
-                return error(ValidationError::pattern(instance, self.original.clone()));
+                return error(ValidationError::pattern(
+                    self.schema_path.clone(),
+                    instance_path.clone(),
+                    instance,
+                    self.original.clone(),
+                ));
             }
         }
         no_error()
     }
 
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         if let Value::String(item) = instance {
             if !self.pattern.is_match(item) {
                 return false;
@@ -54,9 +75,9 @@ impl Validate for PatternValidator {
     }
 }
 
-impl ToString for PatternValidator {
-    fn to_string(&self) -> String {
-        format!("pattern: {}", self.pattern)
+impl fmt::Display for PatternValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "pattern: {}", self.pattern)
     }
 }
 
@@ -103,6 +124,80 @@ fn convert_regex(pattern: &str) -> Result<Regex, regex::Error> {
     Regex::new(&out)
 }
 
+/// Compile a `patternProperties` key. Unlike `pattern`, it is used with Rust's `regex` syntax
+/// as is, but it is still checked for ECMA 262 incompatibilities if `ecma_regex_compat` is set.
+pub(crate) fn compile_pattern_key(
+    pattern: &str,
+    context: &CompilationContext,
+) -> Result<Regex, CompilationError> {
+    check_ecma_compat(
+        pattern,
+        context.config.has_ecma_regex_compat(),
+        &context.schema_path,
+    )?;
+    Regex::new(pattern)
+        .map_err(|error| CompilationError::from_regex(pattern, &context.schema_path, &error))
+}
+
+fn check_ecma_compat(
+    pattern: &str,
+    ecma_regex_compat: bool,
+    path: &JSONPointer,
+) -> Result<(), CompilationError> {
+    match ecma_incompatibility(pattern) {
+        Some(reason) if ecma_regex_compat => {
+            Err(CompilationError::invalid_pattern(pattern, path, reason))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Describes syntax in `pattern` that is not a part of ECMA 262 or that is matched differently
+/// by the `regex` crate. Character classes like `\d` are not reported as they are converted.
+fn ecma_incompatibility(pattern: &str) -> Option<&'static str> {
+    let mut chars = pattern.chars().peekable();
+    let mut in_class = false;
+    while let Some(current) = chars.next() {
+        match current {
+            '\\' => match chars.next() {
+                Some('A' | 'z' | 'Z') => {
+                    return Some("\\A, \\z and \\Z anchors are not supported in ECMA 262")
+                }
+                Some('b' | 'B') if !in_class => {
+                    return Some("word boundaries are Unicode-aware, unlike in ECMA 262")
+                }
+                _ => {}
+            },
+            '[' if in_class => {
+                // POSIX classes like `[[:alpha:]]` or nested classes
+                return Some("nested character classes are not supported in ECMA 262");
+            }
+            '[' => {
+                in_class = true;
+                if chars.peek() == Some(&']') {
+                    return Some(
+                        "']' at the start of a character class is a literal, unlike in ECMA 262",
+                    );
+                }
+            }
+            ']' if in_class => in_class = false,
+            '&' | '-' | '~' if in_class && chars.peek() == Some(&current) => {
+                return Some("character class set operations are not supported in ECMA 262")
+            }
+            '(' if !in_class && chars.peek() == Some(&'?') => {
+                chars.next();
+                if let Some('P' | 'i' | 'm' | 's' | 'U' | 'u' | 'x' | '-') = chars.peek() {
+                    return Some(
+                        "named groups with 'P' and inline flags are not supported in ECMA 262",
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
 #[allow(clippy::integer_arithmetic)]
 fn replace_control_group(captures: &Captures) -> String {
     // There will be no overflow, because the minimum value is 65 (char 'A')
@@ -121,14 +216,20 @@ fn replace_control_group(captures: &Captures) -> String {
 pub(crate) fn compile(
     _: &Map<String, Value>,
     schema: &Value,
-    _: &CompilationContext,
+    context: &CompilationContext,
 ) -> Option<CompilationResult> {
-    Some(PatternValidator::compile(schema))
+    Some(PatternValidator::compile(
+        schema,
+        context.config.has_ecma_regex_compat(),
+        context.schema_path.clone(),
+    ))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::compilation::options::CompilationOptions;
+    use serde_json::{json, Value};
     use test_case::test_case;
 
     #[test_case(r"^[\w\-\.\+]+$", "CC-BY-4.0", true)]
@@ -145,4 +246,74 @@ mod tests {
     fn invalid_escape_sequences(pattern: &str) {
         assert!(convert_regex(pattern).is_err())
     }
+
+    #[test]
+    fn invalid_pattern() {
+        let schema = json!({"properties": {"foo": {"pattern": "invalid["}}});
+        let error = CompilationOptions::default().compile(&schema).unwrap_err();
+        assert_eq!(
+            error,
+            CompilationError::InvalidPattern {
+                pattern: "invalid[".to_string(),
+                path: "/properties/foo/pattern".to_string(),
+                message: "unclosed character class".to_string(),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Pattern 'invalid[' at '/properties/foo/pattern' failed to compile: unclosed character class"
+        );
+    }
+
+    #[test_case(r"(?i)^foo$"; "inline flags")]
+    #[test_case(r"(?P<name>a)"; "named group")]
+    #[test_case(r"\Afoo\z"; "anchors")]
+    #[test_case(r"\bfoo\b"; "word boundary")]
+    #[test_case(r"[[:alpha:]]"; "posix class")]
+    #[test_case(r"[a-z&&[^aeiou]]"; "class intersection")]
+    #[test_case(r"[]a]"; "leading bracket")]
+    fn ecma_incompatible(pattern: &str) {
+        assert!(ecma_incompatibility(pattern).is_some());
+        let schema = json!({ "pattern": pattern });
+        assert!(CompilationOptions::default()
+            .ecma_regex_compat(true)
+            .compile(&schema)
+            .is_err());
+    }
+
+    #[test_case(&json!({"pattern": "(?i)a"}), "/pattern")]
+    #[test_case(&json!({"patternProperties": {"(?i)a": {}}}), "/patternProperties/(?i)a")]
+    #[test_case(&json!({"patternProperties": {"(?i)a": {}}, "additionalProperties": false}), "/patternProperties/(?i)a"; "with additional properties")]
+    #[test_case(&json!({"patternProperties": {"(?i)a": {}}, "additionalProperties": {"type": "string"}}), "/patternProperties/(?i)a"; "with additional properties schema")]
+    fn ecma_incompatible_error(schema: &Value, path: &str) {
+        let error = CompilationOptions::default()
+            .ecma_regex_compat(true)
+            .compile(schema)
+            .unwrap_err();
+        assert_eq!(
+            error,
+            CompilationError::InvalidPattern {
+                pattern: "(?i)a".to_string(),
+                path: path.to_string(),
+                message: "named groups with 'P' and inline flags are not supported in ECMA 262"
+                    .to_string(),
+            }
+        );
+        // Accepted without the option
+        assert!(JSONSchema::compile(schema).is_ok());
+    }
+
+    #[test_case(r"^\w+$"; "word class")]
+    #[test_case(r"(?:a|b)(c)"; "groups")]
+    #[test_case(r"\\b"; "escaped backslash")]
+    #[test_case(r"[\d\-_]+"; "escapes in class")]
+    #[test_case(r"^[a-z]-[0-9]$"; "ranges")]
+    fn ecma_compatible(pattern: &str) {
+        assert!(ecma_incompatibility(pattern).is_none());
+        let schema = json!({ "pattern": pattern });
+        assert!(CompilationOptions::default()
+            .ecma_regex_compat(true)
+            .compile(&schema)
+            .is_ok());
+    }
 }
@@ -1,11 +1,14 @@
 use crate::{
     compilation::{compile_validators, context::CompilationContext, JSONSchema},
     error::{no_error, CompilationError, ErrorIterator},
-    keywords::{format_validators, CompilationResult, Validators},
+    keywords::{format_validators, pattern, CompilationResult, Validators},
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use regex::Regex;
 use serde_json::{Map, Value};
+use std::fmt;
 
 pub(crate) struct PatternPropertiesValidator {
     patterns: Vec<(Regex, Validators)>,
@@ -17,9 +20,10 @@ impl PatternPropertiesValidator {
         if let Value::Object(map) = properties {
             let mut patterns = Vec::with_capacity(map.len());
             for (pattern, subschema) in map {
+                let pattern_context = context.with_path(pattern.as_str());
                 patterns.push((
-                    Regex::new(pattern)?,
-                    compile_validators(subschema, context)?,
+                    pattern::compile_pattern_key(pattern, &pattern_context)?,
+                    compile_validators(subschema, &pattern_context)?,
                 ));
             }
             Ok(Box::new(PatternPropertiesValidator { patterns }))
@@ -30,7 +34,7 @@ impl PatternPropertiesValidator {
 }
 
 impl Validate for PatternPropertiesValidator {
-    fn is_valid(&self, schema: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
         if let Value::Object(item) = instance {
             self.patterns.iter().all(move |(re, validators)| {
                 item.iter()
@@ -38,7 +42,7 @@ impl Validate for PatternPropertiesValidator {
                     .all(move |(_key, value)| {
                         validators
                             .iter()
-                            .all(move |validator| validator.is_valid(schema, value))
+                            .all(move |validator| validator.is_valid(schema, value, ctx))
                     })
             })
         } else {
@@ -46,7 +50,13 @@ impl Validate for PatternPropertiesValidator {
         }
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::Object(item) = instance {
             let errors: Vec<_> = self
                 .patterns
@@ -54,12 +64,14 @@ impl Validate for PatternPropertiesValidator {
                 .flat_map(move |(re, validators)| {
                     item.iter()
                         .filter(move |(key, _)| re.is_match(key))
-                        .flat_map(move |(_key, value)| {
-                            validators
-                                .iter()
-                                .flat_map(move |validator| validator.validate(schema, value))
+                        .flat_map(move |(key, value)| {
+                            let instance_path = instance_path.extend_with(key.as_str());
+                            validators.iter().flat_map(move |validator| {
+                                validator.validate(schema, value, &instance_path, ctx)
+                            })
                         })
                 })
+                .take(ctx.error_budget())
                 .collect();
             Box::new(errors.into_iter())
         } else {
@@ -68,9 +80,10 @@ impl Validate for PatternPropertiesValidator {
     }
 }
 
-impl ToString for PatternPropertiesValidator {
-    fn to_string(&self) -> String {
-        format!(
+impl fmt::Display for PatternPropertiesValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
             "patternProperties: {{{}}}",
             self.patterns
                 .iter()
@@ -2,9 +2,13 @@ use crate::{
     compilation::{compile_validators, context::CompilationContext, JSONSchema},
     error::{no_error, CompilationError, ErrorIterator},
     keywords::{format_key_value_validators, CompilationResult, Validators},
+    output::{Annotation, PartialApplication},
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use serde_json::{Map, Value};
+use std::fmt;
 
 pub(crate) struct PropertiesValidator {
     properties: Vec<(String, Validators)>,
@@ -17,7 +21,11 @@ impl PropertiesValidator {
             Value::Object(map) => {
                 let mut properties = Vec::with_capacity(map.len());
                 for (key, subschema) in map {
-                    properties.push((key.clone(), compile_validators(subschema, context)?));
+                    let property_context = context.with_path(key.as_str());
+                    properties.push((
+                        key.clone(),
+                        compile_validators(subschema, &property_context)?,
+                    ));
                 }
                 Ok(Box::new(PropertiesValidator { properties }))
             }
@@ -27,14 +35,14 @@ impl PropertiesValidator {
 }
 
 impl Validate for PropertiesValidator {
-    fn is_valid(&self, schema: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
         if let Value::Object(item) = instance {
             self.properties.iter().all(move |(name, validators)| {
                 let option = item.get(name);
                 option.into_iter().all(move |item| {
                     validators
                         .iter()
-                        .all(move |validator| validator.is_valid(schema, item))
+                        .all(move |validator| validator.is_valid(schema, item, ctx))
                 })
             })
         } else {
@@ -42,7 +50,13 @@ impl Validate for PropertiesValidator {
         }
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::Object(item) = instance {
             let errors: Vec<_> = self
                 .properties
@@ -50,22 +64,64 @@ impl Validate for PropertiesValidator {
                 .flat_map(move |(name, validators)| {
                     let option = item.get(name);
                     option.into_iter().flat_map(move |item| {
-                        validators
-                            .iter()
-                            .flat_map(move |validator| validator.validate(schema, item))
+                        let instance_path = instance_path.extend_with(name.as_str());
+                        validators.iter().flat_map(move |validator| {
+                            validator.validate(schema, item, &instance_path, ctx)
+                        })
                     })
                 })
+                .take(ctx.error_budget())
                 .collect();
             Box::new(errors.into_iter())
         } else {
             no_error()
         }
     }
+
+    fn apply<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> PartialApplication<'a> {
+        let result = self
+            .validate(schema, instance, instance_path, ctx)
+            .collect::<Vec<_>>()
+            .into();
+        if let Value::Object(item) = instance {
+            annotate_properties(
+                result,
+                self.properties
+                    .iter()
+                    .map(|(name, _)| name)
+                    .filter(|name| item.contains_key(*name)),
+            )
+        } else {
+            result
+        }
+    }
 }
 
-impl ToString for PropertiesValidator {
-    fn to_string(&self) -> String {
-        format!(
+/// Annotate a valid result with names of instance properties evaluated by `properties`.
+#[inline]
+pub(crate) fn annotate_properties<'a, 'n>(
+    mut result: PartialApplication<'a>,
+    names: impl Iterator<Item = &'n String>,
+) -> PartialApplication<'a> {
+    if result.is_valid() {
+        let names = names.map(|name| Value::String(name.clone())).collect();
+        result
+            .annotations
+            .push(Annotation::new("properties", Value::Array(names)));
+    }
+    result
+}
+
+impl fmt::Display for PropertiesValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
             "properties: {{{}}}",
             format_key_value_validators(&self.properties)
         )
@@ -84,3 +140,36 @@ pub(crate) fn compile(
         _ => Some(PropertiesValidator::compile(schema, context)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::compilation::JSONSchema;
+    use serde_json::{json, Value};
+    use test_case::test_case;
+
+    #[test_case(&json!({"properties": {"a": {}, "b": {}, "c": {}}}), &json!(["a", "c"]); "properties")]
+    #[test_case(&json!({"properties": {"a": {}, "b": {}, "c": {}}, "additionalProperties": false}), &json!(["a", "c"]); "additional properties false")]
+    #[test_case(&json!({"properties": {"a": {}, "b": {}, "c": {}}, "additionalProperties": {}}), &json!(["a", "c"]); "additional properties schema")]
+    #[test_case(&json!({"properties": {"a": {}, "b": {}, "c": {}}, "patternProperties": {"^x": {}}, "additionalProperties": false}), &json!(["a", "c"]); "with patterns false")]
+    #[test_case(&json!({"properties": {"a": {}, "b": {}, "c": {}}, "patternProperties": {"^x": {}}, "additionalProperties": {}}), &json!(["a", "c"]); "with patterns schema")]
+    #[test_case(&json!({"properties": {"b": {}}}), &json!([]); "no evaluated properties")]
+    fn annotates_evaluated_properties(schema: &Value, expected: &Value) {
+        let compiled = JSONSchema::compile(schema).unwrap();
+        let instance = json!({"a": 1, "c": 2});
+        let output = compiled.apply(&instance);
+        assert!(output.is_valid());
+        assert_eq!(output.annotations().len(), 1);
+        assert_eq!(output.annotations()[0].keyword(), "properties");
+        assert_eq!(output.annotations()[0].value(), expected);
+    }
+
+    #[test]
+    fn no_annotations_on_failure() {
+        let schema = json!({"properties": {"a": {"type": "string"}}});
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        let instance = json!({"a": 1});
+        let output = compiled.apply(&instance);
+        assert!(!output.is_valid());
+        assert!(output.annotations().is_empty());
+    }
+}
@@ -2,10 +2,12 @@ use crate::{
     compilation::{compile_validators, context::CompilationContext, JSONSchema},
     error::{error, no_error, ErrorIterator, ValidationError},
     keywords::{format_validators, CompilationResult, Validators},
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use serde_json::{Map, Value};
-use std::borrow::Borrow;
+use std::fmt;
 
 pub(crate) struct PropertyNamesObjectValidator {
     validators: Validators,
@@ -21,12 +23,12 @@ impl PropertyNamesObjectValidator {
 }
 
 impl Validate for PropertyNamesObjectValidator {
-    fn is_valid(&self, schema: &JSONSchema, instance: &Value) -> bool {
-        if let Value::Object(item) = &instance.borrow() {
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
+        if let Value::Object(item) = instance {
             self.validators.iter().all(move |validator| {
                 item.keys().all(move |key| {
                     let wrapper = Value::String(key.to_string());
-                    validator.is_valid(schema, &wrapper)
+                    validator.is_valid(schema, &wrapper, ctx)
                 })
             })
         } else {
@@ -34,8 +36,14 @@ impl Validate for PropertyNamesObjectValidator {
         }
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        if let Value::Object(item) = &instance.borrow() {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if let Value::Object(item) = instance {
             let errors: Vec<_> = self
                 .validators
                 .iter()
@@ -43,12 +51,14 @@ impl Validate for PropertyNamesObjectValidator {
                     item.keys().flat_map(move |key| {
                         let wrapper = Value::String(key.to_string());
                         let errors: Vec<_> = validator
-                            .validate(schema, &wrapper)
+                            .validate(schema, &wrapper, instance_path, ctx)
                             .map(ValidationError::into_owned)
+                            .take(ctx.error_budget())
                             .collect();
                         errors.into_iter()
                     })
                 })
+                .take(ctx.error_budget())
                 .collect();
             Box::new(errors.into_iter())
         } else {
@@ -56,23 +66,25 @@ impl Validate for PropertyNamesObjectValidator {
         }
     }
 }
-impl ToString for PropertyNamesObjectValidator {
-    fn to_string(&self) -> String {
-        format!("propertyNames: {}", format_validators(&self.validators))
+impl fmt::Display for PropertyNamesObjectValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "propertyNames: {}", format_validators(&self.validators))
     }
 }
 
-pub(crate) struct PropertyNamesBooleanValidator {}
+pub(crate) struct PropertyNamesBooleanValidator {
+    schema_path: JSONPointer,
+}
 
 impl PropertyNamesBooleanValidator {
     #[inline]
-    pub(crate) fn compile() -> CompilationResult {
-        Ok(Box::new(PropertyNamesBooleanValidator {}))
+    pub(crate) fn compile(schema_path: JSONPointer) -> CompilationResult {
+        Ok(Box::new(PropertyNamesBooleanValidator { schema_path }))
     }
 }
 
 impl Validate for PropertyNamesBooleanValidator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         if let Value::Object(item) = instance {
             if !item.is_empty() {
                 return false;
@@ -81,18 +93,29 @@ impl Validate for PropertyNamesBooleanValidator {
         true
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        if self.is_valid(schema, instance) {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
             no_error()
         } else {
-            error(ValidationError::false_schema(instance))
+            error(ValidationError::false_schema(
+                self.schema_path.clone(),
+                instance_path.clone(),
+                instance,
+                "propertyNames",
+            ))
         }
     }
 }
 
-impl ToString for PropertyNamesBooleanValidator {
-    fn to_string(&self) -> String {
-        "propertyNames: false".to_string()
+impl fmt::Display for PropertyNamesBooleanValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("propertyNames: false")
     }
 }
 
@@ -104,7 +127,40 @@ pub(crate) fn compile(
 ) -> Option<CompilationResult> {
     match schema {
         Value::Object(_) => Some(PropertyNamesObjectValidator::compile(schema, context)),
-        Value::Bool(false) => Some(PropertyNamesBooleanValidator::compile()),
+        Value::Bool(false) => Some(PropertyNamesBooleanValidator::compile(
+            context.schema_path.clone(),
+        )),
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::tests_util;
+    use serde_json::{json, Value};
+    use test_case::test_case;
+
+    #[test_case(&json!({"propertyNames": {"maxLength": 3}}), &json!({"foo": 1, "bar": 2}))]
+    #[test_case(&json!({"propertyNames": {"maxLength": 3}}), &json!("foobar"))]
+    #[test_case(&json!({"propertyNames": false}), &json!({}))]
+    #[test_case(&json!({"propertyNames": false}), &json!([1]))]
+    fn is_valid(schema: &Value, instance: &Value) {
+        tests_util::is_valid(schema, instance)
+    }
+
+    #[test_case(&json!({"propertyNames": {"maxLength": 3}}), &json!({"foobar": 1}))]
+    #[test_case(&json!({"propertyNames": {"pattern": "^a"}}), &json!({"abc": 1, "bcd": 2}))]
+    #[test_case(&json!({"propertyNames": false}), &json!({"foo": 1}))]
+    fn is_not_valid(schema: &Value, instance: &Value) {
+        tests_util::is_not_valid(schema, instance)
+    }
+
+    #[test]
+    fn error_reports_property_name() {
+        tests_util::expect_errors(
+            &json!({"propertyNames": {"maxLength": 3}}),
+            &json!({"foo": 1, "foobar": 2}),
+            &["'\"foobar\"' is longer than 3 characters (actual: 6)"],
+        )
+    }
+}
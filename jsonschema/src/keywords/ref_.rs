@@ -2,11 +2,13 @@ use crate::{
     compilation::{compile_validators, context::CompilationContext, JSONSchema},
     error::{error, ErrorIterator, ValidationError},
     keywords::{CompilationResult, Validators},
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use parking_lot::RwLock;
 use serde_json::Value;
-use std::borrow::Cow;
+use std::{borrow::Cow, fmt};
 use url::Url;
 
 pub(crate) struct RefValidator {
@@ -17,6 +19,7 @@ pub(crate) struct RefValidator {
     /// and at the same time during validation we iterate over shared
     /// references (&self) and not owned references (&mut self).
     validators: RwLock<Option<Validators>>,
+    schema_path: JSONPointer,
 }
 
 impl RefValidator {
@@ -26,6 +29,7 @@ impl RefValidator {
         Ok(Box::new(RefValidator {
             reference,
             validators: RwLock::new(None),
+            schema_path: context.schema_path.clone(),
         }))
     }
 
@@ -36,9 +40,14 @@ impl RefValidator {
             let (scope, resolved) = schema.resolver.resolve_fragment(
                 schema.context.config.draft(),
                 &self.reference,
-                schema.schema,
+                schema.document(),
             )?;
-            let context = CompilationContext::new(scope, Cow::Borrowed(&schema.context.config));
+            // Keywords of the referenced schema are reported relative to the `$ref` location
+            let context = CompilationContext {
+                scope: Cow::Owned(scope),
+                config: Cow::Borrowed(&schema.context.config),
+                schema_path: self.schema_path.clone(),
+            };
             let validators = compile_validators(&resolved, &context)?;
 
             // Inject the validators into self.validators
@@ -49,7 +58,7 @@ impl RefValidator {
 }
 
 impl Validate for RefValidator {
-    fn is_valid(&self, schema: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
         if self.ensure_validators(schema).is_err() {
             false
         } else {
@@ -58,11 +67,17 @@ impl Validate for RefValidator {
                 .as_ref()
                 .expect("ensure_validators guarantees the presence of the validators")
                 .iter()
-                .all(move |validator| validator.is_valid(schema, instance))
+                .all(move |validator| validator.is_valid(schema, instance, ctx))
         }
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Err(err) = self.ensure_validators(schema) {
             error(err)
         } else {
@@ -72,7 +87,10 @@ impl Validate for RefValidator {
                     .as_ref()
                     .expect("ensure_validators guarantees the presence of the validators")
                     .iter()
-                    .flat_map(move |validator| validator.validate(schema, instance))
+                    .flat_map(move |validator| {
+                        validator.validate(schema, instance, instance_path, ctx)
+                    })
+                    .take(ctx.error_budget())
                     .collect::<Vec<_>>()
                     .into_iter(),
             )
@@ -80,9 +98,9 @@ impl Validate for RefValidator {
     }
 }
 
-impl ToString for RefValidator {
-    fn to_string(&self) -> String {
-        format!("$ref: {}", self.reference)
+impl fmt::Display for RefValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "$ref: {}", self.reference)
     }
 }
 
@@ -94,3 +112,50 @@ pub(crate) fn compile(
 ) -> Option<CompilationResult> {
     Some(RefValidator::compile(reference, context))
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{compilation::JSONSchema, tests_util};
+    use serde_json::{json, Value};
+    use test_case::test_case;
+
+    fn tree() -> Value {
+        json!({
+            "definitions": {
+                "node": {
+                    "properties": {
+                        "value": {"type": "integer"},
+                        "children": {"items": {"$ref": "#/definitions/node"}}
+                    }
+                }
+            },
+            "$ref": "#/definitions/node"
+        })
+    }
+
+    #[test_case(&json!({"value": 1}); "leaf")]
+    #[test_case(&json!({"value": 1, "children": [{"value": 2, "children": [{"value": 3}]}]}); "nested")]
+    fn recursive_valid(instance: &Value) {
+        tests_util::is_valid(&tree(), instance)
+    }
+
+    #[test_case(&json!({"value": "a"}); "leaf")]
+    #[test_case(&json!({"value": 1, "children": [{"value": 2, "children": [{"value": "a"}]}]}); "nested")]
+    fn recursive_invalid(instance: &Value) {
+        tests_util::is_not_valid(&tree(), instance)
+    }
+
+    #[test]
+    fn schema_path_is_relative_to_ref() {
+        let schema = tree();
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        let instance = json!({"children": [{"value": "a"}]});
+        let errors: Vec<_> = compiled.validate(&instance).unwrap_err().collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].schema_path().to_string(),
+            "/$ref/properties/children/items/$ref/properties/value/type"
+        );
+        assert_eq!(errors[0].instance_path().to_string(), "/children/0/value");
+    }
+}
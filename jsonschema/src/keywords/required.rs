@@ -2,17 +2,21 @@ use crate::{
     compilation::{context::CompilationContext, JSONSchema},
     error::{error, no_error, CompilationError, ErrorIterator, ValidationError},
     keywords::CompilationResult,
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use serde_json::{Map, Value};
+use std::fmt;
 
 pub(crate) struct RequiredValidator {
     required: Vec<String>,
+    schema_path: JSONPointer,
 }
 
 impl RequiredValidator {
     #[inline]
-    pub(crate) fn compile(schema: &Value) -> CompilationResult {
+    pub(crate) fn compile(schema: &Value, schema_path: JSONPointer) -> CompilationResult {
         match schema {
             Value::Array(items) => {
                 let mut required = Vec::with_capacity(items.len());
@@ -22,7 +26,10 @@ impl RequiredValidator {
                         _ => return Err(CompilationError::SchemaError),
                     }
                 }
-                Ok(Box::new(RequiredValidator { required }))
+                Ok(Box::new(RequiredValidator {
+                    required,
+                    schema_path,
+                }))
             }
             _ => Err(CompilationError::SchemaError),
         }
@@ -30,7 +37,7 @@ impl RequiredValidator {
 }
 
 impl Validate for RequiredValidator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         if let Value::Object(item) = instance {
             self.required
                 .iter()
@@ -40,11 +47,22 @@ impl Validate for RequiredValidator {
         }
     }
 
-    fn validate<'a>(&self, _: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
+    fn validate<'a>(
+        &self,
+        _: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        _: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
         if let Value::Object(item) = instance {
             for property_name in &self.required {
                 if !item.contains_key(property_name) {
-                    return error(ValidationError::required(instance, property_name.clone()));
+                    return error(ValidationError::required(
+                        self.schema_path.clone(),
+                        instance_path.clone(),
+                        instance,
+                        property_name.clone(),
+                    ));
                 }
             }
         }
@@ -52,9 +70,9 @@ impl Validate for RequiredValidator {
     }
 }
 
-impl ToString for RequiredValidator {
-    fn to_string(&self) -> String {
-        format!("required: [{}]", self.required.join(", "))
+impl fmt::Display for RequiredValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "required: [{}]", self.required.join(", "))
     }
 }
 
@@ -62,7 +80,33 @@ impl ToString for RequiredValidator {
 pub(crate) fn compile(
     _: &Map<String, Value>,
     schema: &Value,
-    _: &CompilationContext,
+    context: &CompilationContext,
 ) -> Option<CompilationResult> {
-    Some(RequiredValidator::compile(schema))
+    // Nothing to check, the validator is not needed at all
+    if let Value::Array(items) = schema {
+        if items.is_empty() {
+            return None;
+        }
+    }
+    Some(RequiredValidator::compile(
+        schema,
+        context.schema_path.clone(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{tests_util, JSONSchema};
+    use serde_json::{json, Value};
+    use test_case::test_case;
+
+    #[test_case(&json!({}))]
+    #[test_case(&json!({"a": 1}))]
+    #[test_case(&json!(42); "not an object")]
+    fn empty_required(instance: &Value) {
+        let schema = json!({"required": []});
+        tests_util::is_valid(&schema, instance);
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        assert!(compiled.validators.is_empty());
+    }
 }
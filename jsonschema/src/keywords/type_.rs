@@ -1,20 +1,23 @@
 use crate::{
     compilation::{context::CompilationContext, JSONSchema},
     error::{error, no_error, CompilationError, ErrorIterator, ValidationError},
-    keywords::CompilationResult,
+    keywords::{helpers, CompilationResult},
+    paths::JSONPointer,
     primitive_type::{PrimitiveType, PrimitiveTypesBitMap},
+    validation_context::ValidationContext,
     validator::Validate,
 };
 use serde_json::{Map, Number, Value};
-use std::convert::TryFrom;
+use std::{convert::TryFrom, fmt};
 
 pub(crate) struct MultipleTypesValidator {
     types: PrimitiveTypesBitMap,
+    schema_path: JSONPointer,
 }
 
 impl MultipleTypesValidator {
     #[inline]
-    pub(crate) fn compile(items: &[Value]) -> CompilationResult {
+    pub(crate) fn compile(items: &[Value], schema_path: JSONPointer) -> CompilationResult {
         let mut types = PrimitiveTypesBitMap::new();
         for item in items {
             match item {
@@ -28,36 +31,57 @@ impl MultipleTypesValidator {
                 _ => return Err(CompilationError::SchemaError),
             }
         }
-        Ok(Box::new(MultipleTypesValidator { types }))
+        Ok(Box::new(MultipleTypesValidator { types, schema_path }))
+    }
+
+    #[inline]
+    pub(crate) fn compile_types(
+        types: PrimitiveTypesBitMap,
+        schema_path: JSONPointer,
+    ) -> CompilationResult {
+        Ok(Box::new(MultipleTypesValidator { types, schema_path }))
     }
 }
 
 impl Validate for MultipleTypesValidator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         match instance {
             Value::Array(_) => self.types.contains_type(PrimitiveType::Array),
             Value::Bool(_) => self.types.contains_type(PrimitiveType::Boolean),
             Value::Null => self.types.contains_type(PrimitiveType::Null),
             Value::Number(num) => {
                 self.types.contains_type(PrimitiveType::Number)
-                    || (self.types.contains_type(PrimitiveType::Integer) && is_integer(num))
+                    || (self.types.contains_type(PrimitiveType::Integer)
+                        && helpers::is_integer_valued(num))
             }
             Value::Object(_) => self.types.contains_type(PrimitiveType::Object),
             Value::String(_) => self.types.contains_type(PrimitiveType::String),
         }
     }
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        if self.is_valid(schema, instance) {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
             no_error()
         } else {
-            error(ValidationError::multiple_type_error(instance, self.types))
+            error(ValidationError::multiple_type_error(
+                self.schema_path.clone(),
+                instance_path.clone(),
+                instance,
+                self.types,
+            ))
         }
     }
 }
 
-impl ToString for MultipleTypesValidator {
-    fn to_string(&self) -> String {
-        format!(
+impl fmt::Display for MultipleTypesValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
             "type: [{}]",
             self.types
                 .into_iter()
@@ -68,24 +92,34 @@ impl ToString for MultipleTypesValidator {
     }
 }
 
-pub(crate) struct NullTypeValidator {}
+pub(crate) struct NullTypeValidator {
+    schema_path: JSONPointer,
+}
 
 impl NullTypeValidator {
     #[inline]
-    pub(crate) fn compile() -> CompilationResult {
-        Ok(Box::new(NullTypeValidator {}))
+    pub(crate) fn compile(schema_path: JSONPointer) -> CompilationResult {
+        Ok(Box::new(NullTypeValidator { schema_path }))
     }
 }
 
 impl Validate for NullTypeValidator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         instance.is_null()
     }
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        if self.is_valid(schema, instance) {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
             no_error()
         } else {
             error(ValidationError::single_type_error(
+                self.schema_path.clone(),
+                instance_path.clone(),
                 instance,
                 PrimitiveType::Null,
             ))
@@ -93,30 +127,40 @@ impl Validate for NullTypeValidator {
     }
 }
 
-impl ToString for NullTypeValidator {
-    fn to_string(&self) -> String {
-        "type: null".to_string()
+impl fmt::Display for NullTypeValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("type: null")
     }
 }
 
-pub(crate) struct BooleanTypeValidator {}
+pub(crate) struct BooleanTypeValidator {
+    schema_path: JSONPointer,
+}
 
 impl BooleanTypeValidator {
     #[inline]
-    pub(crate) fn compile() -> CompilationResult {
-        Ok(Box::new(BooleanTypeValidator {}))
+    pub(crate) fn compile(schema_path: JSONPointer) -> CompilationResult {
+        Ok(Box::new(BooleanTypeValidator { schema_path }))
     }
 }
 
 impl Validate for BooleanTypeValidator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         instance.is_boolean()
     }
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        if self.is_valid(schema, instance) {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
             no_error()
         } else {
             error(ValidationError::single_type_error(
+                self.schema_path.clone(),
+                instance_path.clone(),
                 instance,
                 PrimitiveType::Boolean,
             ))
@@ -124,62 +168,82 @@ impl Validate for BooleanTypeValidator {
     }
 }
 
-impl ToString for BooleanTypeValidator {
-    fn to_string(&self) -> String {
-        "type: boolean".to_string()
+impl fmt::Display for BooleanTypeValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("type: boolean")
     }
 }
 
-pub(crate) struct StringTypeValidator {}
+pub(crate) struct StringTypeValidator {
+    schema_path: JSONPointer,
+}
 
 impl StringTypeValidator {
     #[inline]
-    pub(crate) fn compile() -> CompilationResult {
-        Ok(Box::new(StringTypeValidator {}))
+    pub(crate) fn compile(schema_path: JSONPointer) -> CompilationResult {
+        Ok(Box::new(StringTypeValidator { schema_path }))
     }
 }
 
 impl Validate for StringTypeValidator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         instance.is_string()
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        if self.is_valid(schema, instance) {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
             no_error()
         } else {
             error(ValidationError::single_type_error(
+                self.schema_path.clone(),
+                instance_path.clone(),
                 instance,
                 PrimitiveType::String,
             ))
         }
     }
 }
-impl ToString for StringTypeValidator {
-    fn to_string(&self) -> String {
-        "type: string".to_string()
+impl fmt::Display for StringTypeValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("type: string")
     }
 }
 
-pub(crate) struct ArrayTypeValidator {}
+pub(crate) struct ArrayTypeValidator {
+    schema_path: JSONPointer,
+}
 
 impl ArrayTypeValidator {
     #[inline]
-    pub(crate) fn compile() -> CompilationResult {
-        Ok(Box::new(ArrayTypeValidator {}))
+    pub(crate) fn compile(schema_path: JSONPointer) -> CompilationResult {
+        Ok(Box::new(ArrayTypeValidator { schema_path }))
     }
 }
 
 impl Validate for ArrayTypeValidator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         instance.is_array()
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        if self.is_valid(schema, instance) {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
             no_error()
         } else {
             error(ValidationError::single_type_error(
+                self.schema_path.clone(),
+                instance_path.clone(),
                 instance,
                 PrimitiveType::Array,
             ))
@@ -187,30 +251,40 @@ impl Validate for ArrayTypeValidator {
     }
 }
 
-impl ToString for ArrayTypeValidator {
-    fn to_string(&self) -> String {
-        "type: array".to_string()
+impl fmt::Display for ArrayTypeValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("type: array")
     }
 }
 
-pub(crate) struct ObjectTypeValidator {}
+pub(crate) struct ObjectTypeValidator {
+    schema_path: JSONPointer,
+}
 
 impl ObjectTypeValidator {
     #[inline]
-    pub(crate) fn compile() -> CompilationResult {
-        Ok(Box::new(ObjectTypeValidator {}))
+    pub(crate) fn compile(schema_path: JSONPointer) -> CompilationResult {
+        Ok(Box::new(ObjectTypeValidator { schema_path }))
     }
 }
 
 impl Validate for ObjectTypeValidator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         instance.is_object()
     }
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        if self.is_valid(schema, instance) {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
             no_error()
         } else {
             error(ValidationError::single_type_error(
+                self.schema_path.clone(),
+                instance_path.clone(),
                 instance,
                 PrimitiveType::Object,
             ))
@@ -218,63 +292,83 @@ impl Validate for ObjectTypeValidator {
     }
 }
 
-impl ToString for ObjectTypeValidator {
-    fn to_string(&self) -> String {
-        "type: object".to_string()
+impl fmt::Display for ObjectTypeValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("type: object")
     }
 }
 
-pub(crate) struct NumberTypeValidator {}
+pub(crate) struct NumberTypeValidator {
+    schema_path: JSONPointer,
+}
 
 impl NumberTypeValidator {
     #[inline]
-    pub(crate) fn compile() -> CompilationResult {
-        Ok(Box::new(NumberTypeValidator {}))
+    pub(crate) fn compile(schema_path: JSONPointer) -> CompilationResult {
+        Ok(Box::new(NumberTypeValidator { schema_path }))
     }
 }
 
 impl Validate for NumberTypeValidator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         instance.is_number()
     }
-    fn validate<'a>(&self, config: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        if self.is_valid(config, instance) {
+    fn validate<'a>(
+        &self,
+        config: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(config, instance, ctx) {
             no_error()
         } else {
             error(ValidationError::single_type_error(
+                self.schema_path.clone(),
+                instance_path.clone(),
                 instance,
                 PrimitiveType::Number,
             ))
         }
     }
 }
-impl ToString for NumberTypeValidator {
-    fn to_string(&self) -> String {
-        "type: number".to_string()
+impl fmt::Display for NumberTypeValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("type: number")
     }
 }
-pub(crate) struct IntegerTypeValidator {}
+pub(crate) struct IntegerTypeValidator {
+    schema_path: JSONPointer,
+}
 
 impl IntegerTypeValidator {
     #[inline]
-    pub(crate) fn compile() -> CompilationResult {
-        Ok(Box::new(IntegerTypeValidator {}))
+    pub(crate) fn compile(schema_path: JSONPointer) -> CompilationResult {
+        Ok(Box::new(IntegerTypeValidator { schema_path }))
     }
 }
 
 impl Validate for IntegerTypeValidator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         if let Value::Number(num) = instance {
-            is_integer(num)
+            helpers::is_integer_valued(num)
         } else {
             false
         }
     }
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        if self.is_valid(schema, instance) {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
             no_error()
         } else {
             error(ValidationError::single_type_error(
+                self.schema_path.clone(),
+                instance_path.clone(),
                 instance,
                 PrimitiveType::Integer,
             ))
@@ -282,48 +376,366 @@ impl Validate for IntegerTypeValidator {
     }
 }
 
-impl ToString for IntegerTypeValidator {
-    fn to_string(&self) -> String {
-        "type: integer".to_string()
+impl fmt::Display for IntegerTypeValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("type: integer")
+    }
+}
+
+/// `type` with coercion of strings, e.g. `"42"` is an integer and `"true"` is a boolean.
+/// Enabled by `CompilationOptions::coerce_types`. The instance itself is not modified.
+pub(crate) struct CoercingTypesValidator {
+    types: PrimitiveTypesBitMap,
+    is_integer: fn(&Number) -> bool,
+    schema_path: JSONPointer,
+}
+
+impl CoercingTypesValidator {
+    #[inline]
+    pub(crate) fn compile(
+        schema: &Value,
+        is_integer: fn(&Number) -> bool,
+        schema_path: JSONPointer,
+    ) -> CompilationResult {
+        let mut types = PrimitiveTypesBitMap::new();
+        let items = match schema {
+            Value::String(_) => std::slice::from_ref(schema),
+            Value::Array(items) => items.as_slice(),
+            _ => return Err(CompilationError::SchemaError),
+        };
+        for item in items {
+            match item.as_str().map(PrimitiveType::try_from) {
+                Some(Ok(primitive_type)) => types |= primitive_type,
+                _ => return Err(CompilationError::SchemaError),
+            }
+        }
+        Ok(Box::new(CoercingTypesValidator {
+            types,
+            is_integer,
+            schema_path,
+        }))
+    }
+
+    fn is_valid_number(&self, num: &Number) -> bool {
+        self.types.contains_type(PrimitiveType::Number)
+            || (self.types.contains_type(PrimitiveType::Integer) && (self.is_integer)(num))
+    }
+}
+
+/// Only strings that are valid JSON numbers are coerced, without surrounding whitespace.
+fn parse_number(string: &str) -> Option<Number> {
+    if string.bytes().any(|byte| byte.is_ascii_whitespace()) {
+        None
+    } else {
+        serde_json::from_str(string).ok()
+    }
+}
+
+impl Validate for CoercingTypesValidator {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
+        match instance {
+            Value::Array(_) => self.types.contains_type(PrimitiveType::Array),
+            Value::Bool(_) => self.types.contains_type(PrimitiveType::Boolean),
+            Value::Null => self.types.contains_type(PrimitiveType::Null),
+            Value::Number(num) => self.is_valid_number(num),
+            Value::Object(_) => self.types.contains_type(PrimitiveType::Object),
+            Value::String(string) => {
+                self.types.contains_type(PrimitiveType::String)
+                    || (self.types.contains_type(PrimitiveType::Boolean)
+                        && (string == "true" || string == "false"))
+                    || matches!(parse_number(string), Some(num) if self.is_valid_number(&num))
+            }
+        }
+    }
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
+            no_error()
+        } else {
+            let mut types = self.types.into_iter();
+            match (types.next(), types.next()) {
+                (Some(type_), None) => error(ValidationError::single_type_error(
+                    self.schema_path.clone(),
+                    instance_path.clone(),
+                    instance,
+                    type_,
+                )),
+                _ => error(ValidationError::multiple_type_error(
+                    self.schema_path.clone(),
+                    instance_path.clone(),
+                    instance,
+                    self.types,
+                )),
+            }
+        }
     }
 }
 
-fn is_integer(num: &Number) -> bool {
-    num.is_u64() || num.is_i64() || num.as_f64().expect("Always valid").fract() == 0.
+impl fmt::Display for CoercingTypesValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "type: [{}]",
+            self.types
+                .into_iter()
+                .map(|type_| format!("{}", type_))
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+    }
 }
 
 #[inline]
 pub(crate) fn compile(
     _: &Map<String, Value>,
     schema: &Value,
-    _: &CompilationContext,
+    context: &CompilationContext,
 ) -> Option<CompilationResult> {
+    if context.config.has_strict_keywords() && has_duplicate_types(schema) {
+        return Some(Err(CompilationError::SchemaError));
+    }
+    if context.config.coerces_types() {
+        return Some(CoercingTypesValidator::compile(
+            schema,
+            helpers::is_integer_valued,
+            context.schema_path.clone(),
+        ));
+    }
     match schema {
-        Value::String(item) => compile_single_type(item.as_str()),
+        Value::String(item) => compile_single_type(item.as_str(), context),
         Value::Array(items) => {
             if items.len() == 1 {
                 if let Some(Value::String(item)) = items.iter().next() {
-                    compile_single_type(item.as_str())
+                    compile_single_type(item.as_str(), context)
                 } else {
                     Some(Err(CompilationError::SchemaError))
                 }
             } else {
-                Some(MultipleTypesValidator::compile(items))
+                Some(MultipleTypesValidator::compile(
+                    items,
+                    context.schema_path.clone(),
+                ))
             }
         }
         _ => Some(Err(CompilationError::SchemaError)),
     }
 }
 
-fn compile_single_type(item: &str) -> Option<CompilationResult> {
+/// Whether the array form of `type` lists some type more than once, e.g. `["string", "string"]`.
+/// Duplicates do not change the validation outcome.
+pub(crate) fn has_duplicate_types(schema: &Value) -> bool {
+    if let Value::Array(items) = schema {
+        items
+            .iter()
+            .enumerate()
+            .any(|(idx, item)| items[..idx].contains(item))
+    } else {
+        false
+    }
+}
+
+#[inline]
+fn compile_single_type(item: &str, context: &CompilationContext) -> Option<CompilationResult> {
     match PrimitiveType::try_from(item) {
-        Ok(PrimitiveType::Array) => Some(ArrayTypeValidator::compile()),
-        Ok(PrimitiveType::Boolean) => Some(BooleanTypeValidator::compile()),
-        Ok(PrimitiveType::Integer) => Some(IntegerTypeValidator::compile()),
-        Ok(PrimitiveType::Null) => Some(NullTypeValidator::compile()),
-        Ok(PrimitiveType::Number) => Some(NumberTypeValidator::compile()),
-        Ok(PrimitiveType::Object) => Some(ObjectTypeValidator::compile()),
-        Ok(PrimitiveType::String) => Some(StringTypeValidator::compile()),
+        Ok(PrimitiveType::Array) => Some(ArrayTypeValidator::compile(context.schema_path.clone())),
+        Ok(PrimitiveType::Boolean) => {
+            Some(BooleanTypeValidator::compile(context.schema_path.clone()))
+        }
+        Ok(PrimitiveType::Integer) => {
+            Some(IntegerTypeValidator::compile(context.schema_path.clone()))
+        }
+        Ok(PrimitiveType::Null) => Some(NullTypeValidator::compile(context.schema_path.clone())),
+        Ok(PrimitiveType::Number) => {
+            Some(NumberTypeValidator::compile(context.schema_path.clone()))
+        }
+        Ok(PrimitiveType::Object) => {
+            Some(ObjectTypeValidator::compile(context.schema_path.clone()))
+        }
+        Ok(PrimitiveType::String) => {
+            Some(StringTypeValidator::compile(context.schema_path.clone()))
+        }
         Err(()) => Some(Err(CompilationError::SchemaError)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        compilation::{options::CompilationOptions, JSONSchema},
+        schemas::Draft,
+        tests_util,
+    };
+    use serde_json::{json, Value};
+    use test_case::test_case;
+
+    #[test_case(&json!(1))]
+    #[test_case(&json!(-1) ; "negative")]
+    #[test_case(&json!(1.0))]
+    #[test_case(&json!(-1.0) ; "negative float")]
+    #[test_case(&json!(18446744073709551615_u64))]
+    #[test_case(&json!(-9223372036854775808_i64))]
+    #[test_case(&json!(1e18))]
+    #[test_case(&json!(1e300))]
+    #[test_case(&json!(-1e300) ; "negative big float")]
+    fn integer_valid(instance: &Value) {
+        tests_util::is_valid(&json!({"type": "integer"}), instance)
+    }
+
+    #[test_case(&json!(1.5))]
+    #[test_case(&json!(-1.5) ; "negative")]
+    #[test_case(&json!(1e-300))]
+    fn integer_invalid(instance: &Value) {
+        tests_util::is_not_valid(&json!({"type": "integer"}), instance)
+    }
+
+    fn coercing(schema: &Value, draft: Draft) -> JSONSchema<'_> {
+        CompilationOptions::default()
+            .with_draft(draft)
+            .coerce_types(true)
+            .compile(schema)
+            .unwrap()
+    }
+
+    #[test_case(&json!({"type": "integer"}), &json!("42"))]
+    #[test_case(&json!({"type": "integer"}), &json!("-1"))]
+    #[test_case(&json!({"type": "integer"}), &json!("1.0"))]
+    #[test_case(&json!({"type": "integer"}), &json!(42) ; "not coerced")]
+    #[test_case(&json!({"type": "number"}), &json!("4.2"))]
+    #[test_case(&json!({"type": "number"}), &json!("1e3"))]
+    #[test_case(&json!({"type": "boolean"}), &json!("true"))]
+    #[test_case(&json!({"type": "boolean"}), &json!("false"))]
+    #[test_case(&json!({"type": ["boolean", "null"]}), &json!("true") ; "multiple types")]
+    #[test_case(&json!({"type": "string"}), &json!("42"))]
+    fn coerced_valid(schema: &Value, instance: &Value) {
+        assert!(coercing(schema, Draft::Draft7).is_valid(instance));
+    }
+
+    #[test_case(&json!({"type": "integer"}), &json!("4.2"))]
+    #[test_case(&json!({"type": "integer"}), &json!("foo"))]
+    #[test_case(&json!({"type": "integer"}), &json!(" 42"))]
+    #[test_case(&json!({"type": "number"}), &json!("NaN"))]
+    #[test_case(&json!({"type": "boolean"}), &json!("True"))]
+    #[test_case(&json!({"type": "boolean"}), &json!(1))]
+    #[test_case(&json!({"type": "null"}), &json!("null"))]
+    fn coerced_invalid(schema: &Value, instance: &Value) {
+        let compiled = coercing(schema, Draft::Draft7);
+        assert!(!compiled.is_valid(instance));
+        assert!(compiled.validate(instance).is_err());
+    }
+
+    #[test]
+    fn coerced_draft4_integer() {
+        let schema = json!({"type": "integer"});
+        let compiled = coercing(&schema, Draft::Draft4);
+        assert!(compiled.is_valid(&json!("42")));
+        // Draft 4 does not treat integer-valued floats as integers
+        assert!(!compiled.is_valid(&json!("1.0")));
+    }
+
+    #[test]
+    fn coerced_error_message() {
+        let schema = json!({"type": "integer"});
+        let compiled = coercing(&schema, Draft::Draft7);
+        let instance = json!("foo");
+        let errors: Vec<_> = compiled.validate(&instance).unwrap_err().collect();
+        assert_eq!(
+            errors[0].to_string(),
+            r#"/type: '"foo"' is not of type 'integer'"#
+        );
+    }
+
+    #[test_case(Draft::Draft4)]
+    #[test_case(Draft::Draft7)]
+    fn duplicate_types(draft: Draft) {
+        let schema = json!({"type": ["string", "null", "string"]});
+        let compiled = CompilationOptions::default()
+            .with_draft(draft)
+            .compile(&schema)
+            .unwrap();
+        assert!(compiled.is_valid(&json!("foo")));
+        assert!(!compiled.is_valid(&json!(1)));
+        let error = CompilationOptions::default()
+            .with_draft(draft)
+            .strict_keywords(true)
+            .compile(&schema)
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            r#"Invalid value of the 'type' keyword at '/type': ["string","null","string"]"#
+        );
+    }
+
+    #[test]
+    fn strict_unique_types() {
+        let schema = json!({"type": ["string", "null"]});
+        assert!(CompilationOptions::default()
+            .strict_keywords(true)
+            .compile(&schema)
+            .is_ok());
+    }
+
+    fn instance_of(type_: &str) -> Value {
+        match type_ {
+            "array" => json!([]),
+            "boolean" => json!(true),
+            "integer" => json!(1),
+            "null" => json!(null),
+            "number" => json!(1.5),
+            "object" => json!({}),
+            "string" => json!("a"),
+            _ => unreachable!("Unknown type"),
+        }
+    }
+
+    #[test_case("array", "boolean")]
+    #[test_case("array", "integer")]
+    #[test_case("array", "null")]
+    #[test_case("array", "number")]
+    #[test_case("array", "object")]
+    #[test_case("array", "string")]
+    #[test_case("boolean", "integer")]
+    #[test_case("boolean", "null")]
+    #[test_case("boolean", "number")]
+    #[test_case("boolean", "object")]
+    #[test_case("boolean", "string")]
+    #[test_case("integer", "null")]
+    #[test_case("integer", "number")]
+    #[test_case("integer", "object")]
+    #[test_case("integer", "string")]
+    #[test_case("null", "number")]
+    #[test_case("null", "object")]
+    #[test_case("null", "string")]
+    #[test_case("number", "object")]
+    #[test_case("number", "string")]
+    #[test_case("object", "string")]
+    fn two_types(first: &str, second: &str) {
+        let schema = json!({ "type": [first, second] });
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        for type_ in &[
+            "array", "boolean", "integer", "null", "number", "object", "string",
+        ] {
+            let instance = instance_of(type_);
+            // Integers are numbers too
+            let is_expected = [first, second].contains(type_)
+                || (*type_ == "integer" && [first, second].contains(&"number"));
+            if is_expected {
+                tests_util::is_valid(&schema, &instance);
+            } else {
+                tests_util::is_not_valid(&schema, &instance);
+                let error = compiled.first_error(&instance).unwrap_err();
+                assert_eq!(
+                    error.to_string(),
+                    format!(
+                        "/type: '{}' is not of types '{}', '{}'",
+                        instance, first, second
+                    )
+                );
+            }
+        }
+    }
+}
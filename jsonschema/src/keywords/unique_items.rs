@@ -1,55 +1,14 @@
 use crate::{
     compilation::{context::CompilationContext, JSONSchema},
     error::{error, no_error, ErrorIterator, ValidationError},
-    keywords::CompilationResult,
+    keywords::{helpers::HashedValue, CompilationResult},
+    paths::JSONPointer,
+    validation_context::ValidationContext,
     validator::Validate,
 };
-use ahash::{AHashSet, AHasher};
+use ahash::AHashSet;
 use serde_json::{Map, Value};
-use std::hash::{Hash, Hasher};
-
-// Based on implementation proposed by Sven Marnach:
-// https://stackoverflow.com/questions/60882381/what-is-the-fastest-correct-way-to-detect-that-there-are-no-duplicates-in-a-json
-#[derive(PartialEq)]
-pub(crate) struct HashedValue<'a>(&'a Value);
-
-impl Eq for HashedValue<'_> {}
-
-impl Hash for HashedValue<'_> {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        match self.0 {
-            Value::Null => state.write_u32(3_221_225_473), // chosen randomly
-            Value::Bool(ref item) => item.hash(state),
-            Value::Number(ref item) => {
-                if let Some(number) = item.as_u64() {
-                    number.hash(state);
-                } else if let Some(number) = item.as_i64() {
-                    number.hash(state);
-                } else if let Some(number) = item.as_f64() {
-                    number.to_bits().hash(state)
-                }
-            }
-            Value::String(ref item) => item.hash(state),
-            Value::Array(ref items) => {
-                for item in items {
-                    HashedValue(item).hash(state);
-                }
-            }
-            Value::Object(ref items) => {
-                let mut hash = 0;
-                for (key, value) in items {
-                    // We have no way of building a new hasher of type `H`, so we
-                    // hardcode using the default hasher of a hash map.
-                    let mut item_hasher = AHasher::default();
-                    key.hash(&mut item_hasher);
-                    HashedValue(value).hash(&mut item_hasher);
-                    hash ^= item_hasher.finish();
-                }
-                state.write_u64(hash);
-            }
-        }
-    }
-}
+use std::fmt;
 
 #[inline]
 pub(crate) fn is_unique(items: &[Value]) -> bool {
@@ -57,17 +16,19 @@ pub(crate) fn is_unique(items: &[Value]) -> bool {
     items.iter().map(HashedValue).all(move |x| seen.insert(x))
 }
 
-pub(crate) struct UniqueItemsValidator {}
+pub(crate) struct UniqueItemsValidator {
+    schema_path: JSONPointer,
+}
 
 impl UniqueItemsValidator {
     #[inline]
-    pub(crate) fn compile() -> CompilationResult {
-        Ok(Box::new(UniqueItemsValidator {}))
+    pub(crate) fn compile(schema_path: JSONPointer) -> CompilationResult {
+        Ok(Box::new(UniqueItemsValidator { schema_path }))
     }
 }
 
 impl Validate for UniqueItemsValidator {
-    fn is_valid(&self, _: &JSONSchema, instance: &Value) -> bool {
+    fn is_valid(&self, _: &JSONSchema, instance: &Value, _: &ValidationContext) -> bool {
         if let Value::Array(items) = instance {
             if !is_unique(items) {
                 return false;
@@ -76,29 +37,39 @@ impl Validate for UniqueItemsValidator {
         true
     }
 
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a> {
-        if self.is_valid(schema, instance) {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        if self.is_valid(schema, instance, ctx) {
             no_error()
         } else {
-            error(ValidationError::unique_items(instance))
+            error(ValidationError::unique_items(
+                self.schema_path.clone(),
+                instance_path.clone(),
+                instance,
+            ))
         }
     }
 }
 
-impl ToString for UniqueItemsValidator {
-    fn to_string(&self) -> String {
-        "uniqueItems: true".to_string()
+impl fmt::Display for UniqueItemsValidator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("uniqueItems: true")
     }
 }
 #[inline]
 pub(crate) fn compile(
     _: &Map<String, Value>,
     schema: &Value,
-    _: &CompilationContext,
+    context: &CompilationContext,
 ) -> Option<CompilationResult> {
     if let Value::Bool(value) = schema {
         if *value {
-            Some(UniqueItemsValidator::compile())
+            Some(UniqueItemsValidator::compile(context.schema_path.clone()))
         } else {
             None
         }
@@ -106,3 +77,25 @@ pub(crate) fn compile(
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::tests_util;
+    use serde_json::{json, Value};
+    use test_case::test_case;
+
+    #[test_case(&json!([1, 2, 3]))]
+    #[test_case(&json!([1, "1", [1], {"a": 1}]); "different types")]
+    #[test_case(&json!([{"a": 1, "b": 2}, {"a": 2, "b": 1}]); "different values")]
+    fn unique(instance: &Value) {
+        tests_util::is_valid(&json!({"uniqueItems": true}), instance)
+    }
+
+    #[test_case(&json!([1, 1.0]); "integer and float")]
+    #[test_case(&json!([-2, -2.0]); "negative integer and float")]
+    #[test_case(&json!([[1], [1.0]]); "nested numbers")]
+    #[test_case(&json!([{"a": 1, "b": 2}, {"b": 2.0, "a": 1}]); "objects")]
+    fn not_unique(instance: &Value) {
+        tests_util::is_not_valid(&json!({"uniqueItems": true}), instance)
+    }
+}
@@ -7,6 +7,9 @@
 //!   - JSON Schema drafts 4, 6, 7 (except some optional test cases);
 //!   - Loading remote documents via HTTP(S);
 //!
+//! The crate requires `std`: schema compilation and validation rely on `regex`, `url`,
+//! `parking_lot` and `chrono`, none of which support `no_std` targets.
+//!
 //! ## Usage Examples:
 //! A schema can be compiled with two main flavours:
 //!  * using default configurations
@@ -75,19 +78,50 @@
 )]
 #![allow(clippy::unnecessary_wraps, clippy::upper_case_acronyms)]
 #![cfg_attr(not(test), allow(clippy::integer_arithmetic, clippy::unwrap_used))]
+mod builder;
+mod compatibility;
 mod compilation;
 mod content_encoding;
 mod content_media_type;
 mod error;
+mod format_registry;
 mod keywords;
+#[cfg(feature = "custom-formats")]
+mod locale_formats;
+mod meta_schemas;
+mod normalize;
+mod output;
+mod paths;
 mod primitive_type;
 mod resolver;
 mod schemas;
+mod validation_context;
 mod validator;
-pub use compilation::{options::CompilationOptions, JSONSchema};
-pub use error::{CompilationError, ErrorIterator, ValidationError};
+mod visitor;
+mod vocabularies;
+pub use builder::SchemaBuilder;
+pub use compatibility::{ChangeKind, KeywordChange, SchemaCompatibility, SchemaDiff};
+pub use compilation::{
+    options::{CompilationOptions, UnknownKeywordBehavior},
+    JSONSchema,
+};
+pub use error::{
+    CompilationError, ErrorIterator, ErrorMessage, OwnedErrorIterator, OwnedValidationError,
+    SubSchemaErrors, ValidationError,
+};
+pub use format_registry::FormatRegistry;
+pub use keywords::custom::Keyword;
+pub use keywords::helpers;
+#[cfg(feature = "custom-formats")]
+pub use locale_formats::Locale;
+pub use normalize::normalize;
+pub use output::{Annotation, FlagOutput, ValidationOutput};
+pub use paths::{JSONPointer, PathChunk};
+pub use primitive_type::{PrimitiveType, PrimitiveTypesBitMap, PrimitiveTypesBitMapIterator};
 pub use schemas::Draft;
 use serde_json::Value;
+pub use validation_context::ValidationContext;
+pub use visitor::SchemaVisitor;
 
 /// A shortcut for validating `instance` against `schema`. Draft version is detected automatically.
 /// ```rust
@@ -109,8 +143,37 @@ pub fn is_valid(schema: &Value, instance: &Value) -> bool {
 
 #[cfg(test)]
 pub(crate) mod tests_util {
-    use super::JSONSchema;
+    use super::{CompilationOptions, JSONSchema, Keyword, ValidationContext};
     use serde_json::Value;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    /// A custom keyword that accepts only strings and counts how many times it is evaluated.
+    struct Counter(Arc<AtomicUsize>);
+
+    impl Keyword for Counter {
+        fn validate(&self, instance: &Value, _: &ValidationContext) -> Result<(), String> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            if instance.is_string() {
+                Ok(())
+            } else {
+                Err("not a string".to_string())
+            }
+        }
+    }
+
+    /// Options with the `counter` keyword registered, along with its evaluation counter.
+    pub(crate) fn with_counter() -> (CompilationOptions, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&calls);
+        let mut options = CompilationOptions::default();
+        options.with_keyword("counter", move |_, _| {
+            Ok(Box::new(Counter(Arc::clone(&counter))))
+        });
+        (options, calls)
+    }
 
     pub(crate) fn is_not_valid(schema: &Value, instance: &Value) {
         let compiled = JSONSchema::compile(schema).unwrap();
@@ -133,7 +196,7 @@ pub(crate) mod tests_util {
                 .validate(instance)
                 .expect_err(format!("{} should not be valid", instance).as_str())
                 .into_iter()
-                .map(|e| e.to_string())
+                .map(|e| e.message().to_string())
                 .collect::<Vec<String>>(),
             errors
         )
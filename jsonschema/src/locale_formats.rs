@@ -0,0 +1,142 @@
+//! Locale-specific formats, e.g. national identifiers or postal codes.
+//! They are not defined by JSON Schema, but commonly used in APIs.
+use crate::format_registry::FormatCheckType;
+use regex::Regex;
+
+lazy_static::lazy_static! {
+    static ref CA_POSTAL_CODE_RE: Regex =
+        Regex::new(r"^[ABCEGHJ-NPRSTVXY][0-9][ABCEGHJ-NPRSTV-Z] ?[0-9][ABCEGHJ-NPRSTV-Z][0-9]\z")
+            .expect("Is a valid regex");
+    static ref US_ZIP_RE: Regex = Regex::new(r"^[0-9]{5}(-[0-9]{4})?\z").expect("Is a valid regex");
+}
+
+/// A group of locale-specific formats, enabled with `CompilationOptions::with_locale_formats`.
+///
+/// | Locale | Format           | Example       |
+/// |--------|------------------|---------------|
+/// | `Us`   | `us-ssn`         | `123-45-6789` |
+/// | `Us`   | `us-zip`         | `12345-6789`  |
+/// | `Ca`   | `ca-postal-code` | `K1A 0B1`     |
+/// | `De`   | `de-plz`         | `10115`       |
+///
+/// ```rust
+/// # use jsonschema::{CompilationOptions, Locale};
+/// # use serde_json::json;
+/// let schema = json!({"format": "us-ssn"});
+/// let compiled = CompilationOptions::default()
+///     .with_locale_formats(Locale::Us)
+///     .compile(&schema)
+///     .expect("A valid schema");
+/// assert!(compiled.is_valid(&json!("123-45-6789")));
+/// assert!(!compiled.is_valid(&json!("000-45-6789")));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    /// United States - `us-ssn` and `us-zip`.
+    Us,
+    /// Canada - `ca-postal-code`.
+    Ca,
+    /// Germany - `de-plz`.
+    De,
+}
+
+impl Locale {
+    /// Format names and their checks for this locale.
+    /// They could be also installed individually, e.g. via `FormatRegistry`.
+    #[must_use]
+    pub fn formats(self) -> &'static [(&'static str, FormatCheckType)] {
+        match self {
+            Locale::Us => &[("us-ssn", is_us_ssn), ("us-zip", is_us_zip)],
+            Locale::Ca => &[("ca-postal-code", is_ca_postal_code)],
+            Locale::De => &[("de-plz", is_de_plz)],
+        }
+    }
+}
+
+/// `AAA-GG-SSSS`. Area `000`, `666` and `9xx`, group `00` and serial `0000` are never assigned.
+fn is_us_ssn(item: &str) -> bool {
+    let parts: Vec<_> = item.split('-').collect();
+    match parts.as_slice() {
+        [area, group, serial]
+            if area.len() == 3
+                && group.len() == 2
+                && serial.len() == 4
+                && parts
+                    .iter()
+                    .all(|part| part.bytes().all(|byte| byte.is_ascii_digit())) =>
+        {
+            !matches!(*area, "000" | "666")
+                && !area.starts_with('9')
+                && *group != "00"
+                && *serial != "0000"
+        }
+        _ => false,
+    }
+}
+
+/// ZIP or ZIP+4 code.
+fn is_us_zip(item: &str) -> bool {
+    US_ZIP_RE.is_match(item)
+}
+
+/// `A1A 1A1`, the space is optional. Letters `D`, `F`, `I`, `O`, `Q` and `U` are not used,
+/// `W` and `Z` are not used as the first letter.
+fn is_ca_postal_code(item: &str) -> bool {
+    CA_POSTAL_CODE_RE.is_match(item)
+}
+
+/// Five digits, codes starting with `00` are not assigned.
+fn is_de_plz(item: &str) -> bool {
+    item.len() == 5 && item.bytes().all(|byte| byte.is_ascii_digit()) && !item.starts_with("00")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Locale;
+    use crate::compilation::options::CompilationOptions;
+    use serde_json::json;
+    use test_case::test_case;
+
+    fn is_valid(locale: Locale, format: &str, instance: &str) -> bool {
+        let schema = json!({ "format": format });
+        CompilationOptions::default()
+            .with_locale_formats(locale)
+            .compile(&schema)
+            .unwrap()
+            .is_valid(&json!(instance))
+    }
+
+    #[test_case(Locale::Us, "us-ssn", "123-45-6789")]
+    #[test_case(Locale::Us, "us-zip", "12345")]
+    #[test_case(Locale::Us, "us-zip", "12345-6789"; "zip plus 4")]
+    #[test_case(Locale::Ca, "ca-postal-code", "K1A 0B1")]
+    #[test_case(Locale::Ca, "ca-postal-code", "K1A0B1"; "postal code without space")]
+    #[test_case(Locale::De, "de-plz", "10115")]
+    fn valid(locale: Locale, format: &str, instance: &str) {
+        assert!(is_valid(locale, format, instance))
+    }
+
+    #[test_case(Locale::Us, "us-ssn", "000-45-6789")]
+    #[test_case(Locale::Us, "us-ssn", "666-45-6789")]
+    #[test_case(Locale::Us, "us-ssn", "900-45-6789")]
+    #[test_case(Locale::Us, "us-ssn", "123-00-6789")]
+    #[test_case(Locale::Us, "us-ssn", "123-45-0000")]
+    #[test_case(Locale::Us, "us-ssn", "123456789")]
+    #[test_case(Locale::Us, "us-ssn", "12a-45-6789")]
+    #[test_case(Locale::Us, "us-zip", "1234")]
+    #[test_case(Locale::Us, "us-zip", "12345-678")]
+    #[test_case(Locale::Ca, "ca-postal-code", "D1A 0B1")]
+    #[test_case(Locale::Ca, "ca-postal-code", "K1A  0B1")]
+    #[test_case(Locale::Ca, "ca-postal-code", "k1a 0b1"; "lowercase postal code")]
+    #[test_case(Locale::De, "de-plz", "00123")]
+    #[test_case(Locale::De, "de-plz", "1011")]
+    fn invalid(locale: Locale, format: &str, instance: &str) {
+        assert!(!is_valid(locale, format, instance))
+    }
+
+    #[test]
+    fn other_locales_are_not_enabled() {
+        // Unknown formats are ignored
+        assert!(is_valid(Locale::De, "us-ssn", "000-00-0000"))
+    }
+}
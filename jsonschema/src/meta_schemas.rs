@@ -0,0 +1,40 @@
+//! Meta-schemas of the supported drafts. They are parsed and compiled once per process.
+use crate::compilation::JSONSchema;
+use ahash::AHashMap;
+use serde_json::Value;
+
+lazy_static::lazy_static! {
+    pub(crate) static ref DRAFT4: Value =
+        serde_json::from_str(include_str!("../meta_schemas/draft4.json")).expect("Valid schema!");
+    pub(crate) static ref DRAFT6: Value =
+        serde_json::from_str(include_str!("../meta_schemas/draft6.json")).expect("Valid schema!");
+    pub(crate) static ref DRAFT7: Value =
+        serde_json::from_str(include_str!("../meta_schemas/draft7.json")).expect("Valid schema!");
+    /// Meta-schemas by their URLs, so they can be referenced without network access.
+    pub(crate) static ref META_SCHEMAS: AHashMap<String, Value> = {
+        let mut store = AHashMap::with_capacity(3);
+        store.insert("http://json-schema.org/draft-04/schema".to_string(), DRAFT4.clone());
+        store.insert("http://json-schema.org/draft-06/schema".to_string(), DRAFT6.clone());
+        store.insert("http://json-schema.org/draft-07/schema".to_string(), DRAFT7.clone());
+        store
+    };
+    pub(crate) static ref DRAFT4_META_SCHEMA: JSONSchema<'static> =
+        JSONSchema::compile(&DRAFT4).expect("Meta-schemas are valid");
+    pub(crate) static ref DRAFT6_META_SCHEMA: JSONSchema<'static> =
+        JSONSchema::compile(&DRAFT6).expect("Meta-schemas are valid");
+    pub(crate) static ref DRAFT7_META_SCHEMA: JSONSchema<'static> =
+        JSONSchema::compile(&DRAFT7).expect("Meta-schemas are valid");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(&DRAFT4_META_SCHEMA, &DRAFT4)]
+    #[test_case(&DRAFT6_META_SCHEMA, &DRAFT6)]
+    #[test_case(&DRAFT7_META_SCHEMA, &DRAFT7)]
+    fn meta_schemas_are_self_valid(compiled: &JSONSchema<'static>, meta_schema: &Value) {
+        assert!(compiled.is_valid(meta_schema));
+    }
+}
@@ -0,0 +1,162 @@
+//! Simplification of schemas without changing the set of valid instances.
+use serde_json::{Map, Value};
+
+/// A semantically equivalent, but simpler version of `schema`.
+///
+/// Sub-schemas are normalized first, then the following transformations are applied:
+///   - `{"allOf": [S]}`, `{"anyOf": [S]}` and `{"oneOf": [S]}` become `S`;
+///   - `{"type": ["string"]}` becomes `{"type": "string"}`;
+///   - `{"not": {"not": S}}` becomes `S`;
+///   - `if` is removed together with `then` and `else` if they are `true` or absent. A schema
+///     without other keywords becomes `true`.
+///
+/// Single-element arrays and `not` are collapsed only if there are no other keywords in the same
+/// schema. Note that references to locations within the normalized schema, e.g.
+/// `#/properties/a/allOf/0`, may no longer be resolvable.
+///
+/// ```rust
+/// # use jsonschema::normalize;
+/// # use serde_json::json;
+/// let schema = json!({"properties": {"a": {"allOf": [{"type": ["string"]}]}}});
+/// assert_eq!(
+///     normalize(&schema),
+///     json!({"properties": {"a": {"type": "string"}}})
+/// );
+/// ```
+#[must_use]
+pub fn normalize(schema: &Value) -> Value {
+    match schema {
+        Value::Object(object) => simplify(
+            object
+                .iter()
+                .map(|(keyword, value)| (keyword.clone(), normalize_keyword(keyword, value)))
+                .collect(),
+        ),
+        _ => schema.clone(),
+    }
+}
+
+/// Normalize sub-schemas within the keyword value, other values are left as is.
+fn normalize_keyword(keyword: &str, value: &Value) -> Value {
+    match (keyword, value) {
+        (
+            "additionalItems"
+            | "additionalProperties"
+            | "contains"
+            | "else"
+            | "if"
+            | "not"
+            | "propertyNames"
+            | "then",
+            _,
+        )
+        | ("items", Value::Object(_) | Value::Bool(_)) => normalize(value),
+        ("allOf" | "anyOf" | "oneOf" | "items", Value::Array(items)) => {
+            Value::Array(items.iter().map(normalize).collect())
+        }
+        ("definitions" | "patternProperties" | "properties", Value::Object(map)) => Value::Object(
+            map.iter()
+                .map(|(name, subschema)| (name.clone(), normalize(subschema)))
+                .collect(),
+        ),
+        ("dependencies", Value::Object(map)) => Value::Object(
+            map.iter()
+                .map(|(name, value)| {
+                    // Array values are property dependencies, not sub-schemas
+                    let value = if value.is_array() {
+                        value.clone()
+                    } else {
+                        normalize(value)
+                    };
+                    (name.clone(), value)
+                })
+                .collect(),
+        ),
+        _ => value.clone(),
+    }
+}
+
+fn simplify(mut schema: Map<String, Value>) -> Value {
+    if let Some(Value::Array(types)) = schema.get_mut("type") {
+        if types.len() == 1 {
+            let type_ = types.pop().expect("Has one element");
+            schema.insert("type".to_string(), type_);
+        }
+    }
+    if schema.contains_key("if")
+        && is_true_or_absent(&schema, "then")
+        && is_true_or_absent(&schema, "else")
+    {
+        schema.remove("if");
+        schema.remove("then");
+        schema.remove("else");
+        if schema.is_empty() {
+            return Value::Bool(true);
+        }
+    }
+    if schema.len() == 1 {
+        match schema.iter().next() {
+            Some((keyword, Value::Array(items)))
+                if items.len() == 1 && matches!(keyword.as_str(), "allOf" | "anyOf" | "oneOf") =>
+            {
+                return items[0].clone();
+            }
+            Some((keyword, Value::Object(inner)))
+                if keyword == "not" && inner.len() == 1 && inner.contains_key("not") =>
+            {
+                return inner["not"].clone();
+            }
+            _ => {}
+        }
+    }
+    Value::Object(schema)
+}
+
+/// Whether the `keyword` sub-schema is absent or valid for any instance.
+fn is_true_or_absent(schema: &Map<String, Value>, keyword: &str) -> bool {
+    match schema.get(keyword) {
+        None | Some(Value::Bool(true)) => true,
+        Some(Value::Object(object)) => object.is_empty(),
+        Some(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::normalize;
+    use serde_json::{json, Value};
+    use test_case::test_case;
+
+    #[test_case(&json!({"allOf": [{"minimum": 1}]}), &json!({"minimum": 1}); "single all of")]
+    #[test_case(&json!({"anyOf": [{"minimum": 1}]}), &json!({"minimum": 1}); "single any of")]
+    #[test_case(&json!({"oneOf": [false]}), &json!(false); "single one of")]
+    #[test_case(&json!({"type": ["string"]}), &json!({"type": "string"}); "single type")]
+    #[test_case(&json!({"not": {"not": {"minimum": 1}}}), &json!({"minimum": 1}); "double not")]
+    #[test_case(&json!({"if": {"minimum": 1}, "then": true}), &json!(true); "if then true")]
+    #[test_case(&json!({"if": {"minimum": 1}, "then": {}, "maximum": 5}), &json!({"maximum": 5}); "if then empty")]
+    #[test_case(
+        &json!({"properties": {"a": {"allOf": [{"not": {"not": {"type": ["integer"]}}}]}}}),
+        &json!({"properties": {"a": {"type": "integer"}}});
+        "nested"
+    )]
+    #[test_case(
+        &json!({"dependencies": {"a": ["b"], "c": {"anyOf": [true]}}}),
+        &json!({"dependencies": {"a": ["b"], "c": true}});
+        "dependencies"
+    )]
+    fn simplified(schema: &Value, expected: &Value) {
+        assert_eq!(normalize(schema), *expected)
+    }
+
+    #[test_case(&json!({"allOf": [{"minimum": 1}], "maximum": 5}); "all of with siblings")]
+    #[test_case(&json!({"anyOf": [{"minimum": 1}, {"maximum": 5}]}); "multiple any of")]
+    #[test_case(&json!({"type": ["string", "null"]}); "multiple types")]
+    #[test_case(&json!({"not": {"not": {}, "minimum": 1}}); "not with siblings")]
+    #[test_case(&json!({"if": {"minimum": 1}, "then": {"maximum": 5}}); "if then")]
+    #[test_case(&json!({"if": {"minimum": 1}, "else": false}); "if else")]
+    #[test_case(&json!({"enum": [{"allOf": [{}]}]}); "not a sub-schema")]
+    #[test_case(&json!({"properties": {"allOf": {"type": ["string", "null"]}}}); "property name")]
+    fn unchanged(schema: &Value) {
+        assert_eq!(normalize(schema), *schema)
+    }
+}
@@ -0,0 +1,291 @@
+//! Structured validation output.
+//! Combines assertion results and annotations collected while applying a schema to an instance.
+use crate::error::ValidationError;
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Serializer};
+use serde_json::Value;
+
+/// A value attached to an instance by a keyword during validation.
+///
+/// For example, `contains` annotates the indices of array items that match its sub-schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    keyword: &'static str,
+    value: Value,
+}
+
+impl Annotation {
+    #[inline]
+    pub(crate) fn new(keyword: &'static str, value: Value) -> Annotation {
+        Annotation { keyword, value }
+    }
+
+    /// The keyword that produced this annotation.
+    #[must_use]
+    #[inline]
+    pub fn keyword(&self) -> &'static str {
+        self.keyword
+    }
+
+    /// The annotation value.
+    #[must_use]
+    #[inline]
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+}
+
+/// The result of applying a single validator to an instance.
+#[derive(Debug, Default)]
+pub(crate) struct PartialApplication<'a> {
+    pub(crate) errors: Vec<ValidationError<'a>>,
+    pub(crate) annotations: Vec<Annotation>,
+}
+
+impl<'a> PartialApplication<'a> {
+    /// A valid result with no annotations.
+    #[inline]
+    pub(crate) fn valid_empty() -> PartialApplication<'a> {
+        PartialApplication::default()
+    }
+
+    #[inline]
+    pub(crate) fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Combine results of validators applied to the same instance.
+    /// Annotations are kept only while the combined result is valid.
+    #[inline]
+    pub(crate) fn merge(&mut self, other: PartialApplication<'a>) {
+        self.errors.extend(other.errors);
+        if self.is_valid() {
+            self.annotations.extend(other.annotations);
+        } else {
+            self.annotations.clear();
+        }
+    }
+}
+
+impl<'a> From<Vec<ValidationError<'a>>> for PartialApplication<'a> {
+    #[inline]
+    fn from(errors: Vec<ValidationError<'a>>) -> Self {
+        PartialApplication {
+            errors,
+            annotations: Vec::new(),
+        }
+    }
+}
+
+/// The outcome of `JSONSchema::apply` - validation errors and annotations for the input instance.
+///
+/// Annotations are collected only for valid instances.
+/// It is serialized in the "basic" output format defined by JSON Schema Draft 2019-09.
+///
+/// ```rust
+/// # use jsonschema::JSONSchema;
+/// # use serde_json::json;
+/// let schema = json!({"maxLength": 5});
+/// let compiled = JSONSchema::compile(&schema).expect("A valid schema");
+/// let instance = json!("foo bar");
+/// let output = compiled.apply(&instance);
+/// assert!(!output.is_valid());
+/// assert_eq!(
+///     serde_json::to_value(&output).expect("Serializable"),
+///     json!({
+///         "valid": false,
+///         "errors": [{
+///             "keywordLocation": "/maxLength",
+///             "instanceLocation": "",
+///             "error": "'\"foo bar\"' is longer than 5 characters (actual: 7)"
+///         }]
+///     })
+/// );
+/// ```
+#[derive(Debug)]
+pub struct ValidationOutput<'a> {
+    errors: Vec<ValidationError<'a>>,
+    annotations: Vec<Annotation>,
+}
+
+impl<'a> ValidationOutput<'a> {
+    /// Whether the instance is valid.
+    #[must_use]
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Validation errors. Empty if the instance is valid.
+    #[must_use]
+    #[inline]
+    pub fn errors(&self) -> &[ValidationError<'a>] {
+        &self.errors
+    }
+
+    /// Collected annotations. Empty if the instance is not valid.
+    #[must_use]
+    #[inline]
+    pub fn annotations(&self) -> &[Annotation] {
+        &self.annotations
+    }
+
+    /// Destructure the output into the validity flag, errors and annotations.
+    #[must_use]
+    #[inline]
+    pub fn into_parts(self) -> (bool, Vec<ValidationError<'a>>, Vec<Annotation>) {
+        (self.is_valid(), self.errors, self.annotations)
+    }
+
+    /// A serializable view in the "flag" output format. It contains only the validity flag.
+    #[must_use]
+    #[inline]
+    pub fn flag(&self) -> FlagOutput {
+        FlagOutput {
+            valid: self.is_valid(),
+        }
+    }
+}
+
+impl<'a> From<PartialApplication<'a>> for ValidationOutput<'a> {
+    #[inline]
+    fn from(application: PartialApplication<'a>) -> Self {
+        let annotations = if application.is_valid() {
+            application.annotations
+        } else {
+            Vec::new()
+        };
+        ValidationOutput {
+            errors: application.errors,
+            annotations,
+        }
+    }
+}
+
+/// The "flag" output format. Serializes to `{"valid": <bool>}`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlagOutput {
+    valid: bool,
+}
+
+impl Serialize for FlagOutput {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+        map.serialize_entry("valid", &self.valid)?;
+        map.end()
+    }
+}
+
+struct ErrorUnit<'e, 'a>(&'e ValidationError<'a>);
+
+impl Serialize for ErrorUnit<'_, '_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(3))?;
+        map.serialize_entry("keywordLocation", &self.0.schema_path().to_string())?;
+        map.serialize_entry("instanceLocation", &self.0.instance_path().to_string())?;
+        map.serialize_entry("error", &self.0.message().to_string())?;
+        map.end()
+    }
+}
+
+impl Serialize for Annotation {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("keyword", self.keyword)?;
+        map.serialize_entry("annotation", &self.value)?;
+        map.end()
+    }
+}
+
+struct Units<'o, T>(&'o [T]);
+
+impl<T: Serialize> Serialize for Units<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for unit in self.0 {
+            seq.serialize_element(unit)?;
+        }
+        seq.end()
+    }
+}
+
+impl Serialize for ValidationOutput<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("valid", &self.is_valid())?;
+        if self.is_valid() {
+            map.serialize_entry("annotations", &Units(&self.annotations))?;
+        } else {
+            let errors: Vec<_> = self.errors.iter().map(ErrorUnit).collect();
+            map.serialize_entry("errors", &Units(&errors))?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Annotation, PartialApplication};
+    use crate::{error::ValidationError, paths::JSONPointer, JSONSchema};
+    use serde_json::{json, Value};
+
+    #[test]
+    fn valid_output() {
+        let schema = json!({"maxLength": 5});
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        let instance = json!("foo");
+        let output = compiled.apply(&instance);
+        assert!(output.is_valid());
+        assert!(output.errors().is_empty());
+        assert_eq!(
+            serde_json::to_value(&output).unwrap(),
+            json!({"valid": true, "annotations": []})
+        );
+        assert_eq!(
+            serde_json::to_value(&output.flag()).unwrap(),
+            json!({"valid": true})
+        );
+    }
+
+    #[test]
+    fn invalid_output() {
+        let schema = json!({"minProperties": 2, "propertyNames": {"minLength": 3}});
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        let instance = json!({"a": 3});
+        let output = compiled.apply(&instance);
+        assert_eq!(
+            serde_json::to_value(&output.flag()).unwrap(),
+            json!({"valid": false})
+        );
+        let (is_valid, errors, annotations) = output.into_parts();
+        assert!(!is_valid);
+        assert_eq!(errors.len(), 2);
+        assert!(annotations.is_empty());
+    }
+
+    fn annotated(keyword: &'static str, value: Value) -> PartialApplication<'static> {
+        PartialApplication {
+            errors: vec![],
+            annotations: vec![Annotation { keyword, value }],
+        }
+    }
+
+    #[test]
+    fn annotations_are_dropped_on_failure() {
+        let instance = json!(1);
+        let mut application = annotated("contains", json!([0]));
+        assert!(application.is_valid());
+        application.merge(annotated("properties", json!(["a"])));
+        assert_eq!(application.annotations.len(), 2);
+        application.merge(
+            vec![ValidationError::false_schema(
+                JSONPointer::default(),
+                JSONPointer::default(),
+                &instance,
+                "",
+            )]
+            .into(),
+        );
+        assert!(!application.is_valid());
+        assert!(application.annotations.is_empty());
+    }
+}
@@ -0,0 +1,142 @@
+//! Facilities for working with paths within schemas or validated instances.
+use serde_json::Value;
+use std::fmt;
+
+/// JSON Pointer as a wrapper around individual path components.
+/// It is displayed as defined in RFC 6901.
+///
+/// ```rust
+/// # use jsonschema::{JSONPointer, PathChunk};
+/// let pointer = JSONPointer::default().extend_with("items").extend_with(0);
+/// assert_eq!(pointer.to_string(), "/items/0");
+/// assert_eq!(pointer.chunks()[1], PathChunk::Index(0));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct JSONPointer(Vec<PathChunk>);
+
+impl JSONPointer {
+    /// Whether the pointer refers to the document root.
+    #[must_use]
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Individual path components.
+    #[must_use]
+    #[inline]
+    pub fn chunks(&self) -> &[PathChunk] {
+        &self.0
+    }
+
+    /// A new pointer with `chunk` appended to the current one.
+    #[must_use]
+    #[inline]
+    pub fn extend_with(&self, chunk: impl Into<PathChunk>) -> Self {
+        let mut chunks = Vec::with_capacity(self.0.len() + 1);
+        chunks.extend_from_slice(&self.0);
+        chunks.push(chunk.into());
+        JSONPointer(chunks)
+    }
+
+    /// A new pointer with the last chunk replaced by `chunk`.
+    #[inline]
+    pub(crate) fn sibling(&self, chunk: impl Into<PathChunk>) -> Self {
+        let mut chunks = self.0.clone();
+        chunks.pop();
+        chunks.push(chunk.into());
+        JSONPointer(chunks)
+    }
+
+    /// The value at this location within `document`. The first `skip` chunks are ignored,
+    /// i.e. they point to `document` within a larger one.
+    #[inline]
+    pub(crate) fn resolve<'v>(&self, document: &'v Value, skip: usize) -> Option<&'v Value> {
+        self.0
+            .iter()
+            .skip(skip)
+            .try_fold(document, |value, chunk| match (chunk, value) {
+                (PathChunk::Property(name), Value::Object(object)) => object.get(&**name),
+                (PathChunk::Index(idx), Value::Array(items)) => items.get(*idx),
+                _ => None,
+            })
+    }
+}
+
+/// A key within a JSON object or an index within a JSON array.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathChunk {
+    /// Property name within a JSON object.
+    Property(Box<str>),
+    /// Index within a JSON array.
+    Index(usize),
+}
+
+impl From<&str> for PathChunk {
+    #[inline]
+    fn from(value: &str) -> Self {
+        PathChunk::Property(value.into())
+    }
+}
+
+impl From<usize> for PathChunk {
+    #[inline]
+    fn from(value: usize) -> Self {
+        PathChunk::Index(value)
+    }
+}
+
+/// Formats the pointer as defined in RFC 6901, e.g. `/properties/a~1b/0`.
+impl fmt::Display for JSONPointer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for chunk in &self.0 {
+            f.write_str("/")?;
+            match chunk {
+                PathChunk::Property(value) => {
+                    for ch in value.chars() {
+                        match ch {
+                            '/' => f.write_str("~1")?,
+                            '~' => f.write_str("~0")?,
+                            _ => write!(f, "{}", ch)?,
+                        }
+                    }
+                }
+                PathChunk::Index(idx) => write!(f, "{}", idx)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JSONPointer;
+    use serde_json::json;
+
+    #[test]
+    fn display() {
+        let pointer = JSONPointer::default()
+            .extend_with("properties")
+            .extend_with("a/b~c")
+            .extend_with(0);
+        assert_eq!(pointer.to_string(), "/properties/a~1b~0c/0");
+        assert_eq!(JSONPointer::default().to_string(), "");
+        assert_eq!(pointer.sibling(1).to_string(), "/properties/a~1b~0c/1");
+    }
+
+    #[test]
+    fn resolve() {
+        let document = json!({"a": [{"b": 1}]});
+        let pointer = JSONPointer::default()
+            .extend_with("a")
+            .extend_with(0)
+            .extend_with("b");
+        assert_eq!(pointer.resolve(&document, 0), Some(&json!(1)));
+        assert_eq!(pointer.resolve(&json!([{"b": 2}]), 1), Some(&json!(2)));
+        assert_eq!(pointer.resolve(&json!({"a": {"0": 1}}), 0), None);
+        assert_eq!(
+            JSONPointer::default().resolve(&document, 0),
+            Some(&document)
+        );
+    }
+}
@@ -3,14 +3,21 @@ use std::{convert::TryFrom, fmt, ops::BitOrAssign};
 
 /// For faster error handling in "type" keyword validator we have this enum, to match
 /// with it instead of a string.
-#[derive(Debug, Clone, Copy)]
-pub(crate) enum PrimitiveType {
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PrimitiveType {
+    /// `array`
     Array,
+    /// `boolean`
     Boolean,
+    /// `integer`
     Integer,
+    /// `null`
     Null,
+    /// `number`
     Number,
+    /// `object`
     Object,
+    /// `string`
     String,
 }
 
@@ -86,8 +93,9 @@ fn bit_map_representation_primitive_type(bit_representation: u8) -> PrimitiveTyp
     }
 }
 
-#[derive(Clone, Copy, Debug)]
-pub(crate) struct PrimitiveTypesBitMap {
+/// A set of primitive types, e.g. from the `type` keyword.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PrimitiveTypesBitMap {
     inner: u8,
 }
 impl PrimitiveTypesBitMap {
@@ -101,8 +109,18 @@ impl PrimitiveTypesBitMap {
         self
     }
 
+    /// Types that are in both sets.
+    #[inline]
+    pub(crate) fn intersection(self, other: Self) -> Self {
+        Self {
+            inner: self.inner & other.inner,
+        }
+    }
+
+    /// Whether `primitive_type` is in the set.
+    #[must_use]
     #[inline(always)]
-    pub(crate) fn contains_type(self, primitive_type: PrimitiveType) -> bool {
+    pub fn contains_type(self, primitive_type: PrimitiveType) -> bool {
         primitive_type_to_bit_map_representation(primitive_type) & self.inner != 0
     }
 }
@@ -117,7 +135,7 @@ impl IntoIterator for PrimitiveTypesBitMap {
     type IntoIter = PrimitiveTypesBitMapIterator;
     fn into_iter(self) -> Self::IntoIter {
         PrimitiveTypesBitMapIterator {
-            range: 1..7,
+            range: 0..7,
             bit_map: self,
         }
     }
@@ -133,7 +151,9 @@ impl From<Vec<PrimitiveType>> for PrimitiveTypesBitMap {
     }
 }
 
-pub(crate) struct PrimitiveTypesBitMapIterator {
+/// An iterator over types in `PrimitiveTypesBitMap`.
+#[derive(Debug)]
+pub struct PrimitiveTypesBitMapIterator {
     range: std::ops::Range<u8>,
     bit_map: PrimitiveTypesBitMap,
 }
@@ -153,3 +173,23 @@ impl Iterator for PrimitiveTypesBitMapIterator {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{PrimitiveType, PrimitiveTypesBitMap};
+
+    #[test]
+    fn iterate_all_types() {
+        let types = vec![
+            PrimitiveType::Array,
+            PrimitiveType::Boolean,
+            PrimitiveType::Integer,
+            PrimitiveType::Null,
+            PrimitiveType::Number,
+            PrimitiveType::Object,
+            PrimitiveType::String,
+        ];
+        let bit_map = PrimitiveTypesBitMap::from(types.clone());
+        assert_eq!(bit_map.into_iter().collect::<Vec<_>>(), types);
+    }
+}
@@ -12,47 +12,93 @@ use std::borrow::Cow;
 use url::Url;
 
 #[derive(Debug)]
-pub(crate) struct Resolver<'a> {
-    // canonical_id: sub-schema mapping to resolve documents by their ID
+pub(crate) struct Resolver {
+    // canonical_id: JSON pointer mapping to resolve sub-schemas of the root document by their ID
     // canonical_id is composed with the root document id
     // (if not specified, then `DEFAULT_ROOT_URL` is used for this purpose)
-    schemas: AHashMap<String, &'a Value>,
+    // Pointers are stored instead of references, so the resolver does not borrow the root
+    // document and could be stored next to it
+    schemas: AHashMap<String, String>,
+    // Sub-schemas of documents in `store` by their canonical IDs, as store keys & JSON pointers.
+    // It allows referencing documents by their `$id`, even if they are stored under other keys
+    stored_schemas: AHashMap<String, (String, String)>,
     store: RwLock<AHashMap<String, Value>>,
 }
 
-impl<'a> Resolver<'a> {
+impl Resolver {
     pub(crate) fn new(
         draft: Draft,
         scope: &Url,
-        schema: &'a Value,
+        schema: &Value,
         store: AHashMap<String, Value>,
-    ) -> Result<Resolver<'a>, CompilationError> {
+    ) -> Result<Resolver, CompilationError> {
         let mut schemas = AHashMap::new();
         // traverse the schema and store all named ones under their canonical ids
-        find_schemas(draft, schema, scope, &mut |id, schema| {
-            schemas.insert(id, schema);
-            None
-        })?;
+        find_schema_pointers(draft, schema, scope, &mut String::new(), &mut schemas)?;
+        let mut stored_schemas = AHashMap::new();
+        for (key, document) in &store {
+            let mut pointers = AHashMap::new();
+            find_schema_pointers(
+                draft,
+                document,
+                &scope.join(key)?,
+                &mut String::new(),
+                &mut pointers,
+            )?;
+            for (id, pointer) in pointers {
+                if !store.contains_key(&id) {
+                    stored_schemas.insert(id, (key.clone(), pointer));
+                }
+            }
+        }
         Ok(Resolver {
             schemas,
+            stored_schemas,
             store: RwLock::new(store),
         })
     }
 
+    /// JSON pointer to the sub-schema of the root document with the given canonical ID.
+    pub(crate) fn pointer_of(&self, url: &str) -> Option<&str> {
+        if url == DEFAULT_ROOT_URL {
+            Some("")
+        } else {
+            self.schemas.get(url).map(String::as_str)
+        }
+    }
+
     /// Load a document for the given `url`.
     /// It may be:
     ///   - the root document (`DEFAULT_ROOT_URL`) case;
     ///   - named subschema that is stored in `self.schemas`;
+    ///   - named subschema of a document from the store;
     ///   - document from a remote location;
-    fn resolve_url(&self, url: &Url, schema: &'a Value) -> Result<Cow<'a, Value>, ValidationError> {
+    fn resolve_url<'a>(
+        &self,
+        url: &Url,
+        schema: &'a Value,
+    ) -> Result<Cow<'a, Value>, ValidationError<'a>> {
         match url.as_str() {
             DEFAULT_ROOT_URL => Ok(Cow::Borrowed(schema)),
             url_str => {
                 if let Some(cached) = self.store.read().get(url_str) {
                     return Ok(Cow::Owned(cached.clone()));
                 }
-                match self.schemas.get(url_str) {
+                match self
+                    .schemas
+                    .get(url_str)
+                    .and_then(|pointer| schema.pointer(pointer))
+                {
                     Some(value) => Ok(Cow::Borrowed(value)),
+                    None if self.stored_schemas.contains_key(url_str) => {
+                        let (key, pointer) = &self.stored_schemas[url_str];
+                        self.store
+                            .read()
+                            .get(key)
+                            .and_then(|document| document.pointer(pointer))
+                            .map(|value| Cow::Owned(value.clone()))
+                            .ok_or_else(|| ValidationError::invalid_reference(url_str.to_string()))
+                    }
                     None => match url.scheme() {
                         "http" | "https" => {
                             #[cfg(any(feature = "reqwest", test))]
@@ -75,12 +121,12 @@ impl<'a> Resolver<'a> {
             }
         }
     }
-    pub(crate) fn resolve_fragment(
+    pub(crate) fn resolve_fragment<'a>(
         &self,
         draft: Draft,
         url: &Url,
         schema: &'a Value,
-    ) -> Result<(Url, Cow<'a, Value>), ValidationError> {
+    ) -> Result<(Url, Cow<'a, Value>), ValidationError<'a>> {
         let mut resource = url.clone();
         resource.set_fragment(None);
         let fragment =
@@ -117,11 +163,9 @@ impl<'a> Resolver<'a> {
     }
 }
 
-fn join_folders(mut resource: Url, folders: &[&str]) -> Result<Url, url::ParseError> {
-    if folders.len() > 1 {
-        for i in folders.iter().skip(1) {
-            resource = resource.join(i)?;
-        }
+pub(crate) fn join_folders(mut resource: Url, folders: &[&str]) -> Result<Url, url::ParseError> {
+    for folder in folders {
+        resource = resource.join(folder)?;
     }
     Ok(resource)
 }
@@ -176,7 +220,51 @@ where
     Ok(None)
 }
 
+/// Find all sub-schemas with IDs in the document and store JSON pointers to them under their canonical IDs.
+fn find_schema_pointers(
+    draft: Draft,
+    schema: &Value,
+    base_url: &Url,
+    pointer: &mut String,
+    schemas: &mut AHashMap<String, String>,
+) -> Result<(), url::ParseError> {
+    match schema {
+        Value::Object(item) => {
+            let mut new_url = None;
+            if let Some(url) = id_of(draft, schema) {
+                let mut url = base_url.join(url)?;
+                // Empty fragments are discouraged and are not distinguishable absent fragments
+                if let Some("") = url.fragment() {
+                    url.set_fragment(None);
+                }
+                schemas.insert(url.to_string(), pointer.clone());
+                new_url = Some(url);
+            }
+            let base_url = new_url.as_ref().unwrap_or(base_url);
+            for (key, subschema) in item {
+                let length = pointer.len();
+                pointer.push('/');
+                pointer.push_str(&key.replace('~', "~0").replace('/', "~1"));
+                find_schema_pointers(draft, subschema, base_url, pointer, schemas)?;
+                pointer.truncate(length);
+            }
+        }
+        Value::Array(items) => {
+            for (idx, item) in items.iter().enumerate() {
+                let length = pointer.len();
+                pointer.push('/');
+                pointer.push_str(&idx.to_string());
+                find_schema_pointers(draft, item, base_url, pointer, schemas)?;
+                pointer.truncate(length);
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
 /// Based on `serde_json`, but tracks folders in the traversed documents.
+/// The document's own identifier is not included as the document is already resolved against it.
 pub(crate) fn pointer<'a>(
     draft: Draft,
     document: &'a Value,
@@ -198,8 +286,10 @@ pub(crate) fn pointer<'a>(
     for token in tokens {
         let target_opt = match *target {
             Value::Object(ref map) => {
-                if let Some(id) = id_of(draft, target) {
-                    folders.push(id);
+                if !std::ptr::eq(target, document) {
+                    if let Some(id) = id_of(draft, target) {
+                        folders.push(id);
+                    }
                 }
                 map.get(&token)
             }
@@ -263,8 +353,11 @@ mod tests {
         // Then in the resolver schema there should be only this schema
         assert_eq!(resolver.schemas.len(), 1);
         assert_eq!(
-            resolver.schemas.get("json-schema:///#foo"),
-            schema.pointer("/definitions/A").as_ref()
+            resolver
+                .schemas
+                .get("json-schema:///#foo")
+                .map(String::as_str),
+            Some("/definitions/A")
         );
     }
 
@@ -283,12 +376,18 @@ mod tests {
         // Then in the resolver schema there should be only these schemas
         assert_eq!(resolver.schemas.len(), 2);
         assert_eq!(
-            resolver.schemas.get("json-schema:///#foo"),
-            schema.pointer("/definitions/A/0").as_ref()
+            resolver
+                .schemas
+                .get("json-schema:///#foo")
+                .map(String::as_str),
+            Some("/definitions/A/0")
         );
         assert_eq!(
-            resolver.schemas.get("json-schema:///#bar"),
-            schema.pointer("/definitions/A/1").as_ref()
+            resolver
+                .schemas
+                .get("json-schema:///#bar")
+                .map(String::as_str),
+            Some("/definitions/A/1")
         );
     }
 
@@ -324,12 +423,18 @@ mod tests {
         // Then in the resolver schema there should be root & sub-schema
         assert_eq!(resolver.schemas.len(), 2);
         assert_eq!(
-            resolver.schemas.get("http://localhost:1234/tree"),
-            schema.pointer("").as_ref()
+            resolver
+                .schemas
+                .get("http://localhost:1234/tree")
+                .map(String::as_str),
+            Some("")
         );
         assert_eq!(
-            resolver.schemas.get("http://localhost:1234/node"),
-            schema.pointer("/definitions/node").as_ref()
+            resolver
+                .schemas
+                .get("http://localhost:1234/node")
+                .map(String::as_str),
+            Some("/definitions/node")
         );
     }
 
@@ -344,8 +449,11 @@ mod tests {
         let resolver = make_resolver(&schema);
         assert_eq!(resolver.schemas.len(), 1);
         assert_eq!(
-            resolver.schemas.get("http://localhost:1234/bar#foo"),
-            schema.pointer("/definitions/A").as_ref()
+            resolver
+                .schemas
+                .get("http://localhost:1234/bar#foo")
+                .map(String::as_str),
+            Some("/definitions/A")
         );
     }
 
@@ -369,18 +477,25 @@ mod tests {
         let resolver = make_resolver(&schema);
         assert_eq!(resolver.schemas.len(), 3);
         assert_eq!(
-            resolver.schemas.get("http://localhost:1234/root"),
-            schema.pointer("").as_ref()
+            resolver
+                .schemas
+                .get("http://localhost:1234/root")
+                .map(String::as_str),
+            Some("")
         );
         assert_eq!(
-            resolver.schemas.get("http://localhost:1234/nested.json"),
-            schema.pointer("/definitions/A").as_ref()
+            resolver
+                .schemas
+                .get("http://localhost:1234/nested.json")
+                .map(String::as_str),
+            Some("/definitions/A")
         );
         assert_eq!(
             resolver
                 .schemas
-                .get("http://localhost:1234/nested.json#foo"),
-            schema.pointer("/definitions/A/definitions/B").as_ref()
+                .get("http://localhost:1234/nested.json#foo")
+                .map(String::as_str),
+            Some("/definitions/A/definitions/B")
         );
     }
 
@@ -396,13 +511,39 @@ mod tests {
         let resolver = make_resolver(&schema);
         assert_eq!(resolver.schemas.len(), 2);
         assert_eq!(
-            resolver.schemas.get("http://localhost:1234/"),
-            schema.pointer("").as_ref()
+            resolver
+                .schemas
+                .get("http://localhost:1234/")
+                .map(String::as_str),
+            Some("")
+        );
+        assert_eq!(
+            resolver
+                .schemas
+                .get("http://localhost:1234/folder/")
+                .map(String::as_str),
+            Some("/items")
         );
+    }
+
+    #[test]
+    fn escaped_pointer() {
+        let schema = json!({
+            "definitions": {
+                "a/b~c": {"$id": "#foo", "type": "integer"}
+            }
+        });
+        let resolver = make_resolver(&schema);
         assert_eq!(
-            resolver.schemas.get("http://localhost:1234/folder/"),
-            schema.pointer("/items").as_ref()
+            resolver
+                .schemas
+                .get("json-schema:///#foo")
+                .map(String::as_str),
+            Some("/definitions/a~1b~0c")
         );
+        let url = Url::parse("json-schema:///#foo").unwrap();
+        let resolved = resolver.resolve_url(&url, &schema).unwrap();
+        assert_eq!(resolved.as_ref(), &schema["definitions"]["a/b~c"]);
     }
 
     #[test]
@@ -426,12 +567,16 @@ mod tests {
         assert_eq!(
             resolver
                 .schemas
-                .get("http://localhost:1234/scope_change_defs1.json"),
-            schema.pointer("").as_ref()
+                .get("http://localhost:1234/scope_change_defs1.json")
+                .map(String::as_str),
+            Some("")
         );
         assert_eq!(
-            resolver.schemas.get("http://localhost:1234/folder/"),
-            schema.pointer("/definitions/baz").as_ref()
+            resolver
+                .schemas
+                .get("http://localhost:1234/folder/")
+                .map(String::as_str),
+            Some("/definitions/baz")
         );
     }
 
@@ -456,6 +601,86 @@ mod tests {
         }
     }
 
+    #[test]
+    fn nested_id_changes_base_uri() {
+        // When a sub-schema changes the base URI
+        let schema = json!({
+            "properties": {
+                "foo": {
+                    "$id": "http://example.com/nested/",
+                    "properties": {
+                        "bar": {"$ref": "item.json"}
+                    }
+                }
+            }
+        });
+        let compiled = JSONSchema::options()
+            .with_document(
+                "http://example.com/nested/item.json".to_string(),
+                json!({"type": "integer"}),
+            )
+            .compile(&schema)
+            .unwrap();
+        // Then relative references inside it are resolved against the new base URI
+        assert!(compiled.is_valid(&json!({"foo": {"bar": 1}})));
+        assert!(!compiled.is_valid(&json!({"foo": {"bar": "a"}})));
+    }
+
+    #[test]
+    fn nested_id_changes_base_uri_for_referenced_schema() {
+        // When a referenced schema is located inside a sub-schema with its own base URI
+        // and the root document has no ID
+        let schema = json!({
+            "$ref": "#/definitions/A/definitions/B",
+            "definitions": {
+                "A": {
+                    "$id": "http://example.com/nested/",
+                    "definitions": {
+                        "B": {"$ref": "item.json"}
+                    }
+                }
+            }
+        });
+        let compiled = JSONSchema::options()
+            .with_document(
+                "http://example.com/nested/item.json".to_string(),
+                json!({"type": "integer"}),
+            )
+            .compile(&schema)
+            .unwrap();
+        // Then the reference inside it is resolved against the nearest base URI
+        assert!(compiled.is_valid(&json!(1)));
+        assert!(!compiled.is_valid(&json!("a")));
+    }
+
+    #[test]
+    fn ref_prevents_sibling_id_from_changing_base_uri() {
+        let schema = json!({
+            "$id": "http://example.com/base/",
+            "allOf": [
+                {
+                    "$id": "http://example.com/",
+                    "$ref": "item.json"
+                }
+            ]
+        });
+        let resolver = make_resolver(&schema);
+        assert_eq!(resolver.schemas.len(), 1);
+        let compiled = JSONSchema::options()
+            .with_document(
+                "http://example.com/base/item.json".to_string(),
+                json!({"type": "integer"}),
+            )
+            .with_document(
+                "http://example.com/item.json".to_string(),
+                json!({"type": "string"}),
+            )
+            .compile(&schema)
+            .unwrap();
+        assert!(compiled.is_valid(&json!(1)));
+        assert!(!compiled.is_valid(&json!("a")));
+    }
+
     #[test]
     fn id_value_is_cleaned() {
         let schema = json!({
@@ -478,4 +703,55 @@ mod tests {
             .schemas
             .contains_key("http://foo.com/schema.json"));
     }
+
+    #[test]
+    fn stored_document_by_id() {
+        // When a stored document declares its own ID, different from its key in the store
+        let person = json!({
+            "$id": "https://example.com/schemas/person",
+            "definitions": {
+                "address": {"type": "string"}
+            }
+        });
+        let schema = json!({
+            "properties": {
+                "address": {"$ref": "https://example.com/schemas/person#/definitions/address"}
+            }
+        });
+        let compiled = JSONSchema::options()
+            .with_document("person.json".to_string(), person)
+            .compile(&schema)
+            .unwrap();
+        // Then it is referenced by that ID without network requests
+        assert!(compiled.is_valid(&json!({"address": "Main St."})));
+        assert!(!compiled.is_valid(&json!({"address": 42})));
+    }
+
+    #[test]
+    fn stored_document_nested_id() {
+        // When a sub-schema of a stored document has its own ID
+        let library = json!({
+            "$id": "https://example.com/schemas/",
+            "definitions": {
+                "person": {
+                    "$id": "person",
+                    "definitions": {
+                        "address": {"type": "string"}
+                    }
+                }
+            }
+        });
+        let schema = json!({"$ref": "https://example.com/schemas/person#/definitions/address"});
+        let compiled = JSONSchema::options()
+            .with_document("https://example.com/schemas/".to_string(), library)
+            .compile(&schema)
+            .unwrap();
+        // Then it is resolvable by its ID, relative to the base URI of the stored document
+        assert!(compiled.is_valid(&json!("Main St.")));
+        assert!(!compiled.is_valid(&json!(42)));
+        assert!(compiled
+            .resolver
+            .stored_schemas
+            .contains_key("https://example.com/schemas/person"));
+    }
 }
@@ -1,8 +1,10 @@
 use crate::{compilation::context::CompilationContext, keywords};
+use ahash::AHashMap;
 use serde_json::{Map, Value};
 
 /// JSON Schema Draft version
 #[derive(Debug, PartialEq, Copy, Clone)]
+#[non_exhaustive]
 pub enum Draft {
     /// JSON Schema Draft 4
     Draft4,
@@ -10,6 +12,9 @@ pub enum Draft {
     Draft6,
     /// JSON Schema Draft 7
     Draft7,
+    /// JSON Schema Draft 2019-09 (partial support).
+    /// It uses the Draft 7 vocabulary, but keywords alongside `$ref` are not ignored.
+    Draft201909,
 }
 
 impl Default for Draft {
@@ -18,87 +23,125 @@ impl Default for Draft {
     }
 }
 
+/// Keywords that do not affect validation.
+pub(crate) const ANNOTATION_KEYWORDS: &[&str] = &[
+    "$comment",
+    "$defs",
+    "$id",
+    "$schema",
+    "default",
+    "definitions",
+    "deprecated",
+    "description",
+    "examples",
+    "id",
+    "readOnly",
+    "title",
+    "writeOnly",
+];
+
 type CompileFunc =
     fn(&Map<String, Value>, &Value, &CompilationContext) -> Option<keywords::CompilationResult>;
 
 impl Draft {
+    /// Whether all keywords alongside `$ref` should be ignored.
+    /// Starting from Draft 2019-09 they are evaluated together with `$ref`.
+    #[inline]
+    pub(crate) fn ignores_ref_siblings(self) -> bool {
+        !matches!(self, Draft::Draft201909)
+    }
+
+    /// Compilation function for `keyword` if it is a part of this draft's vocabulary.
+    #[inline]
     pub(crate) fn get_validator(self, keyword: &str) -> Option<CompileFunc> {
-        match keyword {
-            "additionalItems" => Some(keywords::additional_items::compile),
-            "additionalProperties" => Some(keywords::additional_properties::compile),
-            "allOf" => Some(keywords::all_of::compile),
-            "anyOf" => Some(keywords::any_of::compile),
-            "const" => match self {
-                Draft::Draft4 => None,
-                Draft::Draft6 | Draft::Draft7 => Some(keywords::const_::compile),
-            },
-            "contains" => match self {
-                Draft::Draft4 => None,
-                Draft::Draft6 | Draft::Draft7 => Some(keywords::contains::compile),
-            },
-            "contentMediaType" => match self {
-                Draft::Draft7 | Draft::Draft6 => Some(keywords::content::compile_media_type),
-                Draft::Draft4 => None,
-            },
-            "contentEncoding" => match self {
-                Draft::Draft7 | Draft::Draft6 => Some(keywords::content::compile_content_encoding),
-                Draft::Draft4 => None,
-            },
-            "dependencies" => Some(keywords::dependencies::compile),
-            "enum" => Some(keywords::enum_::compile),
-            "exclusiveMaximum" => match self {
-                Draft::Draft7 | Draft::Draft6 => Some(keywords::exclusive_maximum::compile),
-                Draft::Draft4 => None,
-            },
-            "exclusiveMinimum" => match self {
-                Draft::Draft7 | Draft::Draft6 => Some(keywords::exclusive_minimum::compile),
-                Draft::Draft4 => None,
-            },
-            "format" => Some(keywords::format::compile),
-            "if" => match self {
-                Draft::Draft7 => Some(keywords::if_::compile),
-                Draft::Draft6 | Draft::Draft4 => None,
-            },
-            "items" => Some(keywords::items::compile),
-            "maximum" => match self {
-                Draft::Draft4 => Some(keywords::legacy::maximum_draft_4::compile),
-                Draft::Draft6 | Draft::Draft7 => Some(keywords::maximum::compile),
-            },
-            "maxItems" => Some(keywords::max_items::compile),
-            "maxLength" => Some(keywords::max_length::compile),
-            "maxProperties" => Some(keywords::max_properties::compile),
-            "minimum" => match self {
-                Draft::Draft4 => Some(keywords::legacy::minimum_draft_4::compile),
-                Draft::Draft6 | Draft::Draft7 => Some(keywords::minimum::compile),
-            },
-            "minItems" => Some(keywords::min_items::compile),
-            "minLength" => Some(keywords::min_length::compile),
-            "minProperties" => Some(keywords::min_properties::compile),
-            "multipleOf" => Some(keywords::multiple_of::compile),
-            "not" => Some(keywords::not::compile),
-            "oneOf" => Some(keywords::one_of::compile),
-            "pattern" => Some(keywords::pattern::compile),
-            "patternProperties" => Some(keywords::pattern_properties::compile),
-            "properties" => Some(keywords::properties::compile),
-            "propertyNames" => match self {
-                Draft::Draft4 => None,
-                Draft::Draft6 | Draft::Draft7 => Some(keywords::property_names::compile),
-            },
-            "required" => Some(keywords::required::compile),
-            "type" => match self {
-                Draft::Draft4 => Some(keywords::legacy::type_draft_4::compile),
-                Draft::Draft6 | Draft::Draft7 => Some(keywords::type_::compile),
-            },
-            "uniqueItems" => Some(keywords::unique_items::compile),
-            _ => None,
-        }
+        let keywords: &KeywordMap = match self {
+            Draft::Draft4 => &DRAFT4_KEYWORDS,
+            Draft::Draft6 => &DRAFT6_KEYWORDS,
+            // Draft 2019-09 uses the Draft 7 vocabulary
+            Draft::Draft7 | Draft::Draft201909 => &DRAFT7_KEYWORDS,
+        };
+        keywords.get(keyword).copied()
+    }
+
+    /// Whether `keyword` is defined by this draft, including keywords that do not produce
+    /// validators on their own, like annotations or `then`.
+    #[inline]
+    pub(crate) fn is_known_keyword(self, keyword: &str) -> bool {
+        self.get_validator(keyword).is_some()
+            || ANNOTATION_KEYWORDS.contains(&keyword)
+            || keyword == "$ref"
+            // Handled by `if`
+            || (matches!(keyword, "then" | "else") && self.get_validator("if").is_some())
     }
 }
 
+type KeywordMap = AHashMap<&'static str, CompileFunc>;
+
+lazy_static::lazy_static! {
+    static ref DRAFT4_KEYWORDS: KeywordMap = keywords_of(Draft::Draft4);
+    static ref DRAFT6_KEYWORDS: KeywordMap = keywords_of(Draft::Draft6);
+    static ref DRAFT7_KEYWORDS: KeywordMap = keywords_of(Draft::Draft7);
+}
+
+/// Build a keyword lookup table for the given draft.
+fn keywords_of(draft: Draft) -> KeywordMap {
+    let mut keywords: KeywordMap = AHashMap::with_capacity(40);
+    keywords.insert("additionalItems", keywords::additional_items::compile);
+    keywords.insert(
+        "additionalProperties",
+        keywords::additional_properties::compile,
+    );
+    keywords.insert("allOf", keywords::all_of::compile);
+    keywords.insert("anyOf", keywords::any_of::compile);
+    keywords.insert("dependencies", keywords::dependencies::compile);
+    keywords.insert("enum", keywords::enum_::compile);
+    keywords.insert("format", keywords::format::compile);
+    keywords.insert("items", keywords::items::compile);
+    keywords.insert("maxItems", keywords::max_items::compile);
+    keywords.insert("maxLength", keywords::max_length::compile);
+    keywords.insert("maxProperties", keywords::max_properties::compile);
+    keywords.insert("minItems", keywords::min_items::compile);
+    keywords.insert("minLength", keywords::min_length::compile);
+    keywords.insert("minProperties", keywords::min_properties::compile);
+    keywords.insert("multipleOf", keywords::multiple_of::compile);
+    keywords.insert("not", keywords::not::compile);
+    keywords.insert("oneOf", keywords::one_of::compile);
+    keywords.insert("pattern", keywords::pattern::compile);
+    keywords.insert("patternProperties", keywords::pattern_properties::compile);
+    keywords.insert("properties", keywords::properties::compile);
+    keywords.insert("required", keywords::required::compile);
+    keywords.insert("uniqueItems", keywords::unique_items::compile);
+    if draft == Draft::Draft4 {
+        keywords.insert("maximum", keywords::legacy::maximum_draft_4::compile);
+        keywords.insert("minimum", keywords::legacy::minimum_draft_4::compile);
+        keywords.insert("type", keywords::legacy::type_draft_4::compile);
+    } else {
+        keywords.insert("const", keywords::const_::compile);
+        keywords.insert("contains", keywords::contains::compile);
+        keywords.insert("contentMediaType", keywords::content::compile_media_type);
+        keywords.insert(
+            "contentEncoding",
+            keywords::content::compile_content_encoding,
+        );
+        keywords.insert("exclusiveMaximum", keywords::exclusive_maximum::compile);
+        keywords.insert("exclusiveMinimum", keywords::exclusive_minimum::compile);
+        keywords.insert("maximum", keywords::maximum::compile);
+        keywords.insert("minimum", keywords::minimum::compile);
+        keywords.insert("propertyNames", keywords::property_names::compile);
+        keywords.insert("type", keywords::type_::compile);
+    }
+    if matches!(draft, Draft::Draft7 | Draft::Draft201909) {
+        keywords.insert("if", keywords::if_::compile);
+    }
+    keywords
+}
+
 /// Get the `Draft` from a JSON Schema URL.
 #[inline]
 pub(crate) fn draft_from_url(url: &str) -> Option<Draft> {
     match url {
+        "https://json-schema.org/draft/2019-09/schema"
+        | "https://json-schema.org/draft/2019-09/schema#" => Some(Draft::Draft201909),
         "http://json-schema.org/draft-07/schema#" => Some(Draft::Draft7),
         "http://json-schema.org/draft-06/schema#" => Some(Draft::Draft6),
         "http://json-schema.org/draft-04/schema#" => Some(Draft::Draft4),
@@ -115,9 +158,14 @@ pub(crate) fn draft_from_schema(schema: &Value) -> Option<Draft> {
         .and_then(draft_from_url)
 }
 
+/// Get the identifier of a schema, that changes the base URI for its sub-schemas.
+/// Before Draft 2019-09 all keywords alongside `$ref` are ignored, including `$id`.
 #[inline]
 pub(crate) fn id_of(draft: Draft, schema: &Value) -> Option<&str> {
     if let Value::Object(object) = schema {
+        if draft.ignores_ref_siblings() && object.contains_key("$ref") {
+            return None;
+        }
         if draft == Draft::Draft4 {
             object.get("id")
         } else {
@@ -138,11 +186,44 @@ mod tests {
     #[test_case(&json!({"$schema": "http://json-schema.org/draft-07/schema#"}), Some(Draft::Draft7))]
     #[test_case(&json!({"$schema": "http://json-schema.org/draft-06/schema#"}), Some(Draft::Draft6))]
     #[test_case(&json!({"$schema": "http://json-schema.org/draft-04/schema#"}), Some(Draft::Draft4))]
+    #[test_case(&json!({"$schema": "https://json-schema.org/draft/2019-09/schema"}), Some(Draft::Draft201909))]
     #[test_case(&json!({"$schema": "http://example.com/custom/schema#"}), None)]
     fn test_draft_from_schema(schema: &Value, draft: Option<Draft>) {
         assert_eq!(draft_from_schema(schema), draft)
     }
 
+    #[test_case(Draft::Draft4, &json!({"id": "http://example.com/"}), Some("http://example.com/"))]
+    #[test_case(Draft::Draft7, &json!({"$id": "http://example.com/"}), Some("http://example.com/"))]
+    #[test_case(Draft::Draft7, &json!({"id": "http://example.com/"}), None)]
+    #[test_case(Draft::Draft7, &json!({"$id": "http://example.com/", "$ref": "#/definitions/a"}), None)]
+    #[test_case(Draft::Draft201909, &json!({"$id": "http://example.com/", "$ref": "#/definitions/a"}), Some("http://example.com/"))]
+    fn test_id_of(draft: Draft, schema: &Value, expected: Option<&str>) {
+        assert_eq!(id_of(draft, schema), expected)
+    }
+
+    #[test_case(Draft::Draft4, "type", true)]
+    #[test_case(Draft::Draft4, "const", false)]
+    #[test_case(Draft::Draft4, "if", false)]
+    #[test_case(Draft::Draft6, "const", true)]
+    #[test_case(Draft::Draft6, "if", false)]
+    #[test_case(Draft::Draft7, "if", true)]
+    #[test_case(Draft::Draft201909, "if", true)]
+    #[test_case(Draft::Draft7, "title", false)]
+    #[test_case(Draft::Draft7, "unknown", false)]
+    fn test_get_validator(draft: Draft, keyword: &str, expected: bool) {
+        assert_eq!(draft.get_validator(keyword).is_some(), expected)
+    }
+
+    #[test_case(Draft::Draft7, "title", true)]
+    #[test_case(Draft::Draft7, "then", true)]
+    #[test_case(Draft::Draft6, "then", false)]
+    #[test_case(Draft::Draft4, "$ref", true)]
+    #[test_case(Draft::Draft4, "const", false)]
+    #[test_case(Draft::Draft7, "minimun", false)]
+    fn test_is_known_keyword(draft: Draft, keyword: &str, expected: bool) {
+        assert_eq!(draft.is_known_keyword(keyword), expected)
+    }
+
     #[test]
     fn test_default() {
         assert_eq!(Draft::default(), Draft::Draft7)
@@ -0,0 +1,107 @@
+//! Application data available to custom keywords during validation.
+use ahash::AHashMap;
+use std::{
+    any::{Any, TypeId},
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+/// User-provided data for custom keywords, e.g. a database handle or a request context.
+/// Values are looked up by their type.
+///
+/// ```rust
+/// # use jsonschema::ValidationContext;
+/// struct UserId(u64);
+///
+/// let mut context = ValidationContext::new();
+/// context.insert(UserId(42));
+/// assert_eq!(context.get::<UserId>().map(|user| user.0), Some(42));
+/// assert!(context.get::<String>().is_none());
+/// ```
+#[derive(Default)]
+pub struct ValidationContext {
+    data: Arc<AHashMap<TypeId, Arc<dyn Any + Send + Sync>>>,
+    /// The number of errors `JSONSchema::validate_limited` still reports. Nested validators stop
+    /// collecting errors beyond it.
+    budget: Option<AtomicUsize>,
+}
+
+impl ValidationContext {
+    /// An empty context.
+    #[must_use]
+    #[inline]
+    pub fn new() -> Self {
+        ValidationContext::default()
+    }
+
+    /// Add a value to the context. A previous value of the same type is replaced.
+    #[inline]
+    pub fn insert<T: Any + Send + Sync>(&mut self, value: T) -> &mut Self {
+        Arc::make_mut(&mut self.data).insert(TypeId::of::<T>(), Arc::new(value));
+        self
+    }
+
+    /// A value of type `T`, if it was added to the context.
+    #[must_use]
+    #[inline]
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.data
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref::<T>())
+    }
+
+    /// The same data with a budget of `limit` errors.
+    pub(crate) fn with_budget(&self, limit: usize) -> ValidationContext {
+        ValidationContext {
+            data: Arc::clone(&self.data),
+            budget: Some(AtomicUsize::new(limit)),
+        }
+    }
+
+    /// The number of errors worth collecting from nested validators. The budget is spent only
+    /// by errors reported to the caller, see `spend`.
+    #[inline]
+    pub(crate) fn error_budget(&self) -> usize {
+        self.budget
+            .as_ref()
+            .map_or(usize::MAX, |budget| budget.load(Ordering::Relaxed))
+    }
+
+    /// Spend the budget on an error reported to the caller.
+    #[inline]
+    pub(crate) fn spend(&self) {
+        if let Some(budget) = &self.budget {
+            let _ = budget.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |remaining| {
+                remaining.checked_sub(1)
+            });
+        }
+    }
+}
+
+impl Clone for ValidationContext {
+    fn clone(&self) -> Self {
+        ValidationContext {
+            data: Arc::clone(&self.data),
+            budget: self
+                .budget
+                .as_ref()
+                .map(|budget| AtomicUsize::new(budget.load(Ordering::Relaxed))),
+        }
+    }
+}
+
+impl fmt::Debug for ValidationContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValidationContext")
+            .field("len", &self.data.len())
+            .finish()
+    }
+}
+
+lazy_static::lazy_static! {
+    /// The context of validation calls without user-provided data.
+    pub(crate) static ref EMPTY: ValidationContext = ValidationContext::new();
+}
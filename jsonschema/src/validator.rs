@@ -1,17 +1,104 @@
-use crate::{compilation::JSONSchema, error::ErrorIterator};
+use crate::{
+    compilation::JSONSchema, error::ErrorIterator, output::PartialApplication, paths::JSONPointer,
+    validation_context::ValidationContext,
+};
 use serde_json::Value;
 use std::fmt;
 
-pub(crate) trait Validate: Send + Sync + ToString {
-    fn validate<'a>(&self, schema: &'a JSONSchema, instance: &'a Value) -> ErrorIterator<'a>;
+/// The minimal object-safe validation surface.
+/// Textual representation is not required - see `ValidateDisplay`.
+pub(crate) trait Validate: Send + Sync {
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a>;
     // The same as above, but does not construct ErrorIterator.
     // It is faster for cases when the result is not needed (like anyOf), since errors are
     // not constructed
-    fn is_valid(&self, schema: &JSONSchema, instance: &Value) -> bool;
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool;
+    // Validate the instance and collect annotations. Validators that produce annotations, or
+    // apply sub-schemas that may produce them, should override this method.
+    fn apply<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> PartialApplication<'a> {
+        self.validate(schema, instance, instance_path, ctx)
+            .collect::<Vec<_>>()
+            .into()
+    }
 }
 
-impl fmt::Debug for dyn Validate + Send + Sync {
+/// Boxed validators, e.g. compiled keywords, can be used wherever a `Validate` is expected.
+impl<T: Validate + ?Sized> Validate for Box<T> {
+    #[inline]
+    fn validate<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> ErrorIterator<'a> {
+        (**self).validate(schema, instance, instance_path, ctx)
+    }
+    #[inline]
+    fn is_valid(&self, schema: &JSONSchema, instance: &Value, ctx: &ValidationContext) -> bool {
+        (**self).is_valid(schema, instance, ctx)
+    }
+    #[inline]
+    fn apply<'a>(
+        &self,
+        schema: &'a JSONSchema,
+        instance: &'a Value,
+        instance_path: &JSONPointer,
+        ctx: &'a ValidationContext,
+    ) -> PartialApplication<'a> {
+        (**self).apply(schema, instance, instance_path, ctx)
+    }
+}
+
+/// Validators that can be displayed as the part of the schema they were compiled from.
+/// Used for compiled keywords, where the representation is needed for `Debug` output.
+pub(crate) trait ValidateDisplay: Validate + fmt::Display {}
+
+impl<T: Validate + fmt::Display> ValidateDisplay for T {}
+
+impl fmt::Debug for dyn ValidateDisplay + Send + Sync {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_str(&self.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Validate;
+    use crate::{compilation::JSONSchema, paths::JSONPointer, ValidationContext};
+    use serde_json::{json, Value};
+
+    fn errors<V: Validate>(validator: &V, schema: &JSONSchema, instance: &Value) -> usize {
+        validator
+            .validate(
+                schema,
+                instance,
+                &JSONPointer::default(),
+                &ValidationContext::new(),
+            )
+            .count()
+    }
+
+    #[test]
+    fn boxed_validator() {
+        let schema = json!({"minLength": 3});
+        let compiled = JSONSchema::compile(&schema).unwrap();
+        // `Box<dyn ValidateDisplay>` is a `Validate` itself
+        let validator = &compiled.validators[0];
+        assert!(validator.is_valid(&compiled, &json!("foo"), &ValidationContext::new()));
+        assert_eq!(errors(validator, &compiled, &json!("fo")), 1);
+        assert_eq!(errors(validator, &compiled, &json!("foo")), 0);
+    }
+}
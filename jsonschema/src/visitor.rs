@@ -0,0 +1,263 @@
+//! Traversal of schemas for analysis tools, e.g. documentation or code generators.
+use crate::{
+    compilation::JSONSchema,
+    paths::JSONPointer,
+    primitive_type::{PrimitiveType, PrimitiveTypesBitMap},
+};
+use serde_json::{Map, Value};
+use std::convert::TryFrom;
+
+/// A visitor over a schema and all its sub-schemas.
+///
+/// Every method has a no-op default implementation, therefore only the relevant ones need to be
+/// implemented. For each sub-schema `visit_schema` is called first, then `visit_keyword` and
+/// a keyword-specific method (if any) are called for each of its keywords.
+/// References are not followed.
+///
+/// ```rust
+/// # use jsonschema::{JSONSchema, SchemaVisitor};
+/// # use serde_json::json;
+/// #[derive(Default)]
+/// struct Patterns(Vec<String>);
+///
+/// impl SchemaVisitor for Patterns {
+///     fn visit_pattern(&mut self, pattern: &str) {
+///         self.0.push(pattern.to_string());
+///     }
+/// }
+///
+/// let schema = json!({"properties": {"id": {"pattern": "^[0-9]+$"}}, "pattern": "^a"});
+/// let compiled = JSONSchema::compile(&schema).expect("A valid schema");
+/// let mut visitor = Patterns::default();
+/// compiled.accept(&mut visitor);
+/// assert_eq!(visitor.0, vec!["^a", "^[0-9]+$"]);
+/// ```
+pub trait SchemaVisitor {
+    /// A schema or a sub-schema located at `path` (a JSON Pointer) within the root schema.
+    fn visit_schema(&mut self, _path: &str, _schema: &Value) {}
+    /// Any keyword of the current schema, including unknown ones.
+    fn visit_keyword(&mut self, _keyword: &str, _value: &Value) {}
+    /// `type`.
+    fn visit_type(&mut self, _types: PrimitiveTypesBitMap) {}
+    /// `minimum`.
+    fn visit_minimum(&mut self, _limit: f64) {}
+    /// `maximum`.
+    fn visit_maximum(&mut self, _limit: f64) {}
+    /// `minLength`.
+    fn visit_min_length(&mut self, _limit: u64) {}
+    /// `maxLength`.
+    fn visit_max_length(&mut self, _limit: u64) {}
+    /// `pattern`.
+    fn visit_pattern(&mut self, _pattern: &str) {}
+    /// `format`.
+    fn visit_format(&mut self, _format: &str) {}
+    /// `enum`.
+    fn visit_enum(&mut self, _options: &[Value]) {}
+    /// `const`.
+    fn visit_const(&mut self, _value: &Value) {}
+    /// `required`.
+    fn visit_required(&mut self, _properties: &[&str]) {}
+    /// `properties`. Sub-schemas of each property are visited afterwards.
+    fn visit_properties(&mut self, _properties: &Map<String, Value>) {}
+}
+
+impl<'a> JSONSchema<'a> {
+    /// Traverse the schema with `visitor`.
+    pub fn accept(&self, visitor: &mut impl SchemaVisitor) {
+        walk(&self.schema, &JSONPointer::default(), visitor)
+    }
+}
+
+fn walk(schema: &Value, path: &JSONPointer, visitor: &mut impl SchemaVisitor) {
+    visitor.visit_schema(&path.to_string(), schema);
+    if let Value::Object(object) = schema {
+        for (keyword, value) in object {
+            visitor.visit_keyword(keyword, value);
+            visit_keyword(keyword, value, visitor);
+        }
+        for (keyword, value) in object {
+            let path = path.extend_with(keyword.as_str());
+            match (keyword.as_str(), value) {
+                (
+                    "additionalItems"
+                    | "additionalProperties"
+                    | "contains"
+                    | "else"
+                    | "if"
+                    | "not"
+                    | "propertyNames"
+                    | "then",
+                    _,
+                )
+                | ("items", Value::Object(_) | Value::Bool(_)) => walk(value, &path, visitor),
+                ("allOf" | "anyOf" | "oneOf" | "items", Value::Array(items)) => {
+                    for (idx, item) in items.iter().enumerate() {
+                        walk(item, &path.extend_with(idx), visitor)
+                    }
+                }
+                ("definitions" | "patternProperties" | "properties", Value::Object(map)) => {
+                    for (name, subschema) in map {
+                        walk(subschema, &path.extend_with(name.as_str()), visitor)
+                    }
+                }
+                ("dependencies", Value::Object(map)) => {
+                    // Array values are property dependencies, not sub-schemas
+                    for (name, subschema) in map.iter().filter(|(_, value)| !value.is_array()) {
+                        walk(subschema, &path.extend_with(name.as_str()), visitor)
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn visit_keyword(keyword: &str, value: &Value, visitor: &mut impl SchemaVisitor) {
+    match (keyword, value) {
+        ("type", Value::String(_) | Value::Array(_)) => {
+            let items = match value {
+                Value::Array(items) => items.as_slice(),
+                _ => std::slice::from_ref(value),
+            };
+            let mut types = PrimitiveTypesBitMap::new();
+            for type_ in items
+                .iter()
+                .filter_map(Value::as_str)
+                .filter_map(|type_| PrimitiveType::try_from(type_).ok())
+            {
+                types |= type_;
+            }
+            visitor.visit_type(types)
+        }
+        ("minimum", Value::Number(limit)) => {
+            visitor.visit_minimum(limit.as_f64().expect("Always valid"))
+        }
+        ("maximum", Value::Number(limit)) => {
+            visitor.visit_maximum(limit.as_f64().expect("Always valid"))
+        }
+        ("minLength", Value::Number(limit)) => {
+            if let Some(limit) = limit.as_u64() {
+                visitor.visit_min_length(limit)
+            }
+        }
+        ("maxLength", Value::Number(limit)) => {
+            if let Some(limit) = limit.as_u64() {
+                visitor.visit_max_length(limit)
+            }
+        }
+        ("pattern", Value::String(pattern)) => visitor.visit_pattern(pattern),
+        ("format", Value::String(format)) => visitor.visit_format(format),
+        ("enum", Value::Array(options)) => visitor.visit_enum(options),
+        ("const", _) => visitor.visit_const(value),
+        ("required", Value::Array(properties)) => {
+            let properties: Vec<_> = properties.iter().filter_map(Value::as_str).collect();
+            visitor.visit_required(&properties)
+        }
+        ("properties", Value::Object(properties)) => visitor.visit_properties(properties),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SchemaVisitor;
+    use crate::{
+        compilation::JSONSchema,
+        primitive_type::{PrimitiveType, PrimitiveTypesBitMap},
+    };
+    use serde_json::{json, Map, Value};
+
+    #[derive(Default)]
+    struct Recorder {
+        paths: Vec<String>,
+        keywords: Vec<String>,
+        types: Vec<PrimitiveTypesBitMap>,
+        limits: Vec<f64>,
+        required: Vec<String>,
+        properties: Vec<String>,
+    }
+
+    impl SchemaVisitor for Recorder {
+        fn visit_schema(&mut self, path: &str, _: &Value) {
+            self.paths.push(path.to_string())
+        }
+        fn visit_keyword(&mut self, keyword: &str, _: &Value) {
+            self.keywords.push(keyword.to_string())
+        }
+        fn visit_type(&mut self, types: PrimitiveTypesBitMap) {
+            self.types.push(types)
+        }
+        fn visit_minimum(&mut self, limit: f64) {
+            self.limits.push(limit)
+        }
+        fn visit_maximum(&mut self, limit: f64) {
+            self.limits.push(limit)
+        }
+        fn visit_required(&mut self, properties: &[&str]) {
+            self.required
+                .extend(properties.iter().map(|name| (*name).to_string()))
+        }
+        fn visit_properties(&mut self, properties: &Map<String, Value>) {
+            self.properties.extend(properties.keys().cloned())
+        }
+    }
+
+    fn record(schema: &Value) -> Recorder {
+        let compiled = JSONSchema::compile(schema).unwrap();
+        let mut recorder = Recorder::default();
+        compiled.accept(&mut recorder);
+        recorder
+    }
+
+    #[test]
+    fn visits_sub_schemas() {
+        let recorder = record(&json!({
+            "allOf": [{"minimum": 1}, true],
+            "items": [{"maximum": 5}],
+            "properties": {"a": {"not": {}}},
+            "dependencies": {"b": ["a"], "c": {}},
+            "definitions": {"d": {"$ref": "#"}}
+        }));
+        assert_eq!(
+            recorder.paths,
+            vec![
+                "",
+                "/allOf/0",
+                "/allOf/1",
+                "/definitions/d",
+                "/dependencies/c",
+                "/items/0",
+                "/properties/a",
+                "/properties/a/not"
+            ]
+        );
+        assert_eq!(recorder.limits, vec![1., 5.]);
+        assert_eq!(recorder.properties, vec!["a"]);
+        assert!(recorder.keywords.contains(&"$ref".to_string()));
+    }
+
+    #[test]
+    fn visits_keywords() {
+        let recorder = record(&json!({
+            "type": ["array", "null"],
+            "required": ["a", "b"],
+            "custom": 1
+        }));
+        assert_eq!(
+            recorder.types,
+            vec![PrimitiveTypesBitMap::from(vec![
+                PrimitiveType::Array,
+                PrimitiveType::Null
+            ])]
+        );
+        assert_eq!(recorder.required, vec!["a", "b"]);
+        assert_eq!(recorder.keywords, vec!["custom", "required", "type"]);
+    }
+
+    #[test]
+    fn single_type() {
+        let recorder = record(&json!({"type": "string"}));
+        let types: Vec<_> = recorder.types[0].into_iter().collect();
+        assert_eq!(types, vec![PrimitiveType::String]);
+    }
+}
@@ -0,0 +1,230 @@
+//! Vocabularies declared by custom meta-schemas via the `$vocabulary` keyword.
+//! Keywords of known vocabularies that are not declared by the meta-schema are ignored.
+use crate::error::CompilationError;
+use serde_json::Value;
+
+/// A known vocabulary of Draft 2019-09 or Draft 2020-12.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Vocabulary {
+    Core,
+    Applicator,
+    Unevaluated,
+    Validation,
+    MetaData,
+    Format,
+    FormatAnnotation,
+    Content,
+}
+
+impl Vocabulary {
+    const ALL: [Vocabulary; 8] = [
+        Vocabulary::Core,
+        Vocabulary::Applicator,
+        Vocabulary::Unevaluated,
+        Vocabulary::Validation,
+        Vocabulary::MetaData,
+        Vocabulary::Format,
+        Vocabulary::FormatAnnotation,
+        Vocabulary::Content,
+    ];
+
+    fn from_uri(uri: &str) -> Option<Vocabulary> {
+        let name = uri
+            .strip_prefix("https://json-schema.org/draft/2019-09/vocab/")
+            .or_else(|| uri.strip_prefix("https://json-schema.org/draft/2020-12/vocab/"))?;
+        match name {
+            "core" => Some(Vocabulary::Core),
+            "applicator" => Some(Vocabulary::Applicator),
+            "unevaluated" => Some(Vocabulary::Unevaluated),
+            "validation" => Some(Vocabulary::Validation),
+            "meta-data" => Some(Vocabulary::MetaData),
+            // Draft 2019-09 has a single vocabulary that asserts formats
+            "format" | "format-assertion" => Some(Vocabulary::Format),
+            "format-annotation" => Some(Vocabulary::FormatAnnotation),
+            "content" => Some(Vocabulary::Content),
+            _ => None,
+        }
+    }
+
+    /// Supported keywords that are defined by this vocabulary.
+    /// Core keywords, annotations and not supported keywords are not listed.
+    fn keywords(self) -> &'static [&'static str] {
+        match self {
+            Vocabulary::Applicator => &[
+                "additionalItems",
+                "additionalProperties",
+                "allOf",
+                "anyOf",
+                "contains",
+                "dependencies",
+                "else",
+                "if",
+                "items",
+                "not",
+                "oneOf",
+                "patternProperties",
+                "properties",
+                "propertyNames",
+                "then",
+            ],
+            Vocabulary::Validation => &[
+                "const",
+                "enum",
+                "exclusiveMaximum",
+                "exclusiveMinimum",
+                "maxItems",
+                "maxLength",
+                "maxProperties",
+                "maximum",
+                "minItems",
+                "minLength",
+                "minProperties",
+                "minimum",
+                "multipleOf",
+                "pattern",
+                "required",
+                "type",
+                "uniqueItems",
+            ],
+            Vocabulary::Format => &["format"],
+            Vocabulary::Content => &["contentEncoding", "contentMediaType"],
+            Vocabulary::Core
+            | Vocabulary::Unevaluated
+            | Vocabulary::MetaData
+            | Vocabulary::FormatAnnotation => &[],
+        }
+    }
+}
+
+/// Vocabularies enabled by a meta-schema.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Vocabularies(Vec<Vocabulary>);
+
+impl Vocabularies {
+    /// Vocabularies from the `$vocabulary` keyword of `meta_schema`, if it is present.
+    /// Unknown vocabularies are ignored unless they are required.
+    pub(crate) fn from_meta_schema(
+        meta_schema: &Value,
+    ) -> Result<Option<Vocabularies>, CompilationError> {
+        let declared = match meta_schema.get("$vocabulary") {
+            Some(Value::Object(declared)) => declared,
+            Some(_) => return Err(CompilationError::SchemaError),
+            None => return Ok(None),
+        };
+        let mut vocabularies = Vec::with_capacity(declared.len());
+        for (uri, is_required) in declared {
+            match (Vocabulary::from_uri(uri), is_required) {
+                (Some(vocabulary), Value::Bool(_)) => vocabularies.push(vocabulary),
+                (None, Value::Bool(true)) => {
+                    return Err(CompilationError::UnknownVocabulary { uri: uri.clone() })
+                }
+                (None, Value::Bool(false)) => {}
+                (_, _) => return Err(CompilationError::SchemaError),
+            }
+        }
+        Ok(Some(Vocabularies(vocabularies)))
+    }
+
+    /// Whether `keyword` is a part of enabled vocabularies or does not belong to any vocabulary.
+    pub(crate) fn allows(&self, keyword: &str) -> bool {
+        Vocabulary::ALL
+            .iter()
+            .filter(|vocabulary| vocabulary.keywords().contains(&keyword))
+            .all(|vocabulary| self.0.contains(vocabulary))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Vocabularies;
+    use crate::{error::CompilationError, CompilationOptions};
+    use serde_json::{json, Value};
+    use test_case::test_case;
+
+    const META_SCHEMA: &str = "https://example.com/meta";
+
+    fn meta_schema(vocabulary: &Value) -> Value {
+        json!({
+            "$schema": "https://json-schema.org/draft/2019-09/schema",
+            "$id": META_SCHEMA,
+            "$vocabulary": vocabulary
+        })
+    }
+
+    fn compile(vocabulary: &Value, schema: &Value) -> Result<bool, CompilationError> {
+        let mut schema = schema.clone();
+        schema["$schema"] = json!(META_SCHEMA);
+        let compiled = CompilationOptions::default()
+            .with_document(META_SCHEMA.to_string(), meta_schema(vocabulary))
+            .compile(&schema)?;
+        Ok(compiled.is_valid(&json!("foo")))
+    }
+
+    #[test]
+    fn unknown_required_vocabulary() {
+        let vocabulary = json!({
+            "https://json-schema.org/draft/2020-12/vocab/core": true,
+            "https://example.com/vocab/custom": true
+        });
+        let error = compile(&vocabulary, &json!({})).unwrap_err();
+        assert_eq!(
+            error,
+            CompilationError::UnknownVocabulary {
+                uri: "https://example.com/vocab/custom".to_string()
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Unknown required vocabulary: 'https://example.com/vocab/custom'"
+        );
+    }
+
+    #[test]
+    fn unknown_optional_vocabulary() {
+        let vocabulary = json!({
+            "https://json-schema.org/draft/2020-12/vocab/validation": true,
+            "https://example.com/vocab/custom": false
+        });
+        assert_eq!(compile(&vocabulary, &json!({"type": "integer"})), Ok(false));
+    }
+
+    #[test_case(&json!({"type": "integer"}); "validation keyword")]
+    #[test_case(&json!({"not": {"type": "string"}}); "applicator keyword")]
+    #[test_case(&json!({"format": "email"}); "format keyword")]
+    #[test_case(&json!({"contentMediaType": "application/json"}); "content keyword")]
+    fn disabled_vocabulary_keywords_are_ignored(schema: &Value) {
+        // Only the core vocabulary is enabled
+        let vocabulary = json!({"https://json-schema.org/draft/2019-09/vocab/core": true});
+        assert_eq!(compile(&vocabulary, schema), Ok(true));
+    }
+
+    #[test_case("https://json-schema.org/draft/2019-09/vocab/format", true)]
+    #[test_case("https://json-schema.org/draft/2020-12/vocab/format-assertion", true)]
+    #[test_case("https://json-schema.org/draft/2020-12/vocab/format-annotation", false)]
+    fn format_vocabularies(uri: &str, is_asserted: bool) {
+        let vocabulary = json!({ uri: true });
+        assert_eq!(
+            compile(&vocabulary, &json!({"format": "email"})),
+            Ok(!is_asserted)
+        );
+    }
+
+    #[test_case(&json!([]))]
+    #[test_case(&json!({"https://json-schema.org/draft/2020-12/vocab/core": 1}))]
+    fn invalid_vocabulary(vocabulary: &Value) {
+        assert!(Vocabularies::from_meta_schema(&json!({ "$vocabulary": vocabulary })).is_err())
+    }
+
+    #[test]
+    fn without_vocabulary() {
+        assert_eq!(Vocabularies::from_meta_schema(&json!({})), Ok(None));
+        // Meta-schemas without `$vocabulary` enable all keywords
+        let meta_schema = json!({"$id": META_SCHEMA});
+        let schema = json!({"$schema": META_SCHEMA, "type": "integer"});
+        let compiled = CompilationOptions::default()
+            .with_document(META_SCHEMA.to_string(), meta_schema)
+            .compile(&schema)
+            .unwrap();
+        assert!(!compiled.is_valid(&json!("foo")));
+    }
+}
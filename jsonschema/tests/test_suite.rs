@@ -1,3 +1,7 @@
+//! Runs the official JSON Schema Test Suite (the `tests/suite` git submodule).
+//!
+//! Every test case is a separate test named `<draft>_<file>_<group>_<case>`, so results can be
+//! narrowed to a single draft or keyword, e.g. `cargo test --test test_suite draft7_minlength`.
 use json_schema_test_suite::{json_schema_test_suite, TestCase};
 use jsonschema::{Draft, JSONSchema};
 
@@ -45,4 +49,13 @@ fn test_draft(_server_address: &str, test_case: TestCase) {
 
     // Ensure that `JSONSchema::is_valid` is in sync with the validity expectation
     assert_eq!(compiled.is_valid(&test_case.instance), test_case.is_valid);
+    // As well as other validation entry points
+    assert_eq!(
+        compiled.first_error(&test_case.instance).is_ok(),
+        test_case.is_valid
+    );
+    assert_eq!(
+        compiled.apply(&test_case.instance).is_valid(),
+        test_case.is_valid
+    );
 }